@@ -0,0 +1,92 @@
+//! `shaders/` 아래의 GLSL shader를 build할 때 SPIR-V로 컴파일함  
+//! `shaders/21/shader.vert`는 `$OUT_DIR/shaders/21/vert.spv`로 컴파일되므로
+//! chapter에서는 `include_bytes!(concat!(env!("OUT_DIR"), "/shaders/21/vert.spv"))`로 가져다 씀
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use shaderc::{CompileOptions, Compiler, EnvVersion, ShaderKind, TargetEnv};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+
+    // shader를 수정하거나 추가하면 다시 컴파일되도록 함
+    println!("cargo:rerun-if-changed=shaders");
+
+    let compiler = Compiler::new().expect("Failed to create shader compiler.");
+    let mut options = CompileOptions::new().expect("Failed to create shader compile options.");
+    options.set_target_env(TargetEnv::Vulkan, EnvVersion::Vulkan1_0 as u32);
+
+    // ray tracing shader는 SPIR-V 1.4 이상이 필요하므로 Vulkan 1.2 환경으로 컴파일
+    let mut ray_tracing_options =
+        CompileOptions::new().expect("Failed to create shader compile options.");
+    ray_tracing_options.set_target_env(TargetEnv::Vulkan, EnvVersion::Vulkan1_2 as u32);
+
+    compile_shaders(
+        &compiler,
+        &options,
+        &ray_tracing_options,
+        Path::new("shaders"),
+        &out_dir,
+    )
+}
+
+/// `directory` 아래의 shader를 재귀적으로 찾아서 `out_dir`의 같은 위치에 컴파일
+fn compile_shaders(
+    compiler: &Compiler,
+    options: &CompileOptions,
+    ray_tracing_options: &CompileOptions,
+    directory: &Path,
+    out_dir: &Path,
+) -> Result<(), Box<dyn Error>> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            compile_shaders(compiler, options, ray_tracing_options, &path, out_dir)?;
+            continue;
+        }
+
+        // 확장자로 shader stage를 결정하고, shader가 아닌 파일은 건너뜀
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let (kind, options) = match extension {
+            "vert" => (ShaderKind::Vertex, options),
+            "frag" => (ShaderKind::Fragment, options),
+            "comp" => (ShaderKind::Compute, options),
+            "geom" => (ShaderKind::Geometry, options),
+            "tesc" => (ShaderKind::TessControl, options),
+            "tese" => (ShaderKind::TessEvaluation, options),
+            "rgen" => (ShaderKind::RayGeneration, ray_tracing_options),
+            "rmiss" => (ShaderKind::Miss, ray_tracing_options),
+            "rchit" => (ShaderKind::ClosestHit, ray_tracing_options),
+            _ => continue,
+        };
+
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let source = fs::read_to_string(&path)?;
+        let artifact = compiler.compile_into_spirv(
+            &source,
+            kind,
+            &path.to_string_lossy(),
+            "main",
+            Some(options),
+        )?;
+
+        // `shader.vert`는 `vert.spv`로, 그 외의 `name.vert`는 `name_vert.spv`로 저장
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        let file_name = if stem == "shader" {
+            format!("{}.spv", extension)
+        } else {
+            format!("{}_{}.spv", stem, extension)
+        };
+
+        let destination = out_dir.join(path.parent().unwrap()).join(file_name);
+        fs::create_dir_all(destination.parent().unwrap())?;
+        fs::write(destination, artifact.as_binary_u8())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+#version 450
+
+// chapter의 MAX_JOINTS와 같아야 함
+const int MAX_JOINTS = 128;
+
+layout(set = 0, binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+} ubo;
+
+// animation의 현재 시점에서 joint마다 vertex에 적용할 matrix
+layout(set = 0, binding = 1) uniform JointMatrices {
+    mat4 joints[MAX_JOINTS];
+} skin;
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+layout(location = 2) in vec2 inTexCoord;
+layout(location = 3) in uvec4 inJoints;
+layout(location = 4) in vec4 inWeights;
+
+layout(location = 0) out vec3 fragPosition;
+layout(location = 1) out vec3 fragNormal;
+layout(location = 2) out vec2 fragTexCoord;
+
+void main() {
+    // vertex에 영향을 주는 joint의 matrix를 weight로 섞음
+    // weight가 모두 0이면 skin이 적용되지 않는 vertex이므로 그대로 둠
+    mat4 skinMatrix = mat4(1.0);
+    if (dot(inWeights, vec4(1.0)) > 0.0) {
+        skinMatrix = inWeights.x * skin.joints[inJoints.x]
+            + inWeights.y * skin.joints[inJoints.y]
+            + inWeights.z * skin.joints[inJoints.z]
+            + inWeights.w * skin.joints[inJoints.w];
+    }
+
+    // lighting은 world space에서 계산함
+    mat4 model = pcs.model * skinMatrix;
+    vec4 position = model * vec4(inPosition, 1.0);
+    gl_Position = ubo.proj * ubo.view * position;
+    fragPosition = position.xyz;
+    fragNormal = mat3(transpose(inverse(model))) * inNormal;
+    fragTexCoord = inTexCoord;
+}
@@ -0,0 +1,68 @@
+#version 450
+
+// chapter의 MAX_JOINTS, MAX_MORPH_TARGETS와 같아야 함
+const int MAX_JOINTS = 128;
+const int MAX_MORPH_TARGETS = 8;
+
+layout(set = 0, binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+} ubo;
+
+// animation의 현재 시점에서 joint마다 vertex에 적용할 matrix
+layout(set = 0, binding = 1) uniform JointMatrices {
+    mat4 joints[MAX_JOINTS];
+} skin;
+
+// morph target마다 vertex 순서대로 position과 normal의 delta가 번갈아 저장됨
+layout(std430, set = 0, binding = 2) readonly buffer MorphTargets {
+    uint targetCount;
+    uint vertexCount;
+    vec4 deltas[];
+} morph;
+
+// fragment shader의 material factor 뒤에 morph target의 weight가 이어짐
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+    layout(offset = 96) vec4 morphWeights[MAX_MORPH_TARGETS / 4];
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+layout(location = 2) in vec2 inTexCoord;
+layout(location = 3) in uvec4 inJoints;
+layout(location = 4) in vec4 inWeights;
+
+layout(location = 0) out vec3 fragPosition;
+layout(location = 1) out vec3 fragNormal;
+layout(location = 2) out vec2 fragTexCoord;
+
+void main() {
+    // glTF는 morph target을 skin보다 먼저 적용함
+    vec3 morphedPosition = inPosition;
+    vec3 morphedNormal = inNormal;
+    for (uint i = 0; i < morph.targetCount; i++) {
+        float weight = pcs.morphWeights[i / 4][i % 4];
+        uint index = (i * morph.vertexCount + gl_VertexIndex) * 2;
+        morphedPosition += weight * morph.deltas[index].xyz;
+        morphedNormal += weight * morph.deltas[index + 1].xyz;
+    }
+
+    // vertex에 영향을 주는 joint의 matrix를 weight로 섞음
+    // weight가 모두 0이면 skin이 적용되지 않는 vertex이므로 그대로 둠
+    mat4 skinMatrix = mat4(1.0);
+    if (dot(inWeights, vec4(1.0)) > 0.0) {
+        skinMatrix = inWeights.x * skin.joints[inJoints.x]
+            + inWeights.y * skin.joints[inJoints.y]
+            + inWeights.z * skin.joints[inJoints.z]
+            + inWeights.w * skin.joints[inJoints.w];
+    }
+
+    // lighting은 world space에서 계산함
+    mat4 model = pcs.model * skinMatrix;
+    vec4 position = model * vec4(morphedPosition, 1.0);
+    gl_Position = ubo.proj * ubo.view * position;
+    fragPosition = position.xyz;
+    fragNormal = mat3(transpose(inverse(model))) * normalize(morphedNormal);
+    fragTexCoord = inTexCoord;
+}
@@ -0,0 +1,54 @@
+#version 450
+
+// terrain 전체에서 layer texture가 반복되는 횟수
+const float LAYER_TILING = 64.0;
+
+// 주변에서 반사되어 들어오는 빛을 대신하는 일정한 밝기
+const float AMBIENT = 0.15;
+
+// 먼 곳의 LOD가 바뀌는 것이 덜 보이도록 안개를 섞음
+const vec3 FOG_COLOR = vec3(0.6, 0.7, 0.8);
+const float FOG_DENSITY = 0.006;
+
+layout(set = 0, binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    vec4 cameraPosition;
+    // 표면에서 빛을 향하는 방향
+    vec4 lightDirection;
+    vec4 lightColor;
+} ubo;
+
+// splat map의 r, g, b, a channel이 각 layer의 비율임
+layout(set = 1, binding = 0) uniform sampler2D splatMap;
+layout(set = 1, binding = 1) uniform sampler2D sandMap;
+layout(set = 1, binding = 2) uniform sampler2D grassMap;
+layout(set = 1, binding = 3) uniform sampler2D rockMap;
+layout(set = 1, binding = 4) uniform sampler2D snowMap;
+
+layout(location = 0) in vec3 fragPosition;
+layout(location = 1) in vec3 fragNormal;
+layout(location = 2) in vec2 fragTexCoord;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    // 비율의 합이 1이 되지 않아도 밝기가 변하지 않도록 정규화함
+    vec4 weights = texture(splatMap, fragTexCoord);
+    weights /= max(dot(weights, vec4(1.0)), 0.0001);
+
+    // layer texture는 terrain 전체에 여러 번 반복해서 입힘
+    vec2 layerTexCoord = fragTexCoord * LAYER_TILING;
+    vec3 albedo = texture(sandMap, layerTexCoord).rgb * weights.r
+        + texture(grassMap, layerTexCoord).rgb * weights.g
+        + texture(rockMap, layerTexCoord).rgb * weights.b
+        + texture(snowMap, layerTexCoord).rgb * weights.a;
+
+    vec3 normal = normalize(fragNormal);
+    float diffuse = max(dot(normal, ubo.lightDirection.xyz), 0.0);
+    vec3 color = albedo * (ubo.lightColor.rgb * diffuse + AMBIENT);
+
+    float distance = length(ubo.cameraPosition.xyz - fragPosition);
+    float fog = 1.0 - exp(-distance * FOG_DENSITY);
+    outColor = vec4(mix(color, FOG_COLOR, fog), 1.0);
+}
@@ -0,0 +1,22 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+} ubo;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+layout(location = 2) in vec2 inTexCoord;
+
+layout(location = 0) out vec3 fragPosition;
+layout(location = 1) out vec3 fragNormal;
+layout(location = 2) out vec2 fragTexCoord;
+
+void main() {
+    // terrain의 vertex는 이미 world 좌표이므로 model matrix가 필요없음
+    gl_Position = ubo.proj * ubo.view * vec4(inPosition, 1.0);
+    fragPosition = inPosition;
+    fragNormal = inNormal;
+    fragTexCoord = inTexCoord;
+}
@@ -0,0 +1,17 @@
+#version 450
+
+layout(location = 0) in vec4 fragColor;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    // point를 가장자리가 부드럽게 투명해지는 원으로 그림
+    vec2 coord = gl_PointCoord - vec2(0.5);
+    float radius = length(coord);
+    if (radius > 0.5) {
+        discard;
+    }
+
+    float alpha = fragColor.a * (1.0 - smoothstep(0.25, 0.5, radius));
+    outColor = vec4(fragColor.rgb, alpha);
+}
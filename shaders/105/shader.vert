@@ -0,0 +1,26 @@
+#version 450
+
+// 가장 가까이 있을 때에도 point가 너무 커지지 않도록 제한하는 크기(pixel)
+const float MAX_POINT_SIZE = 64.0;
+
+// particle의 world 공간에서의 반지름
+const float PARTICLE_RADIUS = 0.02;
+
+layout(push_constant) uniform PushConstants {
+    mat4 viewProj;
+    // world 공간의 길이를 거리 1에서의 pixel 크기로 바꾸는 값
+    float pointScale;
+} pcs;
+
+// 정렬된 index buffer가 가리키는 순서대로 particle을 읽음
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec4 inColor;
+
+layout(location = 0) out vec4 fragColor;
+
+void main() {
+    gl_Position = pcs.viewProj * vec4(inPosition, 1.0);
+    // 멀리 있는 particle일수록 작게 그림
+    gl_PointSize = clamp(2.0 * PARTICLE_RADIUS * pcs.pointScale / gl_Position.w, 1.0, MAX_POINT_SIZE);
+    fragColor = inColor;
+}
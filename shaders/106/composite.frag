@@ -0,0 +1,22 @@
+#version 450
+
+// binding과 input_attachment_index는 render pass의 composite subpass의 input attachment 순서와 같아야 함
+layout(input_attachment_index = 0, binding = 0) uniform subpassInput accumulationInput;
+layout(input_attachment_index = 1, binding = 1) uniform subpassInput revealageInput;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    float revealage = subpassLoad(revealageInput).r;
+    // 반투명한 fragment가 하나도 없는 pixel은 불투명한 scene을 그대로 남김
+    if (revealage == 1.0) {
+        discard;
+    }
+
+    vec4 accumulation = subpassLoad(accumulationInput);
+    // weight의 합으로 나누면 weight를 반영한 반투명한 색들의 평균이 됨
+    vec3 average = accumulation.rgb / max(accumulation.a, 1e-5);
+
+    // blend state가 (1 - revealage)만큼 평균 색을, revealage만큼 불투명한 scene을 섞음
+    outColor = vec4(average, 1.0 - revealage);
+}
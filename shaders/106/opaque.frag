@@ -0,0 +1,18 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    vec4 lightDirection;
+} ubo;
+
+layout(location = 0) in vec3 fragNormal;
+layout(location = 1) in vec4 fragColor;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    // 방향이 있는 빛 하나와 약한 ambient로 간단한 diffuse lighting을 계산함
+    float diffuse = max(dot(normalize(fragNormal), -ubo.lightDirection.xyz), 0.0);
+    outColor = vec4(fragColor.rgb * (0.2 + 0.8 * diffuse), 1.0);
+}
@@ -0,0 +1,25 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    vec4 lightDirection;
+} ubo;
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+layout(location = 2) in vec4 inColor;
+
+layout(location = 0) out vec3 fragNormal;
+layout(location = 1) out vec4 fragColor;
+
+void main() {
+    gl_Position = ubo.proj * ubo.view * pcs.model * vec4(inPosition, 1.0);
+    // model matrix에는 회전과 이동만 있으므로 normal에 그대로 적용할 수 있음
+    fragNormal = mat3(pcs.model) * inNormal;
+    fragColor = inColor;
+}
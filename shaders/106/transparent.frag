@@ -0,0 +1,36 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    vec4 lightDirection;
+} ubo;
+
+layout(location = 0) in vec3 fragNormal;
+layout(location = 1) in vec4 fragColor;
+
+// accumulation attachment에는 weight를 곱한 premultiplied color와 weight를 더함
+layout(location = 0) out vec4 outAccumulation;
+// revealage attachment에는 뒤의 색이 얼마나 보이는지를 곱함
+layout(location = 1) out float outRevealage;
+
+// McGuire와 Bavoil의 weight function
+// 가깝고 불투명한 fragment일수록 큰 weight를 가져서 평균 색에 더 많이 반영됨
+float weight(float alpha, float depth) {
+    float w = pow(min(1.0, alpha * 10.0) + 0.01, 3.0) * 1e8 * pow(1.0 - depth * 0.9, 3.0);
+    // half float에 더해도 overflow되지 않는 범위로 제한함
+    return clamp(w, 1e-2, 3e3);
+}
+
+void main() {
+    // 뒷면도 그리므로 camera를 향하는 방향으로 normal을 뒤집음
+    vec3 normal = normalize(gl_FrontFacing ? fragNormal : -fragNormal);
+    float diffuse = max(dot(normal, -ubo.lightDirection.xyz), 0.0);
+    vec3 color = fragColor.rgb * (0.4 + 0.6 * diffuse);
+    float alpha = fragColor.a;
+
+    float w = weight(alpha, gl_FragCoord.z);
+    outAccumulation = vec4(color * alpha, alpha) * w;
+    // blend state가 (1 - alpha)를 곱하므로 alpha를 그대로 출력함
+    outRevealage = alpha;
+}
@@ -0,0 +1,32 @@
+#version 450
+
+layout(binding = 1) uniform sampler2D texSampler;
+// depth pre-pass에서 기록한 depth image
+// multisampled image이므로 subpassInputMS로 읽어야 함
+layout(input_attachment_index = 0, binding = 2) uniform subpassInputMS inDepth;
+
+layout(push_constant) uniform PushConstants {
+    layout(offset = 64) float opacity;
+    // 0이 아니면 texture 대신 depth를 grayscale로 출력
+    uint visualizeDepth;
+    float near;
+    float far;
+} pcs;
+
+layout(location = 0) in vec3 fragColor;
+layout(location = 1) in vec2 fragTexCoord;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    if (pcs.visualizeDepth != 0) {
+        // 첫 번째 sample만 읽으므로 sample shading이 필요 없음
+        float depth = subpassLoad(inDepth, 0).r;
+        // perspective projection의 depth는 비선형이므로 view space의 거리로 되돌려서 표시
+        float linear = pcs.near * pcs.far / (pcs.far - depth * (pcs.far - pcs.near));
+        float gray = 1.0 - clamp((linear - pcs.near) / (pcs.far - pcs.near), 0.0, 1.0);
+        outColor = vec4(vec3(gray), 1.0);
+    } else {
+        outColor = vec4(texture(texSampler, fragTexCoord).rgb, pcs.opacity);
+    }
+}
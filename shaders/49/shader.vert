@@ -0,0 +1,30 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+} ubo;
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inColor;
+layout(location = 2) in vec2 inTexCoord;
+// instance마다 하나씩 읽어오는 transform
+// mat4는 vec4 4개로 나누어 location 3~6을 차지함
+layout(location = 3) in mat4 inInstanceModel;
+
+layout(location = 0) out vec3 fragColor;
+layout(location = 1) out vec2 fragTexCoord;
+
+// depth pre-pass와 color pass가 같은 depth 값을 계산하도록 보장
+// 계산 결과가 조금이라도 다르면 color pass의 depth test가 실패할 수 있음
+invariant gl_Position;
+
+void main() {
+    gl_Position = ubo.proj * ubo.view * inInstanceModel * pcs.model * vec4(inPosition, 1.0);
+    fragColor = inColor;
+    fragTexCoord = inTexCoord;
+}
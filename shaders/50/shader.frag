@@ -0,0 +1,29 @@
+#version 450
+
+layout(binding = 1) uniform sampler2D texSampler;
+// depth 비교를 sampler가 대신 해주므로 0.0(그림자) ~ 1.0(빛) 사이의 값을 반환함
+layout(binding = 2) uniform sampler2DShadow shadowMap;
+
+layout(push_constant) uniform PushConstants {
+    layout(offset = 64) float opacity;
+} pcs;
+
+layout(location = 0) in vec3 fragColor;
+layout(location = 1) in vec2 fragTexCoord;
+layout(location = 2) in vec4 fragShadowCoord;
+
+layout(location = 0) out vec4 outColor;
+
+// 그림자 안에서도 완전히 검게 보이지 않도록 남겨두는 밝기
+const float AMBIENT = 0.3;
+
+void main() {
+    // orthographic projection이므로 w는 1이지만 perspective projection에서도 동작하도록 나눔
+    vec3 shadowCoord = fragShadowCoord.xyz / fragShadowCoord.w;
+    // clip space의 xy(-1.0 ~ 1.0)를 texture 좌표(0.0 ~ 1.0)로 변환
+    shadowCoord.xy = shadowCoord.xy * 0.5 + 0.5;
+    float lit = texture(shadowMap, shadowCoord);
+
+    vec3 color = texture(texSampler, fragTexCoord).rgb;
+    outColor = vec4(color * (AMBIENT + (1.0 - AMBIENT) * lit), pcs.opacity);
+}
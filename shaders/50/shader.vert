@@ -0,0 +1,31 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    mat4 lightViewProj;
+} ubo;
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inColor;
+layout(location = 2) in vec2 inTexCoord;
+// instance마다 하나씩 읽어오는 transform
+// mat4는 vec4 4개로 나누어 location 3~6을 차지함
+layout(location = 3) in mat4 inInstanceModel;
+
+layout(location = 0) out vec3 fragColor;
+layout(location = 1) out vec2 fragTexCoord;
+// light의 clip space에서의 위치
+layout(location = 2) out vec4 fragShadowCoord;
+
+void main() {
+    vec4 worldPosition = inInstanceModel * pcs.model * vec4(inPosition, 1.0);
+    gl_Position = ubo.proj * ubo.view * worldPosition;
+    fragColor = inColor;
+    fragTexCoord = inTexCoord;
+    fragShadowCoord = ubo.lightViewProj * worldPosition;
+}
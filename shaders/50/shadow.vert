@@ -0,0 +1,19 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    mat4 lightViewProj;
+} ubo;
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 3) in mat4 inInstanceModel;
+
+// shadow map에는 depth만 기록하므로 fragment shader가 없음
+void main() {
+    gl_Position = ubo.lightViewProj * inInstanceModel * pcs.model * vec4(inPosition, 1.0);
+}
@@ -0,0 +1,12 @@
+#version 450
+
+layout(binding = 1) uniform samplerCube skybox;
+
+layout(location = 0) in vec3 fragDirection;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    // 이 tutorial의 world는 z축이 위를 향하지만 cube map은 y축이 위를 향함
+    outColor = vec4(texture(skybox, fragDirection.xzy).rgb, 1.0);
+}
@@ -0,0 +1,31 @@
+#version 450
+
+// swapchain이 UNORM format이면 hardware가 sRGB로 encode해주지 않으므로 shader에서 직접 encode함
+layout(constant_id = 0) const bool MANUAL_GAMMA = false;
+
+layout(binding = 1) uniform sampler2D texSampler;
+
+layout(push_constant) uniform PushConstants {
+    layout(offset = 64) float opacity;
+} pcs;
+
+layout(location = 0) in vec3 fragColor;
+layout(location = 1) in vec2 fragTexCoord;
+
+layout(location = 0) out vec4 outColor;
+
+vec3 srgbFromLinear(vec3 linear) {
+    bvec3 cutoff = lessThan(linear, vec3(0.0031308));
+    vec3 lower = linear * 12.92;
+    vec3 higher = 1.055 * pow(linear, vec3(1.0 / 2.4)) - 0.055;
+    return mix(higher, lower, cutoff);
+}
+
+void main() {
+    // texture는 sRGB format이므로 sampling한 값은 이미 linear임
+    vec3 color = texture(texSampler, fragTexCoord).rgb;
+    if (MANUAL_GAMMA) {
+        color = srgbFromLinear(color);
+    }
+    outColor = vec4(color, pcs.opacity);
+}
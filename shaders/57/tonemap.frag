@@ -0,0 +1,41 @@
+#version 450
+
+// swapchain이 HDR(scRGB) color space이면 tone mapping 없이 밝기만 맞춰서 출력함
+layout(constant_id = 0) const bool HDR_OUTPUT = false;
+
+// scene을 그린 HDR color attachment
+// 같은 pixel만 읽으므로 sampler 대신 input attachment로 읽음
+layout(input_attachment_index = 0, binding = 0) uniform subpassInput hdrColor;
+
+layout(push_constant) uniform PushConstants {
+    // tone mapping 전에 곱하는 노출 값
+    float exposure;
+    // scene의 1.0을 표시할 밝기(nit)
+    // scRGB에서는 1.0이 80 nit이므로 HDR 출력에서만 사용함
+    float paperWhite;
+} pcs;
+
+layout(location = 0) out vec4 outColor;
+
+// Krzysztof Narkowicz의 ACES filmic tone mapping 근사식
+vec3 acesFilm(vec3 x) {
+    const float a = 2.51;
+    const float b = 0.03;
+    const float c = 2.43;
+    const float d = 0.59;
+    const float e = 0.14;
+    return clamp((x * (a * x + b)) / (x * (c * x + d) + e), 0.0, 1.0);
+}
+
+void main() {
+    vec3 color = subpassLoad(hdrColor).rgb * pcs.exposure;
+    if (HDR_OUTPUT) {
+        // display가 자신의 최대 밝기에 맞춰 tone mapping하므로 linear 값을 그대로 넘김
+        color *= pcs.paperWhite / 80.0;
+    } else {
+        // SDR swapchain은 [0, 1] 범위만 표시할 수 있으므로 밝은 부분을 부드럽게 압축함
+        // SRGB format이므로 저장할 때 hardware가 sRGB로 encode함
+        color = acesFilm(color);
+    }
+    outColor = vec4(color, 1.0);
+}
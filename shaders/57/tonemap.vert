@@ -0,0 +1,8 @@
+#version 450
+
+void main() {
+    // vertex buffer 없이 화면 전체를 덮는 삼각형 하나를 그림
+    // (0, 0), (2, 0), (0, 2)를 clip space의 (-1, -1), (3, -1), (-1, 3)으로 변환함
+    vec2 uv = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+    gl_Position = vec4(uv * 2.0 - 1.0, 0.0, 1.0);
+}
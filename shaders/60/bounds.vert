@@ -0,0 +1,34 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+} ubo;
+
+// model과 같은 회전을 적용하고 model space의 bounding box를 그림
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+    vec4 boundsMin;
+    vec4 boundsMax;
+} pcs;
+
+// model을 그릴 때와 같은 instance buffer에서 transform을 읽음
+layout(location = 3) in mat4 inInstanceModel;
+
+// bounding box의 각 꼭짓점은 bit 0, 1, 2가 x, y, z축에서 max쪽인지를 나타냄
+// vertex buffer 없이 gl_VertexIndex로 12개의 삼각형을 만듦
+const uint CORNERS[36] = uint[](
+    0, 2, 6, 0, 6, 4,
+    1, 5, 7, 1, 7, 3,
+    0, 4, 5, 0, 5, 1,
+    2, 3, 7, 2, 7, 6,
+    0, 1, 3, 0, 3, 2,
+    4, 6, 7, 4, 7, 5
+);
+
+void main() {
+    uint corner = CORNERS[gl_VertexIndex];
+    vec3 t = vec3(corner & 1u, (corner >> 1) & 1u, (corner >> 2) & 1u);
+    vec3 position = mix(pcs.boundsMin.xyz, pcs.boundsMax.xyz, t);
+    gl_Position = ubo.proj * ubo.view * inInstanceModel * pcs.model * vec4(position, 1.0);
+}
@@ -0,0 +1,41 @@
+#version 450
+
+// pipeline을 만들 때 값이 정해지는 상수
+// 같은 SPIR-V로 만든 pipeline이라도 값에 따라 driver가 서로 다른 code를 생성함
+// 0: texture 그대로, 1: grayscale, 2: sepia
+layout(constant_id = 0) const uint COLOR_MODE = 0;
+// 0이 아니면 각 channel을 이 단계 수로 양자화함
+layout(constant_id = 1) const uint POSTERIZE_LEVELS = 0;
+
+layout(binding = 1) uniform sampler2D texSampler;
+
+layout(push_constant) uniform PushConstants {
+    layout(offset = 64) float opacity;
+} pcs;
+
+layout(location = 0) in vec3 fragColor;
+layout(location = 1) in vec2 fragTexCoord;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    vec3 color = texture(texSampler, fragTexCoord).rgb;
+
+    // 상수이므로 사용하지 않는 분기는 pipeline을 만들 때 제거됨
+    if (COLOR_MODE == 1) {
+        color = vec3(dot(color, vec3(0.2126, 0.7152, 0.0722)));
+    } else if (COLOR_MODE == 2) {
+        color = vec3(
+            dot(color, vec3(0.393, 0.769, 0.189)),
+            dot(color, vec3(0.349, 0.686, 0.168)),
+            dot(color, vec3(0.272, 0.534, 0.131))
+        );
+    }
+
+    if (POSTERIZE_LEVELS != 0) {
+        float levels = float(POSTERIZE_LEVELS);
+        color = floor(color * levels) / levels;
+    }
+
+    outColor = vec4(color, pcs.opacity);
+}
@@ -0,0 +1,22 @@
+#version 450
+
+layout(binding = 1) uniform sampler2D texSampler;
+
+layout(location = 0) in vec3 fragColor;
+layout(location = 1) in vec2 fragTexCoord;
+
+layout(location = 0) out vec4 outColor;
+
+// texture 위에 겹칠 checker 무늬의 한 변의 칸 수
+const float CHECKER_SIZE = 256.0;
+
+void main() {
+    // mipmap을 사용하지 않고 가장 큰 level에서 읽어서 멀리 있는 표면의 texture가 깜빡이도록 함
+    vec4 color = textureLod(texSampler, fragTexCoord, 0.0);
+
+    // filtering되지 않는 고주파 무늬는 MSAA만으로는 삼각형 내부의 aliasing을 줄일 수 없음
+    vec2 cell = floor(fragTexCoord * CHECKER_SIZE);
+    float checker = mod(cell.x + cell.y, 2.0);
+
+    outColor = vec4(color.rgb * mix(0.6, 1.0, checker), color.a);
+}
@@ -0,0 +1,19 @@
+#version 450
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inColor;
+layout(location = 2) in vec2 inTexCoord;
+
+layout(location = 0) out vec3 geomColor;
+layout(location = 1) out vec2 geomTexCoord;
+
+void main() {
+    // viewport마다 camera가 다르므로 view와 projection은 geometry shader에서 적용함
+    gl_Position = pcs.model * vec4(inPosition, 1.0);
+    geomColor = inColor;
+    geomTexCoord = inTexCoord;
+}
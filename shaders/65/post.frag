@@ -0,0 +1,36 @@
+#version 450
+
+// POST_EFFECTS의 순서와 같아야 함
+const uint EFFECT_VIGNETTE = 1;
+const uint EFFECT_GRAYSCALE = 2;
+const uint EFFECT_CHROMATIC_ABERRATION = 3;
+
+// scene을 그린 offscreen image
+layout(binding = 0) uniform sampler2D sceneSampler;
+
+layout(push_constant) uniform PushConstants {
+    uint effect;
+} pcs;
+
+layout(location = 0) in vec2 fragTexCoord;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    vec3 color = texture(sceneSampler, fragTexCoord).rgb;
+
+    if (pcs.effect == EFFECT_VIGNETTE) {
+        // 화면 중심에서 멀어질수록 어둡게 함
+        float distance = length(fragTexCoord - 0.5);
+        color *= smoothstep(0.8, 0.3, distance);
+    } else if (pcs.effect == EFFECT_GRAYSCALE) {
+        color = vec3(dot(color, vec3(0.2126, 0.7152, 0.0722)));
+    } else if (pcs.effect == EFFECT_CHROMATIC_ABERRATION) {
+        // 화면 가장자리로 갈수록 red와 blue channel을 서로 반대 방향으로 밀어서 읽음
+        vec2 offset = (fragTexCoord - 0.5) * 0.02;
+        color.r = texture(sceneSampler, fragTexCoord + offset).r;
+        color.b = texture(sceneSampler, fragTexCoord - offset).b;
+    }
+
+    outColor = vec4(color, 1.0);
+}
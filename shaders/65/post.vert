@@ -0,0 +1,11 @@
+#version 450
+
+layout(location = 0) out vec2 fragTexCoord;
+
+void main() {
+    // vertex buffer 없이 화면 전체를 덮는 삼각형 하나를 그림
+    // (0, 0), (2, 0), (0, 2)를 clip space의 (-1, -1), (3, -1), (-1, 3)으로 변환함
+    // 화면 안쪽에서는 texture 좌표가 0.0 ~ 1.0이 됨
+    fragTexCoord = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+    gl_Position = vec4(fragTexCoord * 2.0 - 1.0, 0.0, 1.0);
+}
@@ -0,0 +1,23 @@
+#version 450
+
+layout(binding = 1) uniform sampler2D texSampler;
+
+layout(location = 0) in vec3 fragPosition;
+layout(location = 1) in vec2 fragTexCoord;
+
+// 하나의 subpass에서 여러 color attachment에 동시에 씀
+// location이 subpass의 color attachment 순서와 같아야 함
+layout(location = 0) out vec4 outPosition;
+layout(location = 1) out vec4 outNormal;
+layout(location = 2) out vec4 outAlbedo;
+
+void main() {
+    // model에 normal이 없으므로 인접한 pixel의 world 좌표 차이로 면의 normal을 구함
+    // framebuffer의 y축은 아래를 향하므로 dFdy, dFdx 순서로 외적해야 camera를 향함
+    vec3 normal = normalize(cross(dFdy(fragPosition), dFdx(fragPosition)));
+
+    // w가 1.0이면 geometry가 그려진 pixel이고, clear된 배경은 0.0으로 남음
+    outPosition = vec4(fragPosition, 1.0);
+    outNormal = vec4(normal, 0.0);
+    outAlbedo = vec4(texture(texSampler, fragTexCoord).rgb, 1.0);
+}
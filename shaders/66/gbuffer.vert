@@ -0,0 +1,25 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+} ubo;
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inColor;
+layout(location = 2) in vec2 inTexCoord;
+
+layout(location = 0) out vec3 fragPosition;
+layout(location = 1) out vec2 fragTexCoord;
+
+void main() {
+    // lighting은 world space에서 계산하므로 world 좌표를 G-buffer에 기록함
+    vec4 position = pcs.model * vec4(inPosition, 1.0);
+    gl_Position = ubo.proj * ubo.view * position;
+    fragPosition = position.xyz;
+    fragTexCoord = inTexCoord;
+}
@@ -0,0 +1,83 @@
+#version 450
+
+// LIGHT_COUNT와 같아야 함
+const int LIGHT_COUNT = 4;
+
+// G_BUFFER_VIEWS의 순서와 같아야 함
+const uint VIEW_LIGHTING = 0;
+const uint VIEW_POSITION = 1;
+const uint VIEW_NORMAL = 2;
+const uint VIEW_ALBEDO = 3;
+
+// 빛을 직접 받지 않는 면도 완전히 검게 보이지 않도록 남겨두는 밝기
+const float AMBIENT = 0.1;
+
+// G-buffer subpass가 그린 attachment
+// 같은 pixel만 읽으므로 sampler 대신 input attachment로 읽음
+layout(input_attachment_index = 0, binding = 0) uniform subpassInput gPosition;
+layout(input_attachment_index = 1, binding = 1) uniform subpassInput gNormal;
+layout(input_attachment_index = 2, binding = 2) uniform subpassInput gAlbedo;
+
+struct PointLight {
+    // xyz는 world 좌표, w는 빛이 닿는 반경
+    vec4 position;
+    vec4 color;
+};
+
+layout(binding = 3) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    vec4 viewPosition;
+    PointLight lights[LIGHT_COUNT];
+} ubo;
+
+layout(push_constant) uniform PushConstants {
+    uint view;
+} pcs;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    vec4 position = subpassLoad(gPosition);
+    vec3 normal = subpassLoad(gNormal).xyz;
+    vec3 albedo = subpassLoad(gAlbedo).rgb;
+
+    if (pcs.view == VIEW_POSITION) {
+        outColor = vec4(fract(position.xyz), 1.0);
+        return;
+    } else if (pcs.view == VIEW_NORMAL) {
+        outColor = vec4(normal * 0.5 + 0.5, 1.0);
+        return;
+    } else if (pcs.view == VIEW_ALBEDO) {
+        outColor = vec4(albedo, 1.0);
+        return;
+    }
+
+    // geometry가 그려지지 않은 배경은 lighting을 계산하지 않음
+    if (position.w == 0.0) {
+        outColor = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+
+    vec3 viewDir = normalize(ubo.viewPosition.xyz - position.xyz);
+    vec3 color = albedo * AMBIENT;
+
+    // pixel마다 한 번씩만 계산하므로 빛의 수가 늘어나도 그려진 geometry의 양과 관계없음
+    for (int i = 0; i < LIGHT_COUNT; i++) {
+        vec3 toLight = ubo.lights[i].position.xyz - position.xyz;
+        float dist = length(toLight);
+        vec3 lightDir = toLight / dist;
+
+        // 반경에 가까워질수록 부드럽게 0이 되도록 감쇠시킴
+        float attenuation = clamp(1.0 - dist / ubo.lights[i].position.w, 0.0, 1.0);
+        attenuation *= attenuation;
+
+        float diffuse = max(dot(normal, lightDir), 0.0);
+        vec3 halfway = normalize(lightDir + viewDir);
+        float specular = pow(max(dot(normal, halfway), 0.0), 32.0) * 0.5;
+
+        color += (albedo * diffuse + specular) * ubo.lights[i].color.rgb * attenuation;
+    }
+
+    outColor = vec4(color, 1.0);
+}
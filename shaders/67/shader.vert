@@ -0,0 +1,28 @@
+#version 450
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+} ubo;
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+layout(location = 2) in vec2 inTexCoord;
+
+layout(location = 0) out vec3 fragPosition;
+layout(location = 1) out vec3 fragNormal;
+layout(location = 2) out vec2 fragTexCoord;
+
+void main() {
+    // lighting은 world space에서 계산함
+    vec4 position = pcs.model * vec4(inPosition, 1.0);
+    gl_Position = ubo.proj * ubo.view * position;
+    fragPosition = position.xyz;
+    // model matrix에 크기가 균일하지 않은 scale이 있어도 normal이 표면에 수직이 되도록 inverse transpose를 사용
+    fragNormal = mat3(transpose(inverse(pcs.model))) * inNormal;
+    fragTexCoord = inTexCoord;
+}
@@ -0,0 +1,162 @@
+#version 450
+
+// POINT_LIGHT_COUNT와 같아야 함
+const int POINT_LIGHT_COUNT = 2;
+
+const float PI = 3.14159265359;
+
+struct PointLight {
+    vec4 position;
+    // rgb는 색과 세기를 곱한 값
+    vec4 color;
+};
+
+layout(set = 0, binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    vec4 cameraPosition;
+    // 표면에서 빛을 향하는 방향
+    vec4 lightDirection;
+    vec4 lightColor;
+    PointLight pointLights[POINT_LIGHT_COUNT];
+} ubo;
+
+// material texture는 모든 frame이 함께 사용하는 별도의 descriptor set에서 읽음
+layout(set = 1, binding = 0) uniform sampler2D albedoMap;
+layout(set = 1, binding = 1) uniform sampler2D normalMap;
+layout(set = 1, binding = 2) uniform sampler2D metallicRoughnessMap;
+layout(set = 1, binding = 3) uniform sampler2D occlusionMap;
+
+// 시작할 때 environment map에서 미리 계산해둔 image-based lighting 데이터
+layout(set = 2, binding = 0) uniform samplerCube irradianceMap;
+layout(set = 2, binding = 1) uniform samplerCube prefilteredMap;
+layout(set = 2, binding = 2) uniform sampler2D brdfLut;
+
+// vertex shader의 model matrix 뒤에 material의 factor가 이어짐
+layout(push_constant) uniform PushConstants {
+    layout(offset = 64) vec4 baseColorFactor;
+    float metallicFactor;
+    float roughnessFactor;
+    float normalScale;
+    float occlusionStrength;
+} pcs;
+
+layout(location = 0) in vec3 fragPosition;
+layout(location = 1) in vec3 fragNormal;
+layout(location = 2) in vec2 fragTexCoord;
+
+layout(location = 0) out vec4 outColor;
+
+// vertex에 tangent가 없으므로 인접한 pixel의 위치와 texture 좌표의 변화량으로 tangent space를 구함
+vec3 perturbNormal(vec3 normal) {
+    vec3 tangentNormal = texture(normalMap, fragTexCoord).xyz * 2.0 - 1.0;
+    tangentNormal.xy *= pcs.normalScale;
+
+    vec3 dp1 = dFdx(fragPosition);
+    vec3 dp2 = dFdy(fragPosition);
+    vec2 duv1 = dFdx(fragTexCoord);
+    vec2 duv2 = dFdy(fragTexCoord);
+
+    vec3 dp2perp = cross(dp2, normal);
+    vec3 dp1perp = cross(normal, dp1);
+    vec3 tangent = dp2perp * duv1.x + dp1perp * duv2.x;
+    vec3 bitangent = dp2perp * duv1.y + dp1perp * duv2.y;
+
+    // glTF의 v축은 아래를 향하지만 normal map의 +Y는 위를 향하므로 bitangent를 뒤집음
+    float scale = inversesqrt(max(dot(tangent, tangent), dot(bitangent, bitangent)));
+    mat3 tbn = mat3(tangent * scale, -bitangent * scale, normal);
+    return normalize(tbn * tangentNormal);
+}
+
+// 미세한 면(microfacet)의 normal이 halfway vector와 같은 비율 (GGX/Trowbridge-Reitz)
+float distributionGGX(float NdotH, float roughness) {
+    float a = roughness * roughness;
+    float a2 = a * a;
+    float d = NdotH * NdotH * (a2 - 1.0) + 1.0;
+    return a2 / (PI * d * d);
+}
+
+// 미세한 면끼리 서로 가려서 빛이 줄어드는 비율 (Schlick-GGX)
+float geometrySchlickGGX(float NdotX, float roughness) {
+    float r = roughness + 1.0;
+    float k = r * r / 8.0;
+    return NdotX / (NdotX * (1.0 - k) + k);
+}
+
+// 보는 각도에 따라 반사되는 빛의 비율 (Schlick 근사)
+vec3 fresnelSchlick(float cosTheta, vec3 f0) {
+    return f0 + (1.0 - f0) * pow(clamp(1.0 - cosTheta, 0.0, 1.0), 5.0);
+}
+
+// 주변 전체에서 들어오는 빛은 halfway vector가 하나로 정해지지 않으므로 N·V와 roughness로 근사함
+vec3 fresnelSchlickRoughness(float cosTheta, vec3 f0, float roughness) {
+    return f0 + (max(vec3(1.0 - roughness), f0) - f0) * pow(clamp(1.0 - cosTheta, 0.0, 1.0), 5.0);
+}
+
+// L 방향에서 들어온 빛 중 V 방향으로 반사되는 비율에 입사각의 cos을 곱한 값 (Cook-Torrance)
+vec3 brdf(vec3 N, vec3 V, vec3 L, vec3 albedo, float metallic, float roughness) {
+    vec3 H = normalize(V + L);
+    float NdotL = max(dot(N, L), 0.0);
+    float NdotV = max(dot(N, V), 0.0001);
+
+    // 비금속은 4%만 반사하고, 금속은 albedo의 색으로 반사함
+    vec3 f0 = mix(vec3(0.04), albedo, metallic);
+    vec3 F = fresnelSchlick(max(dot(H, V), 0.0), f0);
+    float D = distributionGGX(max(dot(N, H), 0.0), roughness);
+    float G = geometrySchlickGGX(NdotV, roughness) * geometrySchlickGGX(NdotL, roughness);
+
+    vec3 specular = D * G * F / (4.0 * NdotV * NdotL + 0.0001);
+    // 반사되지 않은 빛만 표면 안으로 들어가 확산되며, 금속은 확산되는 빛이 없음
+    vec3 diffuse = (1.0 - F) * (1.0 - metallic) * albedo / PI;
+
+    return (diffuse + specular) * NdotL;
+}
+
+// environment map에서 모든 방향으로 들어오는 빛 중 V 방향으로 반사되는 빛 (split-sum 근사)
+vec3 ambient(vec3 N, vec3 V, vec3 albedo, float metallic, float roughness) {
+    float NdotV = max(dot(N, V), 0.0001);
+    vec3 f0 = mix(vec3(0.04), albedo, metallic);
+    vec3 F = fresnelSchlickRoughness(NdotV, f0, roughness);
+
+    // diffuse는 normal 방향의 반구에서 들어오는 빛을 미리 적분해둔 irradiance map에서 읽음
+    vec3 diffuse = (1.0 - F) * (1.0 - metallic) * albedo * texture(irradianceMap, N).rgb;
+
+    // specular는 roughness가 클수록 넓은 범위를 적분한 낮은 mip level에서 읽음
+    vec3 R = reflect(-V, N);
+    float maxLod = float(textureQueryLevels(prefilteredMap) - 1);
+    vec3 prefiltered = textureLod(prefilteredMap, R, roughness * maxLod).rgb;
+    vec2 envBrdf = texture(brdfLut, vec2(NdotV, roughness)).rg;
+    vec3 specular = prefiltered * (F * envBrdf.x + envBrdf.y);
+
+    return diffuse + specular;
+}
+
+void main() {
+    vec4 baseColor = texture(albedoMap, fragTexCoord) * pcs.baseColorFactor;
+    vec3 metallicRoughness = texture(metallicRoughnessMap, fragTexCoord).rgb;
+    float metallic = metallicRoughness.b * pcs.metallicFactor;
+    // roughness가 0이면 highlight가 한 점으로 모여서 보이지 않으므로 최소값을 둠
+    float roughness = clamp(metallicRoughness.g * pcs.roughnessFactor, 0.04, 1.0);
+    float occlusion = mix(1.0, texture(occlusionMap, fragTexCoord).r, pcs.occlusionStrength);
+
+    vec3 N = perturbNormal(normalize(fragNormal));
+    vec3 V = normalize(ubo.cameraPosition.xyz - fragPosition);
+
+    vec3 L = normalize(ubo.lightDirection.xyz);
+    vec3 color = brdf(N, V, L, baseColor.rgb, metallic, roughness) * ubo.lightColor.rgb;
+
+    for (int i = 0; i < POINT_LIGHT_COUNT; i++) {
+        vec3 toLight = ubo.pointLights[i].position.xyz - fragPosition;
+        float dist = length(toLight);
+        // point light의 밝기는 거리의 제곱에 반비례함
+        vec3 radiance = ubo.pointLights[i].color.rgb / (dist * dist);
+        color += brdf(N, V, toLight / dist, baseColor.rgb, metallic, roughness) * radiance;
+    }
+
+    color += ambient(N, V, baseColor.rgb, metallic, roughness) * occlusion;
+
+    // 1.0보다 밝은 값을 표시할 수 있도록 Reinhard tone mapping으로 압축함
+    // SRGB swapchain이므로 저장할 때 hardware가 sRGB로 encode함
+    color = color / (color + 1.0);
+    outColor = vec4(color, baseColor.a);
+}
@@ -0,0 +1,17 @@
+#version 450
+
+// equirectangular image를 변환한 cube map
+// world와 같이 z축이 위를 향하도록 변환했으므로 방향을 그대로 사용함
+layout(set = 2, binding = 3) uniform samplerCube environmentMap;
+
+layout(location = 0) in vec3 fragDirection;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    vec3 color = texture(environmentMap, fragDirection).rgb;
+
+    // model과 같은 tone mapping을 적용해야 배경과 반사된 환경의 밝기가 일치함
+    color = color / (color + 1.0);
+    outColor = vec4(color, 1.0);
+}
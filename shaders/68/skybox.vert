@@ -0,0 +1,42 @@
+#version 450
+
+layout(set = 0, binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+} ubo;
+
+// 원점을 중심으로 하는 cube의 꼭짓점
+// index의 0, 1, 2번 bit가 각각 x, y, z 좌표의 부호를 나타냄
+const vec3 CORNERS[8] = vec3[](
+    vec3(-1.0, -1.0, -1.0),
+    vec3( 1.0, -1.0, -1.0),
+    vec3(-1.0,  1.0, -1.0),
+    vec3( 1.0,  1.0, -1.0),
+    vec3(-1.0, -1.0,  1.0),
+    vec3( 1.0, -1.0,  1.0),
+    vec3(-1.0,  1.0,  1.0),
+    vec3( 1.0,  1.0,  1.0)
+);
+
+// 면마다 삼각형 2개
+// model과 같이 바깥에서 보았을 때 반시계 방향이므로 cube 안에서는 뒷면이 보임
+const int INDICES[36] = int[](
+    1, 3, 7, 1, 7, 5, // +X
+    0, 4, 6, 0, 6, 2, // -X
+    2, 6, 7, 2, 7, 3, // +Y
+    0, 1, 5, 0, 5, 4, // -Y
+    4, 5, 7, 4, 7, 6, // +Z
+    0, 2, 3, 0, 3, 1  // -Z
+);
+
+layout(location = 0) out vec3 fragDirection;
+
+void main() {
+    vec3 position = CORNERS[INDICES[gl_VertexIndex]];
+    fragDirection = position;
+
+    // camera가 움직여도 skybox가 항상 같은 거리에 있도록 view matrix의 이동을 제거하고 회전만 사용
+    vec4 clipPosition = ubo.proj * mat4(mat3(ubo.view)) * vec4(position, 1.0);
+    // z를 w로 바꾸면 perspective divide 후의 depth가 항상 1.0(가장 먼 값)이 됨
+    gl_Position = clipPosition.xyww;
+}
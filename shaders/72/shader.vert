@@ -0,0 +1,40 @@
+#version 450
+#extension GL_EXT_buffer_reference : require
+#extension GL_EXT_scalar_block_layout : require
+
+// Rust의 Vertex와 같은 순서로 빈틈없이 저장되어 있으므로 scalar layout으로 읽음
+struct Vertex {
+    vec3 position;
+    vec3 color;
+    vec2 texCoord;
+};
+
+// buffer reference는 descriptor 없이 device address로 접근하는 buffer의 pointer 역할을 함
+layout(buffer_reference, scalar) readonly buffer VertexBuffer {
+    Vertex vertices[];
+};
+
+layout(buffer_reference, scalar) readonly buffer UniformBuffer {
+    mat4 view;
+    mat4 proj;
+};
+
+// buffer reference는 8 byte 주소이므로 model matrix 바로 뒤에 이어서 전달함
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+    VertexBuffer vertexBuffer;
+    UniformBuffer uniformBuffer;
+} pcs;
+
+layout(location = 0) out vec3 fragColor;
+layout(location = 1) out vec2 fragTexCoord;
+
+void main() {
+    // vertex input을 사용하지 않으므로 index buffer에서 읽은 index로 vertex를 직접 가져옴
+    Vertex vertex = pcs.vertexBuffer.vertices[gl_VertexIndex];
+    UniformBuffer ubo = pcs.uniformBuffer;
+
+    gl_Position = ubo.proj * ubo.view * pcs.model * vec4(vertex.position, 1.0);
+    fragColor = vertex.color;
+    fragTexCoord = vertex.texCoord;
+}
@@ -0,0 +1,21 @@
+#version 450
+
+layout(location = 0) in float inHeight;
+layout(location = 1) in vec3 inNormal;
+
+layout(location = 0) out vec4 outColor;
+
+// 위쪽에서 비스듬히 비추는 directional light
+const vec3 LIGHT_DIRECTION = normalize(vec3(-1.0, -0.5, 1.0));
+
+void main() {
+    // 높이에 따라 풀, 흙, 눈의 색을 섞음
+    vec3 grass = vec3(0.2, 0.5, 0.2);
+    vec3 dirt = vec3(0.5, 0.4, 0.3);
+    vec3 snow = vec3(0.95, 0.95, 0.95);
+    vec3 color = mix(grass, dirt, smoothstep(0.3, 0.6, inHeight));
+    color = mix(color, snow, smoothstep(0.75, 0.9, inHeight));
+
+    float diffuse = max(dot(normalize(inNormal), LIGHT_DIRECTION), 0.0);
+    outColor = vec4(color * (0.2 + 0.8 * diffuse), 1.0);
+}
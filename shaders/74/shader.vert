@@ -0,0 +1,12 @@
+#version 450
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec2 inTexCoord;
+
+layout(location = 0) out vec2 outTexCoord;
+
+void main() {
+    // 변환은 displacement가 끝난 뒤 tessellation evaluation shader에서 함
+    gl_Position = vec4(inPosition, 1.0);
+    outTexCoord = inTexCoord;
+}
@@ -0,0 +1,17 @@
+#version 450
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+} pcs;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 3) in vec3 inNormal;
+
+layout(location = 0) out vec3 geomNormal;
+
+void main() {
+    // view, projection 변환은 normal 선분을 만든 뒤 geometry shader에서 함
+    gl_Position = pcs.model * vec4(inPosition, 1.0);
+    // model matrix는 회전과 이동만 포함하므로 normal에도 그대로 적용할 수 있음
+    geomNormal = mat3(pcs.model) * inNormal;
+}
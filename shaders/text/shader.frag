@@ -0,0 +1,14 @@
+#version 450
+
+layout(binding = 0) uniform sampler2D atlas;
+
+layout(location = 0) in vec4 fragColor;
+layout(location = 1) in vec2 fragTexCoord;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    // atlas에는 glyph가 pixel을 덮는 비율(coverage)만 R channel에 저장되어 있음
+    float coverage = texture(atlas, fragTexCoord).r;
+    outColor = vec4(fragColor.rgb, fragColor.a * coverage);
+}
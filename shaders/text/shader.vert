@@ -0,0 +1,20 @@
+#version 450
+
+// 화면 크기(pixel 단위)로 glyph quad의 좌표를 clip space로 변환
+layout(push_constant) uniform PushConstants {
+    vec2 screenSize;
+} pcs;
+
+layout(location = 0) in vec2 inPosition;
+layout(location = 1) in vec2 inTexCoord;
+layout(location = 2) in vec4 inColor;
+
+layout(location = 0) out vec4 fragColor;
+layout(location = 1) out vec2 fragTexCoord;
+
+void main() {
+    // 화면 좌표와 Vulkan 모두 y축이 아래를 향하므로 뒤집을 필요가 없음
+    gl_Position = vec4(2.0 * inPosition / pcs.screenSize - 1.0, 0.0, 1.0);
+    fragColor = inColor;
+    fragTexCoord = inTexCoord;
+}
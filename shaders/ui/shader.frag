@@ -0,0 +1,26 @@
+#version 450
+
+// swapchain이 UNORM format이면 hardware가 sRGB로 encode해주지 않으므로 shader에서 직접 encode함
+layout(constant_id = 0) const bool ENCODE_SRGB = false;
+
+layout(binding = 0) uniform sampler2D texSampler;
+
+layout(location = 0) in vec4 fragColor;
+layout(location = 1) in vec2 fragTexCoord;
+
+layout(location = 0) out vec4 outColor;
+
+vec3 srgbFromLinear(vec3 linear) {
+    bvec3 cutoff = lessThan(linear, vec3(0.0031308));
+    vec3 lower = linear * 12.92;
+    vec3 higher = 1.055 * pow(linear, vec3(1.0 / 2.4)) - 0.055;
+    return mix(higher, lower, cutoff);
+}
+
+void main() {
+    // vertex color와 texture 모두 premultiplied alpha임
+    outColor = fragColor * texture(texSampler, fragTexCoord);
+    if (ENCODE_SRGB) {
+        outColor.rgb = srgbFromLinear(outColor.rgb);
+    }
+}
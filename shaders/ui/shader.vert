@@ -0,0 +1,28 @@
+#version 450
+
+// 화면 크기(point 단위)로 egui의 좌표를 clip space로 변환
+layout(push_constant) uniform PushConstants {
+    vec2 screenSize;
+} pcs;
+
+layout(location = 0) in vec2 inPosition;
+layout(location = 1) in vec2 inTexCoord;
+layout(location = 2) in vec4 inColor;
+
+layout(location = 0) out vec4 fragColor;
+layout(location = 1) out vec2 fragTexCoord;
+
+// egui의 vertex color는 sRGB로 저장되어 있으므로 linear로 변환해야 sRGB swapchain에서 원래 색이 나옴
+vec3 linearFromSrgb(vec3 srgb) {
+    bvec3 cutoff = lessThan(srgb, vec3(0.04045));
+    vec3 lower = srgb / 12.92;
+    vec3 higher = pow((srgb + 0.055) / 1.055, vec3(2.4));
+    return mix(higher, lower, cutoff);
+}
+
+void main() {
+    // egui와 Vulkan 모두 y축이 아래를 향하므로 뒤집을 필요가 없음
+    gl_Position = vec4(2.0 * inPosition / pcs.screenSize - 1.0, 0.0, 1.0);
+    fragColor = vec4(linearFromSrgb(inColor.rgb), inColor.a);
+    fragTexCoord = inTexCoord;
+}
@@ -313,9 +313,7 @@ impl QueueFamilyIndices {
         if let Some(graphics) = graphics {
             Ok(Self { graphics })
         } else {
-            Err(anyhow!(SuitabilityError(
-                "Missing required queue families."
-            )))
+            Err(anyhow!(SuitabilityError("required queue families")))
         }
     }
 }
@@ -319,9 +319,10 @@ impl App {
                 .destroy_debug_utils_messenger_ext(self.data.messenger, None);
         }
 
+        self.device.destroy_device(None);
+        // device가 파괴된 후에 instance를 파괴해야 함
         // 프로그램이 종료되면 인스턴스를 파괴해야 함
         self.instance.destroy_instance(None);
-        self.device.destroy_device(None);
     }
 }
 
@@ -362,9 +363,7 @@ impl QueueFamilyIndices {
         if let Some(graphics) = graphics {
             Ok(Self { graphics })
         } else {
-            Err(anyhow!(SuitabilityError(
-                "Missing required queue families."
-            )))
+            Err(anyhow!(SuitabilityError("required queue families")))
         }
     }
 }
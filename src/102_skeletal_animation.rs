@@ -0,0 +1,2606 @@
+#![allow(
+    dead_code,
+    unused_variables,
+    clippy::too_many_arguments,
+    clippy::unnecessary_wraps
+)]
+
+use anyhow::{anyhow, Result};
+use cgmath::{point3, vec3, vec4, Deg, InnerSpace, SquareMatrix};
+use log::*;
+use vulkanalia::loader::{LibloadingLoader, LIBRARY};
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::window as vk_window;
+use winit::dpi::LogicalSize;
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoop;
+use winit::window::{Window, WindowBuilder};
+
+use vulkan_tutorial::animation::{Animation, Skeleton};
+use vulkan_tutorial::camera::{Camera, CameraController};
+use vulkan_tutorial::config;
+use vulkan_tutorial::debug::{cmd_begin_label, cmd_end_label, set_object_name};
+use vulkan_tutorial::device::{
+    create_logical_device, pick_physical_device, QueueFamilyIndices, SuitabilityError,
+};
+use vulkan_tutorial::error::RenderError;
+use vulkan_tutorial::gltf_model::{load_gltf, Material, MaterialImage, Model};
+use vulkan_tutorial::instance::create_instance;
+use vulkan_tutorial::pipeline::create_shader_module;
+use vulkan_tutorial::profiler::GpuProfiler;
+use vulkan_tutorial::swapchain::{
+    create_render_finished_semaphores, create_swapchain, create_swapchain_image_views, Swapchain,
+    SwapchainOptions,
+};
+use vulkan_tutorial::timer::FrameTimer;
+
+use vulkanalia::vk::ExtDebugUtilsExtension;
+use vulkanalia::vk::KhrSurfaceExtension;
+use vulkanalia::vk::KhrSwapchainExtension;
+
+use std::mem::size_of;
+use std::ptr::copy_nonoverlapping as memcpy;
+use std::time::{Duration, Instant};
+
+type Vec2 = cgmath::Vector2<f32>;
+type Vec3 = cgmath::Vector3<f32>;
+type Vec4 = cgmath::Vector4<f32>;
+type Mat4 = cgmath::Matrix4<f32>;
+
+/// 동시에 실행될 frame의 수
+const MAX_FRAMES_IN_FLIGHT: usize = 2;
+
+/// frame마다 GPU time을 측정할 수 있는 최대 구간의 수
+const MAX_GPU_SCOPES: u32 = 8;
+
+/// window title  
+/// 뒤에 frame 통계를 덧붙여서 표시함
+const TITLE: &str = "Vulkan Tutorial (Rust)";
+
+/// 화면에 그릴 glTF model의 경로  
+/// skin과 animation이 있는 model이어야 움직이는 모습을 볼 수 있음
+const MODEL_PATH: &str = "CesiumMan.glb";
+
+/// skin이 가질 수 있는 최대 joint 수  
+/// vertex shader의 `MAX_JOINTS`와 같아야 하며, joint matrix buffer가 uniform buffer의 최소 보장 크기(16KB)를 넘지 않도록 정함
+const MAX_JOINTS: usize = 128;
+
+/// model을 비추는 point light의 수  
+/// fragment shader의 `POINT_LIGHT_COUNT`와 같아야 함
+const POINT_LIGHT_COUNT: usize = 2;
+
+/// Our Vulkan app.  
+/// Vulkan 프로그램동안 setup, rendering, destruction로직을 구현하는 구조체  
+#[derive(Clone, Debug)]
+struct App {
+    // vulkan entry point를 저장하기 위한 필드
+    entry: Entry,
+    // vulkan instance를 저장하기 위한 필드
+    instance: Instance,
+    data: AppData,
+    device: Device,
+    // frame track을 유지하기 위한 필드
+    frame: usize,
+    // window의 크기가 변경되었는지 추적하기 위한 필드
+    // 모든 driver가 resize시 OUT_OF_DATE_KHR를 보장하지는 않으므로 직접 추적함
+    resized: bool,
+    // 애니메이션을 위해 앱이 시작된 시간을 저장하는 필드
+    start: Instant,
+    // uniform buffer에 전달할 view와 projection matrix를 만드는 camera
+    camera: Camera,
+    // window event를 받아서 camera를 움직이는 controller
+    camera_controller: CameraController,
+    // model의 joint와 node hierarchy, skin이 없으면 None
+    skeleton: Option<Skeleton>,
+    // skeleton에 재생할 animation, model에 animation이 없으면 rest pose로 그림
+    animation: Option<Animation>,
+    // frame time과 FPS를 측정하는 timer
+    frame_timer: FrameTimer,
+    // 가장 최근에 읽어온 구간별 GPU time
+    gpu_timings: Vec<(String, Duration)>,
+}
+
+impl App {
+    /// Creates our Vulkan app.
+    unsafe fn create(window: &Window) -> Result<Self> {
+        // Vulkan command를 Vulkan shared library에서 로드하기 위해 사용됨
+        let loader = LibloadingLoader::new(LIBRARY)?;
+        let entry = Entry::new(loader).map_err(|b| anyhow!("{}", b))?;
+        let mut data = AppData::default();
+        let (instance, messenger) = create_instance(window, &entry)?;
+        data.messenger = messenger;
+
+        data.surface = vk_window::create_surface(&instance, &window, &window)?;
+
+        data.physical_device = pick_physical_device(&instance, data.surface, |i, p| {
+            check_physical_device_features(i, p)
+        })?;
+        data.msaa_samples = get_max_msaa_samples(&instance, &data);
+
+        // anisotropic filtering을 사용하기 위해 활성화
+        let features = vk::PhysicalDeviceFeatures::builder().sampler_anisotropy(true);
+        let (device, graphics_queue, present_queue) = create_logical_device(
+            &entry,
+            &instance,
+            data.surface,
+            data.physical_device,
+            &features,
+        )?;
+        data.graphics_queue = graphics_queue;
+        data.present_queue = present_queue;
+        data.swapchain = create_swapchain(
+            window,
+            &instance,
+            &device,
+            data.surface,
+            data.physical_device,
+            &SwapchainOptions::from_config()?,
+        )?;
+        data.swapchain_image_views = create_swapchain_image_views(&device, &data.swapchain)?;
+        create_render_pass(&instance, &device, &mut data)?;
+        create_descriptor_set_layout(&device, &mut data)?;
+        create_pipeline(&device, &mut data)?;
+        create_command_pools(&instance, &device, &mut data)?;
+        create_color_objects(&instance, &device, &mut data)?;
+        create_depth_objects(&instance, &device, &mut data)?;
+        create_framebuffers(&device, &mut data)?;
+        // glTF 파일에서 mesh와 material을 읽음
+        let model = load_gltf(config::get()?.resource(MODEL_PATH))?;
+        create_material_textures(&instance, &device, &mut data, &model.material)?;
+        create_texture_sampler(&device, &mut data)?;
+        load_model(&mut data, &model)?;
+        create_geometry_buffer(&instance, &device, &mut data)?;
+        create_vertex_buffer(&instance, &device, &mut data)?;
+        create_index_buffer(&instance, &device, &mut data)?;
+        create_uniform_buffers(&instance, &device, &mut data)?;
+        create_descriptor_pool(&device, &mut data)?;
+        create_descriptor_sets(&device, &mut data)?;
+        create_command_buffers(&device, &mut data)?;
+        create_sync_objects(&device, &mut data)?;
+        create_profiler(&instance, &device, &mut data)?;
+        set_object_names(&instance, &device, &data)?;
+        set_swapchain_object_names(&instance, &device, &data)?;
+
+        // 첫 번째 animation을 반복해서 재생함
+        let animation = model.animations.into_iter().next();
+        match &animation {
+            Some(animation) => info!(
+                "Playing animation {:?} ({:.2}s).",
+                animation.name, animation.duration
+            ),
+            None => info!("Model has no animation, drawing the rest pose."),
+        }
+
+        Ok(Self {
+            entry,
+            instance,
+            data,
+            device,
+            frame: 0,
+            resized: false,
+            start: Instant::now(),
+            // (3, 0, 0.5)에서 원점을 바라보는 위치에서 시작
+            camera: Camera::look_at(point3(3.0, 0.0, 1.0), point3(0.0, 0.0, 0.75)),
+            camera_controller: CameraController::default(),
+            skeleton: model.skeleton,
+            animation,
+            frame_timer: FrameTimer::default(),
+            gpu_timings: vec![],
+        })
+    }
+
+    /// Renders a frame for our Vulkan app.  
+    /// swapchain이나 surface를 다시 생성해야 하는 경우 `RenderError`로 알려서 event loop에서 처리하도록 함
+    unsafe fn render(&mut self, window: &Window) -> Result<(), RenderError> {
+        // 크기가 0인 swapchain은 만들 수 없으므로 window가 복원될 때까지 건너뜀
+        let size = window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            return Ok(());
+        }
+
+        // 지난 frame 이후의 입력을 camera에 반영
+        let dt = self.frame_timer.tick().as_secs_f32();
+        self.camera_controller.update(&mut self.camera, dt);
+
+        // frame이 끝날 때 까지 대기
+        self.device
+            .wait_for_fences(&[self.data.in_flight_fences[self.frame]], true, u64::MAX)?;
+
+        // 이 frame이 이전에 기록한 timestamp는 fence를 기다렸으므로 이미 사용할 수 있음
+        let gpu_timings = self.data.profiler.read(&self.device, self.frame)?;
+        // 가장 바깥쪽 구간이 frame 전체의 GPU time임
+        if let Some((_, gpu_time)) = gpu_timings.first() {
+            self.frame_timer.record_gpu_time(*gpu_time);
+        }
+        if !gpu_timings.is_empty() {
+            self.gpu_timings = gpu_timings;
+        }
+
+        let result = self.device.acquire_next_image_khr(
+            self.data.swapchain.handle,
+            u64::MAX,
+            self.data.image_available_semaphores[self.frame],
+            vk::Fence::null(),
+        );
+
+        // swapchain이 surface와 더 이상 호환되지 않으면 RenderError::OutOfDate로 변환되어 반환됨
+        let image_index = result?.0 as usize;
+
+        if !self.data.images_in_flight[image_index as usize].is_null() {
+            self.device.wait_for_fences(
+                &[self.data.images_in_flight[image_index as usize]],
+                true,
+                u64::MAX,
+            )?;
+        }
+
+        self.data.images_in_flight[image_index as usize] = self.data.in_flight_fences[self.frame];
+
+        // image를 사용하는 이전 frame이 끝났으므로 uniform buffer를 안전하게 갱신할 수 있음
+        self.update_uniform_buffer(image_index)?;
+        // 현재 frame의 fence를 기다렸으므로 command buffer를 다시 기록할 수 있음
+        let command_buffer = self.update_command_buffer(image_index)?;
+
+        let wait_semaphores = &[self.data.image_available_semaphores[self.frame]];
+        let wait_stages = &[vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
+        let command_buffers = &[command_buffer];
+        let signal_semaphores = &[self.data.render_finished_semaphores[image_index]];
+        let submit_info = vk::SubmitInfo::builder()
+            .wait_semaphores(wait_semaphores)
+            .wait_dst_stage_mask(wait_stages)
+            .command_buffers(command_buffers)
+            .signal_semaphores(signal_semaphores);
+
+        self.device
+            .reset_fences(&[self.data.in_flight_fences[self.frame]])?;
+
+        self.device.queue_submit(
+            self.data.graphics_queue,
+            &[submit_info],
+            self.data.in_flight_fences[self.frame],
+        )?;
+
+        let swapchains = &[self.data.swapchain.handle];
+        let image_indices = &[image_index as u32];
+        let present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(signal_semaphores)
+            .swapchains(swapchains)
+            .image_indices(image_indices);
+
+        let result = self
+            .device
+            .queue_present_khr(self.data.present_queue, &present_info);
+
+        // command buffer는 이미 제출되었으므로 present의 결과와 관계없이 다음 frame으로 넘어감
+        self.frame = (self.frame + 1) % MAX_FRAMES_IN_FLIGHT;
+
+        // 1초마다 최근 frame들의 평균 통계를 window title에 표시
+        // 구간별 GPU time은 title에 모두 표시하기에는 길어서 log로 남김
+        if let Some(stats) = self.frame_timer.report() {
+            window.set_title(&format!("{} - {}", TITLE, stats));
+            for (name, time) in &self.gpu_timings {
+                info!("GPU `{}`: {:.3} ms", name, time.as_secs_f64() * 1000.0);
+            }
+        }
+
+        // present가 끝난 뒤에 확인해야 semaphore가 올바른 상태로 남음
+        if self.resized {
+            self.resized = false;
+            return Err(RenderError::OutOfDate);
+        }
+
+        match result? {
+            vk::SuccessCode::SUBOPTIMAL_KHR => Err(RenderError::Suboptimal),
+            _ => Ok(()),
+        }
+    }
+
+    /// swapchain과 swapchain에 의존하는 오브젝트들을 다시 생성  
+    /// window의 크기가 변경되는 등 surface가 변경되면 기존 swapchain을 더 이상 사용할 수 없음
+    unsafe fn recreate_swapchain(&mut self, window: &Window) -> Result<()> {
+        // 사용중인 리소스를 건드리지 않도록 device가 idle상태가 될 때 까지 대기
+        self.device.device_wait_idle()?;
+        let format = self.data.swapchain.format;
+        self.destroy_swapchain();
+        self.create_swapchain_objects(window, format)
+    }
+
+    /// surface와 surface에 의존하는 swapchain을 다시 생성  
+    /// surface가 lost 상태가 되면 swapchain을 다시 생성하는 것만으로는 복구할 수 없음
+    unsafe fn recreate_surface(&mut self, window: &Window) -> Result<()> {
+        self.device.device_wait_idle()?;
+        let format = self.data.swapchain.format;
+        // swapchain은 surface에 의존하므로 surface보다 먼저 파괴해야 함
+        self.destroy_swapchain();
+        self.instance.destroy_surface_khr(self.data.surface, None);
+        self.data.surface = vk_window::create_surface(&self.instance, &window, &window)?;
+        // 같은 window에 대한 surface이므로 기존 physical device와 queue family를 그대로 사용할 수 있다고 가정함
+        self.create_swapchain_objects(window, format)
+    }
+
+    /// swapchain과 swapchain에 의존하는 오브젝트들을 생성  
+    /// `format`은 이전 swapchain의 image format으로, 바뀐 경우에만 render pass와 pipeline을 다시 생성함
+    unsafe fn create_swapchain_objects(
+        &mut self,
+        window: &Window,
+        format: vk::Format,
+    ) -> Result<()> {
+        self.data.swapchain = create_swapchain(
+            window,
+            &self.instance,
+            &self.device,
+            self.data.surface,
+            self.data.physical_device,
+            &SwapchainOptions::from_config()?,
+        )?;
+        self.data.swapchain_image_views =
+            create_swapchain_image_views(&self.device, &self.data.swapchain)?;
+        // viewport와 scissor는 dynamic state이므로 pipeline은 그대로 사용할 수 있음
+        // swapchain image format이 바뀐 경우에만 render pass와 pipeline을 다시 생성
+        if self.data.swapchain.format != format {
+            self.destroy_pipeline();
+            create_render_pass(&self.instance, &self.device, &mut self.data)?;
+            create_pipeline(&self.device, &mut self.data)?;
+        }
+        // color image와 depth image는 swapchain image와 같은 크기여야 함
+        create_color_objects(&self.instance, &self.device, &mut self.data)?;
+        create_depth_objects(&self.instance, &self.device, &mut self.data)?;
+        create_framebuffers(&self.device, &mut self.data)?;
+        // uniform buffer와 descriptor set은 swapchain image마다 하나씩 존재
+        create_uniform_buffers(&self.instance, &self.device, &mut self.data)?;
+        create_descriptor_pool(&self.device, &mut self.data)?;
+        create_descriptor_sets(&self.device, &mut self.data)?;
+        // swapchain image의 개수가 바뀔 수 있음
+        self.data
+            .images_in_flight
+            .resize(self.data.swapchain.images.len(), vk::Fence::null());
+        // swapchain image마다 존재하는 render finished semaphore도 image의 개수에 맞게 다시 생성
+        create_render_finished_semaphores(
+            &self.device,
+            &self.data.swapchain,
+            &mut self.data.render_finished_semaphores,
+        )?;
+        // 새로 생성된 오브젝트에도 이름을 붙임
+        set_swapchain_object_names(&self.instance, &self.device, &self.data)?;
+        Ok(())
+    }
+
+    /// 현재 frame의 command buffer를 image_index에 해당하는 framebuffer에 그리도록 다시 기록  
+    /// 매 frame 기록하므로 push constant로 전달하는 model matrix를 시간에 따라 바꿀 수 있음
+    unsafe fn update_command_buffer(&mut self, image_index: usize) -> Result<vk::CommandBuffer> {
+        // command pool을 reset하면 pool에서 할당된 command buffer가 모두 초기 상태로 돌아감
+        let command_pool = self.data.command_pools[self.frame];
+        self.device
+            .reset_command_pool(command_pool, vk::CommandPoolResetFlags::empty())?;
+
+        let command_buffer = self.data.command_buffers[self.frame];
+
+        // 한 번 제출된 후 다시 기록되므로 ONE_TIME_SUBMIT을 지정
+        let info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+
+        self.device.begin_command_buffer(command_buffer, &info)?;
+        cmd_begin_label(
+            &self.instance,
+            command_buffer,
+            &format!("Frame {}", self.frame),
+            [0.5, 0.5, 0.5, 1.0],
+        )?;
+
+        // query reset은 render pass 밖에서 기록해야 함
+        self.data
+            .profiler
+            .begin_frame(&self.device, command_buffer, self.frame);
+        self.data
+            .profiler
+            .begin_scope(&self.device, command_buffer, "Frame");
+
+        let render_area = vk::Rect2D::builder()
+            .offset(vk::Offset2D::default())
+            .extent(self.data.swapchain.extent);
+
+        let color_clear_value = vk::ClearValue {
+            color: vk::ClearColorValue {
+                float32: [0.0, 0.0, 0.0, 1.0],
+            },
+        };
+
+        // depth는 가장 먼 값인 1.0으로 초기화
+        let depth_clear_value = vk::ClearValue {
+            depth_stencil: vk::ClearDepthStencilValue {
+                depth: 1.0,
+                stencil: 0,
+            },
+        };
+
+        let clear_values = &[color_clear_value, depth_clear_value];
+        let info = vk::RenderPassBeginInfo::builder()
+            .render_pass(self.data.render_pass)
+            .framebuffer(self.data.framebuffers[image_index])
+            .render_area(render_area)
+            .clear_values(clear_values);
+
+        cmd_begin_label(
+            &self.instance,
+            command_buffer,
+            "Main Render Pass",
+            [0.2, 0.6, 1.0, 1.0],
+        )?;
+        self.data
+            .profiler
+            .begin_scope(&self.device, command_buffer, "Main Render Pass");
+        self.device
+            .cmd_begin_render_pass(command_buffer, &info, vk::SubpassContents::INLINE);
+
+        self.device.cmd_bind_pipeline(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            self.data.pipeline,
+        );
+
+        // pipeline에서 dynamic state로 지정한 viewport와 scissor를 현재 swapchain 크기에 맞게 설정
+        let viewport = vk::Viewport::builder()
+            .x(0.0)
+            .y(0.0)
+            .width(self.data.swapchain.extent.width as f32)
+            .height(self.data.swapchain.extent.height as f32)
+            .min_depth(0.0)
+            .max_depth(1.0);
+
+        let scissor = vk::Rect2D::builder()
+            .offset(vk::Offset2D { x: 0, y: 0 })
+            .extent(self.data.swapchain.extent);
+
+        self.device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+        self.device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+
+        // 같은 buffer를 offset만 다르게 해서 vertex buffer와 index buffer로 bind
+        self.device.cmd_bind_vertex_buffers(
+            command_buffer,
+            0,
+            &[self.data.geometry_buffer],
+            &[self.data.vertex_offset],
+        );
+        self.device.cmd_bind_index_buffer(
+            command_buffer,
+            self.data.geometry_buffer,
+            self.data.index_offset,
+            vk::IndexType::UINT32,
+        );
+
+        // 0번 set은 swapchain image에 해당하는 uniform buffer, 1번 set은 material texture
+        self.device.cmd_bind_descriptor_sets(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            self.data.pipeline_layout,
+            0,
+            &[
+                self.data.descriptor_sets[image_index],
+                self.data.material_descriptor_set,
+            ],
+            &[],
+        );
+
+        // glTF는 y축이 위를 향하므로 x축을 기준으로 90도 회전해서 z축이 위를 향하도록 맞춤
+        // skin이 있는 vertex는 joint matrix로 먼저 배치된 뒤 이 matrix가 적용됨
+        let model = Mat4::from_angle_x(Deg(90.0));
+        let model_bytes =
+            std::slice::from_raw_parts(&model as *const Mat4 as *const u8, size_of::<Mat4>());
+
+        self.device.cmd_push_constants(
+            command_buffer,
+            self.data.pipeline_layout,
+            vk::ShaderStageFlags::VERTEX,
+            0,
+            model_bytes,
+        );
+
+        // material의 factor는 model matrix 뒤에 이어서 전달함
+        let material_bytes = std::slice::from_raw_parts(
+            &self.data.material_constants as *const MaterialConstants as *const u8,
+            size_of::<MaterialConstants>(),
+        );
+        self.device.cmd_push_constants(
+            command_buffer,
+            self.data.pipeline_layout,
+            vk::ShaderStageFlags::FRAGMENT,
+            size_of::<Mat4>() as u32,
+            material_bytes,
+        );
+
+        cmd_begin_label(
+            &self.instance,
+            command_buffer,
+            "Draw Model",
+            [1.0, 0.8, 0.2, 1.0],
+        )?;
+        self.device
+            .cmd_draw_indexed(command_buffer, self.data.indices.len() as u32, 1, 0, 0, 0);
+        cmd_end_label(&self.instance, command_buffer);
+
+        self.device.cmd_end_render_pass(command_buffer);
+        self.data.profiler.end_scope(&self.device, command_buffer);
+        cmd_end_label(&self.instance, command_buffer);
+
+        self.data.profiler.end_scope(&self.device, command_buffer);
+        cmd_end_label(&self.instance, command_buffer);
+        self.device.end_command_buffer(command_buffer)?;
+
+        Ok(command_buffer)
+    }
+
+    /// image_index에 해당하는 uniform buffer에 camera와 projection matrix, light 정보를 기록
+    unsafe fn update_uniform_buffer(&self, image_index: usize) -> Result<()> {
+        // camera controller가 갱신한 camera의 위치와 방향을 사용
+        let view = self.camera.view();
+        let proj = self.camera.projection(
+            self.data.swapchain.extent.width as f32 / self.data.swapchain.extent.height as f32,
+        );
+
+        // 햇빛처럼 위쪽에서 비추는 directional light
+        let light_direction = vec3(0.5, 0.3, 1.0).normalize().extend(0.0);
+        let light_color = vec4(2.0, 1.9, 1.8, 0.0);
+
+        // 따뜻한 색과 차가운 색의 point light가 model의 반대편에서 돌면서 비춤
+        let time = self.start.elapsed().as_secs_f32();
+        let colors = [vec4(8.0, 4.0, 1.5, 0.0), vec4(1.5, 3.0, 8.0, 0.0)];
+        let point_lights = std::array::from_fn(|i| {
+            let angle = time + i as f32 * std::f32::consts::TAU / POINT_LIGHT_COUNT as f32;
+            PointLight {
+                position: vec4(angle.cos() * 2.0, angle.sin() * 2.0, 0.5, 1.0),
+                color: colors[i],
+            }
+        });
+
+        let ubo = UniformBufferObject {
+            view,
+            proj,
+            camera_position: self.camera.position.to_homogeneous(),
+            light_direction,
+            light_color,
+            point_lights,
+        };
+
+        let memory = self.device.map_memory(
+            self.data.uniform_buffers_memory[image_index],
+            0,
+            size_of::<UniformBufferObject>() as u64,
+            vk::MemoryMapFlags::empty(),
+        )?;
+
+        memcpy(&ubo, memory.cast(), 1);
+
+        self.device
+            .unmap_memory(self.data.uniform_buffers_memory[image_index]);
+
+        // animation의 현재 시점에서 joint matrix를 계산
+        // 사용하지 않는 joint는 단위 행렬로 채움
+        let mut joints = JointMatrices {
+            joints: [Mat4::identity(); MAX_JOINTS],
+        };
+        if let Some(skeleton) = &self.skeleton {
+            let matrices = skeleton.joint_matrices(self.animation.as_ref(), time);
+            joints.joints[..matrices.len()].copy_from_slice(&matrices);
+        }
+
+        let memory = self.device.map_memory(
+            self.data.joint_buffers_memory[image_index],
+            0,
+            size_of::<JointMatrices>() as u64,
+            vk::MemoryMapFlags::empty(),
+        )?;
+
+        memcpy(&joints, memory.cast(), 1);
+
+        self.device
+            .unmap_memory(self.data.joint_buffers_memory[image_index]);
+
+        Ok(())
+    }
+
+    /// Destroys our Vulkan app.  
+    /// vk::DebugUtilsMessengerEXT오브젝트는 앱이 종료되기 전에 cleanup되어야 한다.
+    unsafe fn destroy(&mut self) {
+        self.destroy_swapchain();
+        self.destroy_pipeline();
+
+        // 모든 command들이 끝나고 synchronization이 필요하지 않으므로 semaphore를 파괴
+        self.data
+            .render_finished_semaphores
+            .iter()
+            .for_each(|s| self.device.destroy_semaphore(*s, None));
+        self.data
+            .image_available_semaphores
+            .iter()
+            .for_each(|s| self.device.destroy_semaphore(*s, None));
+        // fence를 파괴
+        self.data
+            .in_flight_fences
+            .iter()
+            .for_each(|f| self.device.destroy_fence(*f, None));
+
+        // material texture에 사용된 sampler, image view, image를 파괴
+        self.device.destroy_sampler(self.data.texture_sampler, None);
+        self.data.material_textures.iter().for_each(|t| {
+            self.device.destroy_image_view(t.view, None);
+            self.device.destroy_image(t.image, None);
+            self.device.free_memory(t.memory, None);
+        });
+
+        // vertex와 index 데이터를 담은 buffer를 파괴하고 할당된 메모리를 해제
+        self.device.destroy_buffer(self.data.geometry_buffer, None);
+        self.device
+            .free_memory(self.data.geometry_buffer_memory, None);
+
+        // frame마다 사용하던 command pool과 전역 command pool을 파괴
+        // command pool이 파괴되면 할당된 command buffer도 함께 해제됨
+        self.data
+            .command_pools
+            .iter()
+            .for_each(|p| self.device.destroy_command_pool(*p, None));
+        self.device
+            .destroy_command_pool(self.data.command_pool, None);
+
+        // descriptor set layout을 파괴
+        self.device
+            .destroy_descriptor_set_layout(self.data.descriptor_set_layout, None);
+        self.device
+            .destroy_descriptor_set_layout(self.data.material_descriptor_set_layout, None);
+
+        // profiler가 사용하는 timestamp query pool을 파괴
+        self.data.profiler.destroy(&self.device);
+
+        self.device.destroy_device(None);
+
+        if !self.data.messenger.is_null() {
+            // 프로그램이 종료되기 전에 디버그 메세지 핸들러를 파괴
+            self.instance
+                .destroy_debug_utils_messenger_ext(self.data.messenger, None);
+        }
+
+        // device가 파괴된 후에 instance를 파괴해야 함
+        // 프로그램이 종료되면 instance가 파괴되기 전에 surface를 파괴해야 함
+        self.instance.destroy_surface_khr(self.data.surface, None);
+        // 프로그램이 종료되면 인스턴스를 파괴해야 함
+        self.instance.destroy_instance(None);
+    }
+
+    /// swapchain과 swapchain에 의존하는 오브젝트들을 파괴
+    unsafe fn destroy_swapchain(&mut self) {
+        // multisampled color image와 관련된 오브젝트를 파괴
+        self.device
+            .destroy_image_view(self.data.color_image_view, None);
+        self.device.destroy_image(self.data.color_image, None);
+        self.device.free_memory(self.data.color_image_memory, None);
+        // depth image와 관련된 오브젝트를 파괴
+        self.device
+            .destroy_image_view(self.data.depth_image_view, None);
+        self.device.destroy_image(self.data.depth_image, None);
+        self.device.free_memory(self.data.depth_image_memory, None);
+        // descriptor pool을 파괴하면 할당된 descriptor set도 함께 해제됨
+        self.device
+            .destroy_descriptor_pool(self.data.descriptor_pool, None);
+        // uniform buffer를 파괴하고 할당된 메모리를 해제
+        self.data
+            .uniform_buffers
+            .iter()
+            .for_each(|b| self.device.destroy_buffer(*b, None));
+        self.data
+            .uniform_buffers_memory
+            .iter()
+            .for_each(|m| self.device.free_memory(*m, None));
+        // joint matrix buffer를 파괴하고 할당된 메모리를 해제
+        self.data
+            .joint_buffers
+            .iter()
+            .for_each(|b| self.device.destroy_buffer(*b, None));
+        self.data
+            .joint_buffers_memory
+            .iter()
+            .for_each(|m| self.device.free_memory(*m, None));
+        // framebuffers를 파괴
+        self.data
+            .framebuffers
+            .iter()
+            .for_each(|f| self.device.destroy_framebuffer(*f, None));
+        // swapchain image view를 파괴
+        self.data
+            .swapchain_image_views
+            .iter()
+            .for_each(|v| self.device.destroy_image_view(*v, None));
+        // device전에 청소되어야 함
+        self.device
+            .destroy_swapchain_khr(self.data.swapchain.handle, None);
+    }
+
+    /// graphics pipeline과 pipeline이 의존하는 render pass를 파괴  
+    /// swapchain의 크기가 바뀌어도 유지되므로 swapchain과 따로 파괴함
+    unsafe fn destroy_pipeline(&mut self) {
+        // graphics pipeline을 파괴
+        self.device.destroy_pipeline(self.data.pipeline, None);
+        // pipeline layout을 파괴
+        self.device
+            .destroy_pipeline_layout(self.data.pipeline_layout, None);
+        // render pass를 파괴
+        self.device.destroy_render_pass(self.data.render_pass, None);
+    }
+}
+
+/// The Vulkan handles and associated properties used by our Vulkan app.  
+/// Vulkan 리소스 컨테이너 역할
+#[derive(Clone, Debug, Default)]
+struct AppData {
+    // surface
+    surface: vk::SurfaceKHR,
+    // 디버그 메세지를 처리하기 위한 messenger 핸들러
+    messenger: vk::DebugUtilsMessengerEXT,
+    // physical device 핸들
+    physical_device: vk::PhysicalDevice,
+    // physical device가 지원하는 최대 MSAA sample 수
+    msaa_samples: vk::SampleCountFlags,
+    // logical device와 함께 생성된 graphics queue를 컨트롤하기 위한 핸들
+    graphics_queue: vk::Queue,
+    // present queue를 컨트롤하기 위한 핸들
+    present_queue: vk::Queue,
+    // swapchain 핸들과 swapchain image의 format, extent, image들
+    swapchain: Swapchain,
+    // image view를 저장하기 위한 필드
+    swapchain_image_views: Vec<vk::ImageView>,
+    // render pass를 저장하기 위한 필드
+    render_pass: vk::RenderPass,
+    // shader의 uniform value를 저장하기 위한 필드
+    pipeline_layout: vk::PipelineLayout,
+    // pipe line을 저장하기 위한 필드
+    pipeline: vk::Pipeline,
+    // framebuffer들을 저장하기 위한 필드
+    framebuffers: Vec<vk::Framebuffer>,
+    // single time command에 사용하는 전역 command pool
+    command_pool: vk::CommandPool,
+    // frame마다 하나씩 존재하는 command pool
+    command_pools: Vec<vk::CommandPool>,
+    // frame마다 하나씩 존재하며 매 frame 다시 기록되는 command buffer
+    command_buffers: Vec<vk::CommandBuffer>,
+    // 이미지가 얻어졌고 rendering 준비가 됨을 알리기 위한 세마포어
+    image_available_semaphores: Vec<vk::Semaphore>,
+    // rendering이 완료되었고 presentation가 일어남을 알리기 위한 세마포어
+    render_finished_semaphores: Vec<vk::Semaphore>,
+    // frame을 위한 fence
+    in_flight_fences: Vec<vk::Fence>,
+    // swapchain image가 사용중인지 추적하기위한 필드
+    images_in_flight: Vec<vk::Fence>,
+    // timestamp query로 구간별 GPU time을 측정하는 profiler
+    profiler: GpuProfiler,
+    // model에서 읽어온 vertex 데이터
+    vertices: Vec<Vertex>,
+    // model에서 읽어온 index 데이터
+    indices: Vec<u32>,
+    // vertex와 index 데이터를 함께 저장하는 buffer
+    geometry_buffer: vk::Buffer,
+    // geometry buffer에 bind된 device memory
+    geometry_buffer_memory: vk::DeviceMemory,
+    // geometry buffer에서 vertex 데이터가 시작되는 offset
+    vertex_offset: vk::DeviceSize,
+    // geometry buffer에서 index 데이터가 시작되는 offset
+    index_offset: vk::DeviceSize,
+    // shader에서 사용하는 descriptor들의 binding 정보
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    // swapchain image마다 존재하는 uniform buffer
+    uniform_buffers: Vec<vk::Buffer>,
+    // uniform buffer에 bind된 device memory
+    uniform_buffers_memory: Vec<vk::DeviceMemory>,
+    // swapchain image마다 존재하는 joint matrix uniform buffer
+    joint_buffers: Vec<vk::Buffer>,
+    // joint matrix buffer에 bind된 device memory
+    joint_buffers_memory: Vec<vk::DeviceMemory>,
+    // descriptor set을 할당하기 위한 pool
+    descriptor_pool: vk::DescriptorPool,
+    // swapchain image마다 존재하는 descriptor set
+    descriptor_sets: Vec<vk::DescriptorSet>,
+    // material texture들의 binding 정보
+    material_descriptor_set_layout: vk::DescriptorSetLayout,
+    // material texture를 연결한 descriptor set
+    // 모든 frame이 같은 texture를 사용하므로 하나만 있으면 됨
+    material_descriptor_set: vk::DescriptorSet,
+    // albedo, normal, metallic-roughness, occlusion 순서로 저장한 material texture
+    material_textures: Vec<Texture>,
+    // fragment shader에 push constant로 전달하는 material의 factor
+    material_constants: MaterialConstants,
+    // texture를 읽을 때 filtering과 addressing 방법을 결정하는 sampler
+    texture_sampler: vk::Sampler,
+    // multisampled rendering 결과를 저장하는 color image
+    color_image: vk::Image,
+    // color image에 bind된 device memory
+    color_image_memory: vk::DeviceMemory,
+    // color attachment로 사용하기 위한 image view
+    color_image_view: vk::ImageView,
+    // fragment의 depth를 기록하는 image
+    depth_image: vk::Image,
+    // depth image에 bind된 device memory
+    depth_image_memory: vk::DeviceMemory,
+    // depth attachment로 사용하기 위한 image view
+    depth_image_view: vk::ImageView,
+}
+
+/// texture sampler에서 사용할 anisotropic filtering을 physical device가 지원하는지 확인
+unsafe fn check_physical_device_features(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+) -> Result<()> {
+    let features = instance.get_physical_device_features(physical_device);
+    if features.sampler_anisotropy != vk::TRUE {
+        return Err(anyhow!(SuitabilityError("sampler anisotropy feature")));
+    }
+
+    Ok(())
+}
+
+/// color와 depth attachment가 모두 지원하는 가장 큰 sample 수를 찾음
+unsafe fn get_max_msaa_samples(instance: &Instance, data: &AppData) -> vk::SampleCountFlags {
+    let properties = instance.get_physical_device_properties(data.physical_device);
+    let counts = properties.limits.framebuffer_color_sample_counts
+        & properties.limits.framebuffer_depth_sample_counts;
+    [
+        vk::SampleCountFlags::_64,
+        vk::SampleCountFlags::_32,
+        vk::SampleCountFlags::_16,
+        vk::SampleCountFlags::_8,
+        vk::SampleCountFlags::_4,
+        vk::SampleCountFlags::_2,
+    ]
+    .iter()
+    .cloned()
+    .find(|c| counts.contains(*c))
+    .unwrap_or(vk::SampleCountFlags::_1)
+}
+
+/// render pass 생성
+unsafe fn create_render_pass(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    // multisampled image는 바로 present할 수 없으므로 resolve attachment로 옮겨야 함
+    let color_attachment = vk::AttachmentDescription::builder()
+        .format(data.swapchain.format)
+        .samples(data.msaa_samples)
+        .load_op(vk::AttachmentLoadOp::CLEAR)
+        .store_op(vk::AttachmentStoreOp::STORE)
+        .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+        .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .final_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+    let color_attachment_ref = vk::AttachmentReference::builder()
+        .attachment(0)
+        .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+    // depth 데이터는 그리기가 끝난 뒤에 사용하지 않으므로 저장하지 않음
+    let depth_stencil_attachment = vk::AttachmentDescription::builder()
+        .format(get_depth_format(instance, data)?)
+        .samples(data.msaa_samples)
+        .load_op(vk::AttachmentLoadOp::CLEAR)
+        .store_op(vk::AttachmentStoreOp::DONT_CARE)
+        .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+        .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .final_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
+
+    let depth_stencil_attachment_ref = vk::AttachmentReference::builder()
+        .attachment(1)
+        .layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
+
+    // multisampled color attachment가 resolve되어 present될 swapchain image
+    let color_resolve_attachment = vk::AttachmentDescription::builder()
+        .format(data.swapchain.format)
+        .samples(vk::SampleCountFlags::_1)
+        .load_op(vk::AttachmentLoadOp::DONT_CARE)
+        .store_op(vk::AttachmentStoreOp::STORE)
+        .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+        .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .final_layout(vk::ImageLayout::PRESENT_SRC_KHR);
+
+    let color_resolve_attachment_ref = vk::AttachmentReference::builder()
+        .attachment(2)
+        .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+    // subpass는 하나의 depth attachment만 사용할 수 있음
+    // subpass가 끝나면 color attachment가 resolve attachment로 resolve됨
+    let color_attachments = &[color_attachment_ref];
+    let resolve_attachments = &[color_resolve_attachment_ref];
+    let subpass = vk::SubpassDescription::builder()
+        .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+        .color_attachments(color_attachments)
+        .depth_stencil_attachment(&depth_stencil_attachment_ref)
+        .resolve_attachments(resolve_attachments);
+
+    // depth image는 load 시점에 clear되므로 early fragment test stage도 기다려야 함
+    let dependency = vk::SubpassDependency::builder()
+        .src_subpass(vk::SUBPASS_EXTERNAL)
+        .dst_subpass(0)
+        .src_stage_mask(
+            vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+        )
+        .src_access_mask(vk::AccessFlags::empty())
+        .dst_stage_mask(
+            vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+        )
+        .dst_access_mask(
+            vk::AccessFlags::COLOR_ATTACHMENT_WRITE
+                | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+        );
+
+    let attachments = &[
+        color_attachment,
+        depth_stencil_attachment,
+        color_resolve_attachment,
+    ];
+    let subpasses = &[subpass];
+    let dependencies = &[dependency];
+    let info = vk::RenderPassCreateInfo::builder()
+        .attachments(attachments)
+        .subpasses(subpasses)
+        .dependencies(dependencies);
+
+    data.render_pass = device.create_render_pass(&info, None)?;
+
+    Ok(())
+}
+
+/// descriptor set layout 생성  
+/// 0번 set은 frame마다 바뀌는 uniform buffer와 joint matrix를, 1번 set은 material의 texture들을 읽음  
+/// 자주 바뀌는 데이터와 material을 나누면 model마다 material set만 바꿔서 bind할 수 있음
+unsafe fn create_descriptor_set_layout(device: &Device, data: &mut AppData) -> Result<()> {
+    // vertex shader는 matrix를, fragment shader는 camera 위치와 light 정보를 읽음
+    let ubo_binding = vk::DescriptorSetLayoutBinding::builder()
+        .binding(0)
+        .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+        .descriptor_count(1)
+        .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT);
+
+    // joint matrix는 vertex shader에서만 읽음
+    let joint_binding = vk::DescriptorSetLayoutBinding::builder()
+        .binding(1)
+        .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+        .descriptor_count(1)
+        .stage_flags(vk::ShaderStageFlags::VERTEX);
+
+    let bindings = &[ubo_binding, joint_binding];
+    let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(bindings);
+
+    data.descriptor_set_layout = device.create_descriptor_set_layout(&info, None)?;
+
+    // albedo, normal, metallic-roughness, occlusion texture를 binding 0부터 순서대로 읽음
+    let material_bindings = (0..4)
+        .map(|i| {
+            vk::DescriptorSetLayoutBinding::builder()
+                .binding(i)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+                .build()
+        })
+        .collect::<Vec<_>>();
+
+    let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&material_bindings);
+
+    data.material_descriptor_set_layout = device.create_descriptor_set_layout(&info, None)?;
+
+    Ok(())
+}
+
+/// pipeline 생성
+unsafe fn create_pipeline(device: &Device, data: &mut AppData) -> Result<()> {
+    let vert = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/102/vert.spv"));
+    let frag = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/67/frag.spv"));
+
+    let vert_shader_module = create_shader_module(device, &vert[..])?;
+    let frag_shader_module = create_shader_module(device, &frag[..])?;
+
+    let vert_stage = vk::PipelineShaderStageCreateInfo::builder()
+        .stage(vk::ShaderStageFlags::VERTEX)
+        .module(vert_shader_module)
+        .name(b"main\0");
+
+    let frag_stage = vk::PipelineShaderStageCreateInfo::builder()
+        .stage(vk::ShaderStageFlags::FRAGMENT)
+        .module(frag_shader_module)
+        .name(b"main\0");
+
+    // shader에 vertex 데이터의 형식을 알려줌
+    let binding_descriptions = &[Vertex::binding_description()];
+    let attribute_descriptions = Vertex::attribute_descriptions();
+    let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
+        .vertex_binding_descriptions(binding_descriptions)
+        .vertex_attribute_descriptions(&attribute_descriptions);
+
+    let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::builder()
+        .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+        .primitive_restart_enable(false);
+
+    // viewport와 scissor는 dynamic state로 지정하므로 개수만 알려주면 됨
+    let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+        .viewport_count(1)
+        .scissor_count(1);
+
+    let rasterization_state = vk::PipelineRasterizationStateCreateInfo::builder()
+        .depth_clamp_enable(false)
+        .rasterizer_discard_enable(false)
+        .polygon_mode(vk::PolygonMode::FILL)
+        .line_width(1.0)
+        .cull_mode(vk::CullModeFlags::BACK)
+        // projection matrix에서 y축을 뒤집었으므로 vertex의 순서도 반대가 됨
+        .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+        .depth_bias_enable(false);
+
+    let multisample_state = vk::PipelineMultisampleStateCreateInfo::builder()
+        .sample_shading_enable(false)
+        .rasterization_samples(data.msaa_samples);
+
+    // 새로운 fragment의 depth가 더 작을 때(더 가까울 때)만 통과시킴
+    let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::builder()
+        .depth_test_enable(true)
+        .depth_write_enable(true)
+        .depth_compare_op(vk::CompareOp::LESS)
+        .depth_bounds_test_enable(false)
+        .min_depth_bounds(0.0) // Optional.
+        .max_depth_bounds(1.0) // Optional.
+        .stencil_test_enable(false);
+
+    // 불투명한 material만 그리므로 blending을 사용하지 않음
+    let attachment = vk::PipelineColorBlendAttachmentState::builder()
+        .color_write_mask(vk::ColorComponentFlags::all())
+        .blend_enable(false);
+
+    let attachments = &[attachment];
+    let color_blend_state = vk::PipelineColorBlendStateCreateInfo::builder()
+        .logic_op_enable(false)
+        .logic_op(vk::LogicOp::COPY)
+        .attachments(attachments)
+        .blend_constants([0.0, 0.0, 0.0, 0.0]);
+
+    // vertex shader는 model matrix(64 bytes)를, fragment shader는 그 뒤의 material factor를 사용
+    let vert_push_constant_range = vk::PushConstantRange::builder()
+        .stage_flags(vk::ShaderStageFlags::VERTEX)
+        .offset(0)
+        .size(64 /* 16 × 4 byte floats */);
+
+    let frag_push_constant_range = vk::PushConstantRange::builder()
+        .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+        .offset(64)
+        .size(size_of::<MaterialConstants>() as u32);
+
+    // pipeline이 사용할 descriptor set layout과 push constant range를 지정
+    // set의 번호는 배열의 순서와 같음
+    let set_layouts = &[
+        data.descriptor_set_layout,
+        data.material_descriptor_set_layout,
+    ];
+    let push_constant_ranges = &[vert_push_constant_range, frag_push_constant_range];
+    let layout_info = vk::PipelineLayoutCreateInfo::builder()
+        .set_layouts(set_layouts)
+        .push_constant_ranges(push_constant_ranges);
+
+    data.pipeline_layout = device.create_pipeline_layout(&layout_info, None)?;
+
+    // viewport와 scissor는 pipeline에 포함시키지 않고 command buffer를 기록할 때 지정
+    // swapchain의 크기가 바뀌어도 pipeline을 다시 만들 필요가 없음
+    let dynamic_states = &[vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+    let dynamic_state =
+        vk::PipelineDynamicStateCreateInfo::builder().dynamic_states(dynamic_states);
+
+    let stages = &[vert_stage, frag_stage];
+    let info = vk::GraphicsPipelineCreateInfo::builder()
+        .stages(stages)
+        .vertex_input_state(&vertex_input_state)
+        .input_assembly_state(&input_assembly_state)
+        .viewport_state(&viewport_state)
+        .rasterization_state(&rasterization_state)
+        .multisample_state(&multisample_state)
+        .depth_stencil_state(&depth_stencil_state)
+        .color_blend_state(&color_blend_state)
+        .dynamic_state(&dynamic_state)
+        .layout(data.pipeline_layout)
+        .render_pass(data.render_pass)
+        .subpass(0);
+
+    data.pipeline = device
+        .create_graphics_pipelines(vk::PipelineCache::null(), &[info], None)?
+        .0[0];
+
+    device.destroy_shader_module(vert_shader_module, None);
+    device.destroy_shader_module(frag_shader_module, None);
+
+    Ok(())
+}
+
+/// framebuffer 생성
+unsafe fn create_framebuffers(device: &Device, data: &mut AppData) -> Result<()> {
+    data.framebuffers = data
+        .swapchain_image_views
+        .iter()
+        .map(|i| {
+            // 동시에 하나의 subpass만 실행되므로 depth image는 하나만 있으면 됨
+            // swapchain image는 resolve attachment로 사용됨
+            let attachments = &[data.color_image_view, data.depth_image_view, *i];
+            let create_info = vk::FramebufferCreateInfo::builder()
+                .render_pass(data.render_pass)
+                .attachments(attachments)
+                .width(data.swapchain.extent.width)
+                .height(data.swapchain.extent.height)
+                .layers(1);
+
+            device.create_framebuffer(&create_info, None)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(())
+}
+
+/// command pool 생성  
+/// 전역 command pool은 single time command에 사용하고, frame마다 하나씩 command pool을 따로 만듦
+unsafe fn create_command_pools(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    data.command_pool = create_command_pool(instance, device, data)?;
+
+    for _ in 0..MAX_FRAMES_IN_FLIGHT {
+        let command_pool = create_command_pool(instance, device, data)?;
+        data.command_pools.push(command_pool);
+    }
+
+    Ok(())
+}
+
+/// graphics queue family에 command를 제출할 수 있는 command pool을 생성  
+/// 짧게 쓰이고 자주 reset되는 command buffer를 할당하므로 TRANSIENT를 지정
+unsafe fn create_command_pool(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<vk::CommandPool> {
+    let indices = QueueFamilyIndices::get(instance, data.surface, data.physical_device)?;
+
+    let info = vk::CommandPoolCreateInfo::builder()
+        .flags(vk::CommandPoolCreateFlags::TRANSIENT)
+        .queue_family_index(indices.graphics);
+
+    Ok(device.create_command_pool(&info, None)?)
+}
+
+/// frame마다 사용할 command buffer를 frame의 command pool에서 할당
+unsafe fn create_command_buffers(device: &Device, data: &mut AppData) -> Result<()> {
+    for command_pool in &data.command_pools {
+        let allocate_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(*command_pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(1);
+
+        let command_buffer = device.allocate_command_buffers(&allocate_info)?[0];
+        data.command_buffers.push(command_buffer);
+    }
+
+    Ok(())
+}
+
+/// buffer가 요구하는 memory type과 properties를 모두 만족하는 memory type의 index를 찾음
+unsafe fn get_memory_type(
+    instance: &Instance,
+    data: &AppData,
+    properties: vk::MemoryPropertyFlags,
+    requirements: vk::MemoryRequirements,
+) -> Result<u32> {
+    let memory = instance.get_physical_device_memory_properties(data.physical_device);
+
+    // memory_type_bits는 사용 가능한 memory type들을 bit field로 나타냄
+    (0..memory.memory_type_count)
+        .find(|i| {
+            let suitable = (requirements.memory_type_bits & (1 << i)) != 0;
+            let memory_type = memory.memory_types[*i as usize];
+            suitable && memory_type.property_flags.contains(properties)
+        })
+        .ok_or_else(|| anyhow!("Failed to find suitable memory type."))
+}
+
+/// buffer를 생성하고 memory를 할당해서 bind하는 helper function
+unsafe fn create_buffer(
+    instance: &Instance,
+    device: &Device,
+    data: &AppData,
+    size: vk::DeviceSize,
+    usage: vk::BufferUsageFlags,
+    properties: vk::MemoryPropertyFlags,
+) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+    // buffer는 graphics queue에서만 사용되므로 EXCLUSIVE로 설정
+    let buffer_info = vk::BufferCreateInfo::builder()
+        .size(size)
+        .usage(usage)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+    let buffer = device.create_buffer(&buffer_info, None)?;
+
+    let requirements = device.get_buffer_memory_requirements(buffer);
+
+    let memory_info = vk::MemoryAllocateInfo::builder()
+        .allocation_size(requirements.size)
+        .memory_type_index(get_memory_type(instance, data, properties, requirements)?);
+
+    let buffer_memory = device.allocate_memory(&memory_info, None)?;
+
+    // offset이 0이 아니라면 requirements.alignment로 나누어 떨어져야 함
+    device.bind_buffer_memory(buffer, buffer_memory, 0)?;
+
+    Ok((buffer, buffer_memory))
+}
+
+/// src buffer의 내용을 dst buffer의 offset 위치로 복사  
+/// 복사는 command buffer를 통해서 queue에 제출되어야 함
+unsafe fn copy_buffer(
+    device: &Device,
+    data: &AppData,
+    source: vk::Buffer,
+    destination: vk::Buffer,
+    offset: vk::DeviceSize,
+    size: vk::DeviceSize,
+) -> Result<()> {
+    let command_buffer = begin_single_time_commands(device, data)?;
+
+    let regions = vk::BufferCopy::builder().dst_offset(offset).size(size);
+    device.cmd_copy_buffer(command_buffer, source, destination, &[regions]);
+
+    end_single_time_commands(device, data, command_buffer)?;
+
+    Ok(())
+}
+
+/// 한번만 사용할 임시 command buffer를 할당하고 recording을 시작
+unsafe fn begin_single_time_commands(device: &Device, data: &AppData) -> Result<vk::CommandBuffer> {
+    let info = vk::CommandBufferAllocateInfo::builder()
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_pool(data.command_pool)
+        .command_buffer_count(1);
+
+    let command_buffer = device.allocate_command_buffers(&info)?[0];
+
+    // 한번만 사용하고 버릴 command buffer이므로 ONE_TIME_SUBMIT을 사용
+    let info =
+        vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+
+    device.begin_command_buffer(command_buffer, &info)?;
+
+    Ok(command_buffer)
+}
+
+/// 임시 command buffer의 recording을 끝내고 제출한 뒤 실행이 끝날 때 까지 기다림
+unsafe fn end_single_time_commands(
+    device: &Device,
+    data: &AppData,
+    command_buffer: vk::CommandBuffer,
+) -> Result<()> {
+    device.end_command_buffer(command_buffer)?;
+
+    let command_buffers = &[command_buffer];
+    let info = vk::SubmitInfo::builder().command_buffers(command_buffers);
+
+    device.queue_submit(data.graphics_queue, &[info], vk::Fence::null())?;
+    device.queue_wait_idle(data.graphics_queue)?;
+
+    device.free_command_buffers(data.command_pool, &[command_buffer]);
+
+    Ok(())
+}
+
+/// glTF에서 읽은 mesh를 vertex와 index 데이터로 변환하고 material의 factor를 저장
+fn load_model(data: &mut AppData, model: &Model) -> Result<()> {
+    let mesh = &model.mesh;
+
+    // glTF는 vertex를 index로 공유해서 저장하므로 중복된 vertex를 제거할 필요가 없음
+    // glTF의 texture 좌표는 Vulkan과 같이 원점이 좌상단이므로 뒤집지 않음
+    data.vertices = (0..mesh.positions.len())
+        .map(|i| {
+            Vertex::new(
+                mesh.positions[i].into(),
+                mesh.normals[i].into(),
+                mesh.tex_coords[i].into(),
+                // skin이 없는 model은 joint와 weight가 비어있으므로 weight를 0으로 채움
+                mesh.joints.get(i).map_or([0; 4], |j| j.map(u32::from)),
+                mesh.weights.get(i).copied().unwrap_or([0.0; 4]).into(),
+            )
+        })
+        .collect();
+    data.indices = mesh.indices.clone();
+
+    if let Some(skeleton) = &model.skeleton {
+        if skeleton.joint_count() > MAX_JOINTS {
+            return Err(anyhow!(
+                "Model has {} joints, at most {} are supported.",
+                skeleton.joint_count(),
+                MAX_JOINTS
+            ));
+        }
+    }
+
+    data.material_constants = MaterialConstants::new(&model.material);
+
+    Ok(())
+}
+
+/// vertex와 index 데이터를 함께 담을 buffer 생성  
+/// 하나의 buffer를 offset으로 나누어 사용하면 allocation의 수를 줄일 수 있음  
+/// GPU가 가장 빠르게 읽을 수 있는 DEVICE_LOCAL memory를 사용함
+unsafe fn create_geometry_buffer(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    let vertices_size = (size_of::<Vertex>() * data.vertices.len()) as u64;
+    let indices_size = (size_of::<u32>() * data.indices.len()) as u64;
+
+    // vertex 데이터를 앞에, index 데이터를 뒤에 배치
+    // index buffer의 offset은 index type 크기의 배수여야 함
+    data.vertex_offset = 0;
+    data.index_offset = vertices_size.next_multiple_of(size_of::<u32>() as u64);
+
+    // DEVICE_LOCAL memory는 map할 수 없으므로 staging buffer에서 복사받아야 함
+    let (geometry_buffer, geometry_buffer_memory) = create_buffer(
+        instance,
+        device,
+        data,
+        data.index_offset + indices_size,
+        vk::BufferUsageFlags::TRANSFER_DST
+            | vk::BufferUsageFlags::VERTEX_BUFFER
+            | vk::BufferUsageFlags::INDEX_BUFFER,
+        vk::MemoryPropertyFlags::DEVICE_LOCAL,
+    )?;
+
+    data.geometry_buffer = geometry_buffer;
+    data.geometry_buffer_memory = geometry_buffer_memory;
+
+    Ok(())
+}
+
+/// CPU에서 접근 가능한 staging buffer에 데이터를 쓰고  
+/// destination buffer의 offset 위치로 복사함
+unsafe fn upload_to_buffer<T: Copy>(
+    instance: &Instance,
+    device: &Device,
+    data: &AppData,
+    source: &[T],
+    destination: vk::Buffer,
+    offset: vk::DeviceSize,
+) -> Result<()> {
+    let size = (size_of::<T>() * source.len()) as u64;
+
+    // staging buffer 생성
+    // HOST_COHERENT를 사용해서 map된 memory에 쓴 내용이 바로 반영되도록 함
+    let (staging_buffer, staging_buffer_memory) = create_buffer(
+        instance,
+        device,
+        data,
+        size,
+        vk::BufferUsageFlags::TRANSFER_SRC,
+        vk::MemoryPropertyFlags::HOST_COHERENT | vk::MemoryPropertyFlags::HOST_VISIBLE,
+    )?;
+
+    // staging buffer의 memory를 map해서 데이터를 복사
+    let memory = device.map_memory(staging_buffer_memory, 0, size, vk::MemoryMapFlags::empty())?;
+
+    memcpy(source.as_ptr(), memory.cast(), source.len());
+
+    device.unmap_memory(staging_buffer_memory);
+
+    copy_buffer(device, data, staging_buffer, destination, offset, size)?;
+
+    // 복사가 끝났으므로 staging buffer는 더 이상 필요없음
+    device.destroy_buffer(staging_buffer, None);
+    device.free_memory(staging_buffer_memory, None);
+
+    Ok(())
+}
+
+/// vertex 데이터를 geometry buffer의 vertex 영역에 업로드
+unsafe fn create_vertex_buffer(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    upload_to_buffer(
+        instance,
+        device,
+        data,
+        &data.vertices,
+        data.geometry_buffer,
+        data.vertex_offset,
+    )
+}
+
+/// index 데이터를 geometry buffer의 index 영역에 업로드
+unsafe fn create_index_buffer(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    upload_to_buffer(
+        instance,
+        device,
+        data,
+        &data.indices,
+        data.geometry_buffer,
+        data.index_offset,
+    )
+}
+
+/// multisampled color image와 image view 생성  
+/// 하나의 pixel마다 여러개의 sample을 저장하는 render target으로 사용됨
+unsafe fn create_color_objects(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    // render pass 안에서만 사용되므로 TRANSIENT_ATTACHMENT를 사용
+    let (color_image, color_image_memory) = create_image(
+        instance,
+        device,
+        data,
+        data.swapchain.extent.width,
+        data.swapchain.extent.height,
+        1,
+        data.msaa_samples,
+        data.swapchain.format,
+        vk::ImageTiling::OPTIMAL,
+        vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+        vk::MemoryPropertyFlags::DEVICE_LOCAL,
+    )?;
+
+    data.color_image = color_image;
+    data.color_image_memory = color_image_memory;
+
+    data.color_image_view = create_image_view(
+        device,
+        data.color_image,
+        data.swapchain.format,
+        vk::ImageAspectFlags::COLOR,
+        1,
+    )?;
+
+    Ok(())
+}
+
+/// depth image와 image view 생성  
+/// depth image는 render pass가 시작될 때 clear되므로 따로 데이터를 채울 필요가 없음
+unsafe fn create_depth_objects(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    let format = get_depth_format(instance, data)?;
+
+    let (depth_image, depth_image_memory) = create_image(
+        instance,
+        device,
+        data,
+        data.swapchain.extent.width,
+        data.swapchain.extent.height,
+        1,
+        data.msaa_samples,
+        format,
+        vk::ImageTiling::OPTIMAL,
+        vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT,
+        vk::MemoryPropertyFlags::DEVICE_LOCAL,
+    )?;
+
+    data.depth_image = depth_image;
+    data.depth_image_memory = depth_image_memory;
+
+    data.depth_image_view = create_image_view(
+        device,
+        data.depth_image,
+        format,
+        vk::ImageAspectFlags::DEPTH,
+        1,
+    )?;
+
+    Ok(())
+}
+
+/// depth attachment로 사용할 수 있는 format을 선택  
+/// 정밀도가 높은 format부터 순서대로 지원 여부를 확인함
+unsafe fn get_depth_format(instance: &Instance, data: &AppData) -> Result<vk::Format> {
+    let candidates = &[
+        vk::Format::D32_SFLOAT,
+        vk::Format::D32_SFLOAT_S8_UINT,
+        vk::Format::D24_UNORM_S8_UINT,
+    ];
+
+    get_supported_format(
+        instance,
+        data,
+        candidates,
+        vk::ImageTiling::OPTIMAL,
+        vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT,
+    )
+}
+
+/// candidates중에서 tiling과 features를 지원하는 첫번째 format을 찾음
+unsafe fn get_supported_format(
+    instance: &Instance,
+    data: &AppData,
+    candidates: &[vk::Format],
+    tiling: vk::ImageTiling,
+    features: vk::FormatFeatureFlags,
+) -> Result<vk::Format> {
+    candidates
+        .iter()
+        .cloned()
+        .find(|f| {
+            let properties =
+                instance.get_physical_device_format_properties(data.physical_device, *f);
+            match tiling {
+                vk::ImageTiling::LINEAR => properties.linear_tiling_features.contains(features),
+                vk::ImageTiling::OPTIMAL => properties.optimal_tiling_features.contains(features),
+                _ => false,
+            }
+        })
+        .ok_or_else(|| anyhow!("Failed to find supported format!"))
+}
+
+/// material의 texture들을 GPU에 upload  
+/// base color만 sRGB로 저장되어 있고, 나머지 texture는 값을 그대로 사용해야 하므로 UNORM format을 사용함
+unsafe fn create_material_textures(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+    material: &Material,
+) -> Result<()> {
+    // material descriptor set의 binding 순서와 같아야 함
+    let images = [
+        (&material.albedo, vk::Format::R8G8B8A8_SRGB),
+        (&material.normal, vk::Format::R8G8B8A8_UNORM),
+        (&material.metallic_roughness, vk::Format::R8G8B8A8_UNORM),
+        (&material.occlusion, vk::Format::R8G8B8A8_UNORM),
+    ];
+
+    let textures = images
+        .into_iter()
+        .map(|(image, format)| create_texture_image(instance, device, data, image, format))
+        .collect::<Result<Vec<_>>>()?;
+
+    data.material_textures = textures;
+
+    Ok(())
+}
+
+/// RGBA8 image로 mipmap을 가진 texture를 생성  
+/// staging buffer에 pixel 데이터를 쓰고 DEVICE_LOCAL memory의 image로 복사함
+unsafe fn create_texture_image(
+    instance: &Instance,
+    device: &Device,
+    data: &AppData,
+    image: &MaterialImage,
+    format: vk::Format,
+) -> Result<Texture> {
+    let size = image.pixels.len() as u64;
+    let (width, height) = (image.width, image.height);
+
+    // 가장 긴 변이 1이 될 때 까지 절반으로 줄여나가면서 만들 수 있는 level의 수
+    let mip_levels = (width.max(height) as f32).log2().floor() as u32 + 1;
+
+    // staging buffer 생성
+    let (staging_buffer, staging_buffer_memory) = create_buffer(
+        instance,
+        device,
+        data,
+        size,
+        vk::BufferUsageFlags::TRANSFER_SRC,
+        vk::MemoryPropertyFlags::HOST_COHERENT | vk::MemoryPropertyFlags::HOST_VISIBLE,
+    )?;
+
+    let memory = device.map_memory(staging_buffer_memory, 0, size, vk::MemoryMapFlags::empty())?;
+
+    memcpy(image.pixels.as_ptr(), memory.cast(), image.pixels.len());
+
+    device.unmap_memory(staging_buffer_memory);
+
+    // mipmap을 생성할 때 이전 level에서 blit해오므로 TRANSFER_SRC도 필요함
+    let (texture_image, texture_image_memory) = create_image(
+        instance,
+        device,
+        data,
+        width,
+        height,
+        mip_levels,
+        vk::SampleCountFlags::_1,
+        format,
+        vk::ImageTiling::OPTIMAL,
+        vk::ImageUsageFlags::SAMPLED
+            | vk::ImageUsageFlags::TRANSFER_DST
+            | vk::ImageUsageFlags::TRANSFER_SRC,
+        vk::MemoryPropertyFlags::DEVICE_LOCAL,
+    )?;
+
+    // 복사를 받을 수 있도록 layout을 변경
+    transition_image_layout(
+        device,
+        data,
+        texture_image,
+        format,
+        vk::ImageLayout::UNDEFINED,
+        vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+        mip_levels,
+    )?;
+
+    copy_buffer_to_image(device, data, staging_buffer, texture_image, width, height)?;
+
+    // 복사가 끝났으므로 staging buffer는 더 이상 필요없음
+    device.destroy_buffer(staging_buffer, None);
+    device.free_memory(staging_buffer_memory, None);
+
+    // 나머지 level을 생성하면서 shader에서 읽을 수 있도록 layout을 변경
+    generate_mipmaps(
+        instance,
+        device,
+        data,
+        texture_image,
+        format,
+        width,
+        height,
+        mip_levels,
+    )?;
+
+    let texture_image_view = create_image_view(
+        device,
+        texture_image,
+        format,
+        vk::ImageAspectFlags::COLOR,
+        mip_levels,
+    )?;
+
+    Ok(Texture {
+        image: texture_image,
+        memory: texture_image_memory,
+        view: texture_image_view,
+        mip_levels,
+    })
+}
+
+/// 0번 level의 image를 절반씩 줄여가며 blit해서 나머지 mip level을 생성  
+/// 각 level은 blit이 끝난 뒤에 SHADER_READ_ONLY_OPTIMAL layout으로 변경됨
+unsafe fn generate_mipmaps(
+    instance: &Instance,
+    device: &Device,
+    data: &AppData,
+    image: vk::Image,
+    format: vk::Format,
+    width: u32,
+    height: u32,
+    mip_levels: u32,
+) -> Result<()> {
+    // LINEAR filter로 blit하려면 format이 linear filtering을 지원해야 함
+    if !instance
+        .get_physical_device_format_properties(data.physical_device, format)
+        .optimal_tiling_features
+        .contains(vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR)
+    {
+        return Err(anyhow!(
+            "Texture image format does not support linear blitting!"
+        ));
+    }
+
+    let command_buffer = begin_single_time_commands(device, data)?;
+
+    let subresource = vk::ImageSubresourceRange::builder()
+        .aspect_mask(vk::ImageAspectFlags::COLOR)
+        .base_array_layer(0)
+        .layer_count(1)
+        .level_count(1);
+
+    // level마다 subresource_range와 layout만 바꿔가며 재사용함
+    let mut barrier = vk::ImageMemoryBarrier::builder()
+        .image(image)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .subresource_range(subresource);
+
+    let mut mip_width = width;
+    let mut mip_height = height;
+
+    for i in 1..mip_levels {
+        // i - 1번 level에 대한 복사가 끝나면 blit의 source로 사용할 수 있도록 layout을 변경
+        barrier.subresource_range.base_mip_level = i - 1;
+        barrier.old_layout = vk::ImageLayout::TRANSFER_DST_OPTIMAL;
+        barrier.new_layout = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+        barrier.src_access_mask = vk::AccessFlags::TRANSFER_WRITE;
+        barrier.dst_access_mask = vk::AccessFlags::TRANSFER_READ;
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+
+        let src_subresource = vk::ImageSubresourceLayers::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .mip_level(i - 1)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        let dst_subresource = vk::ImageSubresourceLayers::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .mip_level(i)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        // i - 1번 level을 절반 크기로 줄여서 i번 level에 씀
+        // 크기가 1보다 작아지지 않도록 해야 함
+        let blit = vk::ImageBlit::builder()
+            .src_offsets([
+                vk::Offset3D { x: 0, y: 0, z: 0 },
+                vk::Offset3D {
+                    x: mip_width as i32,
+                    y: mip_height as i32,
+                    z: 1,
+                },
+            ])
+            .src_subresource(src_subresource)
+            .dst_offsets([
+                vk::Offset3D { x: 0, y: 0, z: 0 },
+                vk::Offset3D {
+                    x: (if mip_width > 1 { mip_width / 2 } else { 1 }) as i32,
+                    y: (if mip_height > 1 { mip_height / 2 } else { 1 }) as i32,
+                    z: 1,
+                },
+            ])
+            .dst_subresource(dst_subresource);
+
+        device.cmd_blit_image(
+            command_buffer,
+            image,
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[blit],
+            vk::Filter::LINEAR,
+        );
+
+        // blit이 끝난 i - 1번 level은 더 이상 사용하지 않으므로 shader에서 읽을 수 있도록 변경
+        barrier.old_layout = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+        barrier.new_layout = vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL;
+        barrier.src_access_mask = vk::AccessFlags::TRANSFER_READ;
+        barrier.dst_access_mask = vk::AccessFlags::SHADER_READ;
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+
+        if mip_width > 1 {
+            mip_width /= 2;
+        }
+
+        if mip_height > 1 {
+            mip_height /= 2;
+        }
+    }
+
+    // 마지막 level은 blit의 source로 사용되지 않았으므로 따로 layout을 변경
+    barrier.subresource_range.base_mip_level = mip_levels - 1;
+    barrier.old_layout = vk::ImageLayout::TRANSFER_DST_OPTIMAL;
+    barrier.new_layout = vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL;
+    barrier.src_access_mask = vk::AccessFlags::TRANSFER_WRITE;
+    barrier.dst_access_mask = vk::AccessFlags::SHADER_READ;
+
+    device.cmd_pipeline_barrier(
+        command_buffer,
+        vk::PipelineStageFlags::TRANSFER,
+        vk::PipelineStageFlags::FRAGMENT_SHADER,
+        vk::DependencyFlags::empty(),
+        &[] as &[vk::MemoryBarrier],
+        &[] as &[vk::BufferMemoryBarrier],
+        &[barrier],
+    );
+
+    end_single_time_commands(device, data, command_buffer)?;
+
+    Ok(())
+}
+
+/// texture sampler 생성  
+/// sampler는 특정 image에 묶이지 않으므로 여러 texture에서 재사용할 수 있음
+unsafe fn create_texture_sampler(device: &Device, data: &mut AppData) -> Result<()> {
+    let info = vk::SamplerCreateInfo::builder()
+        // 확대(oversampling), 축소(undersampling)시 사용할 filter
+        .mag_filter(vk::Filter::LINEAR)
+        .min_filter(vk::Filter::LINEAR)
+        // image 범위를 벗어난 좌표를 읽을 때 반복해서 읽음
+        .address_mode_u(vk::SamplerAddressMode::REPEAT)
+        .address_mode_v(vk::SamplerAddressMode::REPEAT)
+        .address_mode_w(vk::SamplerAddressMode::REPEAT)
+        .anisotropy_enable(true)
+        .max_anisotropy(16.0)
+        // CLAMP_TO_BORDER를 사용할 때만 사용됨
+        .border_color(vk::BorderColor::INT_OPAQUE_BLACK)
+        // texel 좌표를 0.0 ~ 1.0 범위로 정규화해서 사용
+        .unnormalized_coordinates(false)
+        .compare_enable(false)
+        .compare_op(vk::CompareOp::ALWAYS)
+        // mip level 사이도 선형 보간하는 trilinear filtering을 사용
+        .mipmap_mode(vk::SamplerMipmapMode::LINEAR)
+        .mip_lod_bias(0.0)
+        .min_lod(0.0)
+        // texture마다 mip level 수가 다르므로 가장 많은 texture의 모든 level을 사용할 수 있도록 함
+        .max_lod(
+            data.material_textures
+                .iter()
+                .map(|t| t.mip_levels)
+                .max()
+                .unwrap_or(1) as f32,
+        );
+
+    data.texture_sampler = device.create_sampler(&info, None)?;
+
+    Ok(())
+}
+
+/// image를 생성하고 memory를 할당해서 bind하는 helper function
+unsafe fn create_image(
+    instance: &Instance,
+    device: &Device,
+    data: &AppData,
+    width: u32,
+    height: u32,
+    mip_levels: u32,
+    samples: vk::SampleCountFlags,
+    format: vk::Format,
+    tiling: vk::ImageTiling,
+    usage: vk::ImageUsageFlags,
+    properties: vk::MemoryPropertyFlags,
+) -> Result<(vk::Image, vk::DeviceMemory)> {
+    let info = vk::ImageCreateInfo::builder()
+        .image_type(vk::ImageType::_2D)
+        .extent(vk::Extent3D {
+            width,
+            height,
+            depth: 1,
+        })
+        .mip_levels(mip_levels)
+        .array_layers(1)
+        .format(format)
+        // OPTIMAL은 driver가 정한 순서로 texel을 배치해서 shader에서 효율적으로 접근할 수 있음
+        .tiling(tiling)
+        // 첫번째 transition에서 texel을 보존할 필요가 없으므로 UNDEFINED를 사용
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .usage(usage)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE)
+        .samples(samples);
+
+    let image = device.create_image(&info, None)?;
+
+    let requirements = device.get_image_memory_requirements(image);
+
+    let info = vk::MemoryAllocateInfo::builder()
+        .allocation_size(requirements.size)
+        .memory_type_index(get_memory_type(instance, data, properties, requirements)?);
+
+    let image_memory = device.allocate_memory(&info, None)?;
+
+    device.bind_image_memory(image, image_memory, 0)?;
+
+    Ok((image, image_memory))
+}
+
+/// image view를 생성하는 helper function
+unsafe fn create_image_view(
+    device: &Device,
+    image: vk::Image,
+    format: vk::Format,
+    aspects: vk::ImageAspectFlags,
+    mip_levels: u32,
+) -> Result<vk::ImageView> {
+    let components = vk::ComponentMapping::builder()
+        .r(vk::ComponentSwizzle::IDENTITY)
+        .g(vk::ComponentSwizzle::IDENTITY)
+        .b(vk::ComponentSwizzle::IDENTITY)
+        .a(vk::ComponentSwizzle::IDENTITY);
+
+    let subresource_range = vk::ImageSubresourceRange::builder()
+        .aspect_mask(aspects)
+        .base_mip_level(0)
+        .level_count(mip_levels)
+        .base_array_layer(0)
+        .layer_count(1);
+
+    let info = vk::ImageViewCreateInfo::builder()
+        .image(image)
+        .view_type(vk::ImageViewType::_2D)
+        .format(format)
+        .components(components)
+        .subresource_range(subresource_range);
+
+    Ok(device.create_image_view(&info, None)?)
+}
+
+/// pipeline barrier를 사용해서 image의 layout을 변경  
+/// barrier는 layout 변경과 함께 이전 작업과 이후 작업 사이의 동기화도 담당함
+unsafe fn transition_image_layout(
+    device: &Device,
+    data: &AppData,
+    image: vk::Image,
+    format: vk::Format,
+    old_layout: vk::ImageLayout,
+    new_layout: vk::ImageLayout,
+    mip_levels: u32,
+) -> Result<()> {
+    // layout 변경에 따라 기다려야 하는 작업과 기다리게 할 작업을 결정
+    let (src_access_mask, dst_access_mask, src_stage_mask, dst_stage_mask) =
+        match (old_layout, new_layout) {
+            // 복사는 아무것도 기다릴 필요가 없음
+            (vk::ImageLayout::UNDEFINED, vk::ImageLayout::TRANSFER_DST_OPTIMAL) => (
+                vk::AccessFlags::empty(),
+                vk::AccessFlags::TRANSFER_WRITE,
+                vk::PipelineStageFlags::TOP_OF_PIPE,
+                vk::PipelineStageFlags::TRANSFER,
+            ),
+            // fragment shader에서 읽기 전에 복사가 끝나야 함
+            (vk::ImageLayout::TRANSFER_DST_OPTIMAL, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL) => (
+                vk::AccessFlags::TRANSFER_WRITE,
+                vk::AccessFlags::SHADER_READ,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::FRAGMENT_SHADER,
+            ),
+            _ => return Err(anyhow!("Unsupported image layout transition!")),
+        };
+
+    let command_buffer = begin_single_time_commands(device, data)?;
+
+    let subresource = vk::ImageSubresourceRange::builder()
+        .aspect_mask(vk::ImageAspectFlags::COLOR)
+        .base_mip_level(0)
+        .level_count(mip_levels)
+        .base_array_layer(0)
+        .layer_count(1);
+
+    // queue family의 소유권을 이전하지 않으므로 IGNORED를 사용
+    let barrier = vk::ImageMemoryBarrier::builder()
+        .old_layout(old_layout)
+        .new_layout(new_layout)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .image(image)
+        .subresource_range(subresource)
+        .src_access_mask(src_access_mask)
+        .dst_access_mask(dst_access_mask);
+
+    device.cmd_pipeline_barrier(
+        command_buffer,
+        src_stage_mask,
+        dst_stage_mask,
+        vk::DependencyFlags::empty(),
+        &[] as &[vk::MemoryBarrier],
+        &[] as &[vk::BufferMemoryBarrier],
+        &[barrier],
+    );
+
+    end_single_time_commands(device, data, command_buffer)?;
+
+    Ok(())
+}
+
+/// buffer의 내용을 image로 복사  
+/// image는 TRANSFER_DST_OPTIMAL layout이어야 함
+unsafe fn copy_buffer_to_image(
+    device: &Device,
+    data: &AppData,
+    buffer: vk::Buffer,
+    image: vk::Image,
+    width: u32,
+    height: u32,
+) -> Result<()> {
+    let command_buffer = begin_single_time_commands(device, data)?;
+
+    let subresource = vk::ImageSubresourceLayers::builder()
+        .aspect_mask(vk::ImageAspectFlags::COLOR)
+        .mip_level(0)
+        .base_array_layer(0)
+        .layer_count(1);
+
+    // row_length와 image_height가 0이면 pixel이 빈틈없이 채워져 있다는 의미
+    let region = vk::BufferImageCopy::builder()
+        .buffer_offset(0)
+        .buffer_row_length(0)
+        .buffer_image_height(0)
+        .image_subresource(subresource)
+        .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
+        .image_extent(vk::Extent3D {
+            width,
+            height,
+            depth: 1,
+        });
+
+    device.cmd_copy_buffer_to_image(
+        command_buffer,
+        buffer,
+        image,
+        vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+        &[region],
+    );
+
+    end_single_time_commands(device, data, command_buffer)?;
+
+    Ok(())
+}
+
+/// swapchain image마다 uniform buffer와 joint matrix buffer를 생성  
+/// 매 frame마다 갱신되므로 staging buffer를 사용하지 않고 HOST_VISIBLE memory를 사용함
+unsafe fn create_uniform_buffers(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    data.uniform_buffers.clear();
+    data.uniform_buffers_memory.clear();
+    data.joint_buffers.clear();
+    data.joint_buffers_memory.clear();
+
+    for _ in 0..data.swapchain.images.len() {
+        let (uniform_buffer, uniform_buffer_memory) = create_buffer(
+            instance,
+            device,
+            data,
+            size_of::<UniformBufferObject>() as u64,
+            vk::BufferUsageFlags::UNIFORM_BUFFER,
+            vk::MemoryPropertyFlags::HOST_COHERENT | vk::MemoryPropertyFlags::HOST_VISIBLE,
+        )?;
+
+        data.uniform_buffers.push(uniform_buffer);
+        data.uniform_buffers_memory.push(uniform_buffer_memory);
+
+        let (joint_buffer, joint_buffer_memory) = create_buffer(
+            instance,
+            device,
+            data,
+            size_of::<JointMatrices>() as u64,
+            vk::BufferUsageFlags::UNIFORM_BUFFER,
+            vk::MemoryPropertyFlags::HOST_COHERENT | vk::MemoryPropertyFlags::HOST_VISIBLE,
+        )?;
+
+        data.joint_buffers.push(joint_buffer);
+        data.joint_buffers_memory.push(joint_buffer_memory);
+    }
+
+    Ok(())
+}
+
+/// descriptor pool 생성  
+/// descriptor set은 직접 생성할 수 없고 pool에서 할당해야 함
+unsafe fn create_descriptor_pool(device: &Device, data: &mut AppData) -> Result<()> {
+    // descriptor set마다 uniform buffer와 joint matrix buffer가 있음
+    let ubo_size = vk::DescriptorPoolSize::builder()
+        .type_(vk::DescriptorType::UNIFORM_BUFFER)
+        .descriptor_count(data.swapchain.images.len() as u32 * 2);
+
+    // material descriptor set 하나에 texture가 4개 있음
+    let sampler_size = vk::DescriptorPoolSize::builder()
+        .type_(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+        .descriptor_count(data.material_textures.len() as u32);
+
+    let pool_sizes = &[ubo_size, sampler_size];
+    let info = vk::DescriptorPoolCreateInfo::builder()
+        .pool_sizes(pool_sizes)
+        .max_sets(data.swapchain.images.len() as u32 + 1);
+
+    data.descriptor_pool = device.create_descriptor_pool(&info, None)?;
+
+    Ok(())
+}
+
+/// swapchain image마다 descriptor set을 할당하고 uniform buffer와 joint matrix buffer를 연결
+unsafe fn create_descriptor_sets(device: &Device, data: &mut AppData) -> Result<()> {
+    let layouts = vec![data.descriptor_set_layout; data.swapchain.images.len()];
+    let info = vk::DescriptorSetAllocateInfo::builder()
+        .descriptor_pool(data.descriptor_pool)
+        .set_layouts(&layouts);
+
+    data.descriptor_sets = device.allocate_descriptor_sets(&info)?;
+
+    for i in 0..data.swapchain.images.len() {
+        let info = vk::DescriptorBufferInfo::builder()
+            .buffer(data.uniform_buffers[i])
+            .offset(0)
+            .range(size_of::<UniformBufferObject>() as u64);
+
+        let buffer_info = &[info];
+        let ubo_write = vk::WriteDescriptorSet::builder()
+            .dst_set(data.descriptor_sets[i])
+            .dst_binding(0)
+            .dst_array_element(0)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+            .buffer_info(buffer_info);
+
+        let info = vk::DescriptorBufferInfo::builder()
+            .buffer(data.joint_buffers[i])
+            .offset(0)
+            .range(size_of::<JointMatrices>() as u64);
+
+        let joint_info = &[info];
+        let joint_write = vk::WriteDescriptorSet::builder()
+            .dst_set(data.descriptor_sets[i])
+            .dst_binding(1)
+            .dst_array_element(0)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+            .buffer_info(joint_info);
+
+        device.update_descriptor_sets(&[ubo_write, joint_write], &[] as &[vk::CopyDescriptorSet]);
+    }
+
+    // descriptor pool이 swapchain과 함께 다시 생성되므로 material descriptor set도 다시 할당함
+    let layouts = &[data.material_descriptor_set_layout];
+    let info = vk::DescriptorSetAllocateInfo::builder()
+        .descriptor_pool(data.descriptor_pool)
+        .set_layouts(layouts);
+
+    data.material_descriptor_set = device.allocate_descriptor_sets(&info)?[0];
+
+    // 모든 material texture가 같은 sampler를 사용함
+    let image_infos = data
+        .material_textures
+        .iter()
+        .map(|t| {
+            vk::DescriptorImageInfo::builder()
+                .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .image_view(t.view)
+                .sampler(data.texture_sampler)
+                .build()
+        })
+        .collect::<Vec<_>>();
+
+    // binding 0부터 연속된 binding에 한 번에 연결함
+    let material_write = vk::WriteDescriptorSet::builder()
+        .dst_set(data.material_descriptor_set)
+        .dst_binding(0)
+        .dst_array_element(0)
+        .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+        .image_info(&image_infos);
+
+    device.update_descriptor_sets(&[material_write], &[] as &[vk::CopyDescriptorSet]);
+
+    Ok(())
+}
+
+/// semaphore를 생성하는 함수
+unsafe fn create_sync_objects(device: &Device, data: &mut AppData) -> Result<()> {
+    let semaphore_info = vk::SemaphoreCreateInfo::builder();
+    let fence_info = vk::FenceCreateInfo::builder().flags(vk::FenceCreateFlags::SIGNALED);
+
+    for _ in 0..MAX_FRAMES_IN_FLIGHT {
+        data.image_available_semaphores
+            .push(device.create_semaphore(&semaphore_info, None)?);
+
+        data.in_flight_fences
+            .push(device.create_fence(&fence_info, None)?);
+    }
+
+    data.images_in_flight = data
+        .swapchain
+        .images
+        .iter()
+        .map(|_| vk::Fence::null())
+        .collect();
+
+    // render finished semaphore는 frame이 아닌 swapchain image마다 하나씩 존재
+    create_render_finished_semaphores(
+        device,
+        &data.swapchain,
+        &mut data.render_finished_semaphores,
+    )?;
+
+    Ok(())
+}
+
+/// 구간별 GPU time을 측정하기 위한 profiler를 생성  
+/// command buffer는 graphics queue에 제출되므로 graphics queue family에서 timestamp를 지원해야 함
+unsafe fn create_profiler(instance: &Instance, device: &Device, data: &mut AppData) -> Result<()> {
+    let indices = QueueFamilyIndices::get(instance, data.surface, data.physical_device)?;
+
+    data.profiler = GpuProfiler::new(
+        instance,
+        device,
+        data.physical_device,
+        indices.graphics,
+        MAX_FRAMES_IN_FLIGHT,
+        MAX_GPU_SCOPES,
+    )?;
+
+    Ok(())
+}
+
+/// swapchain과 관계없이 한 번만 생성되는 오브젝트에 debug 이름을 붙임
+unsafe fn set_object_names(instance: &Instance, device: &Device, data: &AppData) -> Result<()> {
+    set_object_name(
+        instance,
+        device,
+        data.descriptor_set_layout,
+        "Descriptor Set Layout",
+    )?;
+    set_object_name(
+        instance,
+        device,
+        data.material_descriptor_set_layout,
+        "Material Descriptor Set Layout",
+    )?;
+    set_object_name(instance, device, data.command_pool, "Global Command Pool")?;
+    for (i, command_pool) in data.command_pools.iter().enumerate() {
+        set_object_name(
+            instance,
+            device,
+            *command_pool,
+            &format!("Frame Command Pool {}", i),
+        )?;
+    }
+    set_object_name(instance, device, data.geometry_buffer, "Geometry Buffer")?;
+    set_object_name(
+        instance,
+        device,
+        data.geometry_buffer_memory,
+        "Geometry Buffer Memory",
+    )?;
+    for (texture, name) in
+        data.material_textures
+            .iter()
+            .zip(["Albedo", "Normal", "Metallic Roughness", "Occlusion"])
+    {
+        set_object_name(
+            instance,
+            device,
+            texture.image,
+            &format!("{} Texture", name),
+        )?;
+        set_object_name(
+            instance,
+            device,
+            texture.memory,
+            &format!("{} Texture Memory", name),
+        )?;
+        set_object_name(
+            instance,
+            device,
+            texture.view,
+            &format!("{} Texture View", name),
+        )?;
+    }
+    set_object_name(instance, device, data.texture_sampler, "Texture Sampler")?;
+    if !data.profiler.query_pool().is_null() {
+        set_object_name(
+            instance,
+            device,
+            data.profiler.query_pool(),
+            "Timestamp Query Pool",
+        )?;
+    }
+
+    for i in 0..MAX_FRAMES_IN_FLIGHT {
+        set_object_name(
+            instance,
+            device,
+            data.image_available_semaphores[i],
+            &format!("Image Available Semaphore {}", i),
+        )?;
+        set_object_name(
+            instance,
+            device,
+            data.in_flight_fences[i],
+            &format!("In Flight Fence {}", i),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// swapchain을 다시 생성할 때 함께 다시 생성되는 오브젝트에 debug 이름을 붙임
+unsafe fn set_swapchain_object_names(
+    instance: &Instance,
+    device: &Device,
+    data: &AppData,
+) -> Result<()> {
+    set_object_name(instance, device, data.swapchain.handle, "Swapchain")?;
+    for (i, image) in data.swapchain.images.iter().enumerate() {
+        set_object_name(instance, device, *image, &format!("Swapchain Image {}", i))?;
+    }
+    for (i, image_view) in data.swapchain_image_views.iter().enumerate() {
+        set_object_name(
+            instance,
+            device,
+            *image_view,
+            &format!("Swapchain Image View {}", i),
+        )?;
+    }
+    for (i, framebuffer) in data.framebuffers.iter().enumerate() {
+        set_object_name(
+            instance,
+            device,
+            *framebuffer,
+            &format!("Framebuffer {}", i),
+        )?;
+    }
+    for (i, uniform_buffer) in data.uniform_buffers.iter().enumerate() {
+        set_object_name(
+            instance,
+            device,
+            *uniform_buffer,
+            &format!("Uniform Buffer {}", i),
+        )?;
+    }
+    for (i, joint_buffer) in data.joint_buffers.iter().enumerate() {
+        set_object_name(
+            instance,
+            device,
+            *joint_buffer,
+            &format!("Joint Buffer {}", i),
+        )?;
+    }
+    for (i, descriptor_set) in data.descriptor_sets.iter().enumerate() {
+        set_object_name(
+            instance,
+            device,
+            *descriptor_set,
+            &format!("Descriptor Set {}", i),
+        )?;
+    }
+    set_object_name(instance, device, data.descriptor_pool, "Descriptor Pool")?;
+    set_object_name(
+        instance,
+        device,
+        data.material_descriptor_set,
+        "Material Descriptor Set",
+    )?;
+
+    // render pass와 pipeline은 swapchain format이 바뀔 때만 다시 생성되지만 다시 이름을 붙여도 문제없음
+    set_object_name(instance, device, data.render_pass, "Main Render Pass")?;
+    set_object_name(instance, device, data.pipeline_layout, "Pipeline Layout")?;
+    set_object_name(instance, device, data.pipeline, "Graphics Pipeline")?;
+
+    set_object_name(instance, device, data.color_image, "Color Image")?;
+    set_object_name(instance, device, data.color_image_view, "Color Image View")?;
+    set_object_name(instance, device, data.depth_image, "Depth Image")?;
+    set_object_name(instance, device, data.depth_image_view, "Depth Image View")?;
+    for (i, semaphore) in data.render_finished_semaphores.iter().enumerate() {
+        set_object_name(
+            instance,
+            device,
+            *semaphore,
+            &format!("Render Finished Semaphore {}", i),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// vertex shader에 전달될 vertex 데이터  
+/// shader의 입력과 memory layout이 일치해야 하므로 `#[repr(C)]`를 사용함
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct Vertex {
+    pos: Vec3,
+    normal: Vec3,
+    tex_coord: Vec2,
+    // vertex에 영향을 주는 joint 4개의 index
+    joints: [u32; 4],
+    // 각 joint가 영향을 주는 정도
+    weights: Vec4,
+}
+
+impl Vertex {
+    const fn new(
+        pos: Vec3,
+        normal: Vec3,
+        tex_coord: Vec2,
+        joints: [u32; 4],
+        weights: Vec4,
+    ) -> Self {
+        Self {
+            pos,
+            normal,
+            tex_coord,
+            joints,
+            weights,
+        }
+    }
+
+    /// vertex 데이터가 buffer에 어떤 간격으로 저장되어 있는지 설명
+    fn binding_description() -> vk::VertexInputBindingDescription {
+        vk::VertexInputBindingDescription::builder()
+            .binding(0)
+            .stride(size_of::<Vertex>() as u32)
+            // instance가 아닌 vertex마다 다음 데이터로 이동
+            .input_rate(vk::VertexInputRate::VERTEX)
+            .build()
+    }
+
+    /// vertex 데이터의 각 attribute를 shader의 location과 연결
+    fn attribute_descriptions() -> [vk::VertexInputAttributeDescription; 5] {
+        let pos = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(0)
+            .format(vk::Format::R32G32B32_SFLOAT)
+            .offset(0)
+            .build();
+        let normal = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(1)
+            .format(vk::Format::R32G32B32_SFLOAT)
+            .offset(size_of::<Vec3>() as u32)
+            .build();
+        let tex_coord = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(2)
+            .format(vk::Format::R32G32_SFLOAT)
+            .offset((size_of::<Vec3>() + size_of::<Vec3>()) as u32)
+            .build();
+        // joint index는 정수로 읽어야 uniform 배열의 index로 사용할 수 있음
+        let joints = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(3)
+            .format(vk::Format::R32G32B32A32_UINT)
+            .offset((size_of::<Vec3>() + size_of::<Vec3>() + size_of::<Vec2>()) as u32)
+            .build();
+        let weights = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(4)
+            .format(vk::Format::R32G32B32A32_SFLOAT)
+            .offset(
+                (size_of::<Vec3>() + size_of::<Vec3>() + size_of::<Vec2>() + size_of::<[u32; 4]>())
+                    as u32,
+            )
+            .build();
+        [pos, normal, tex_coord, joints, weights]
+    }
+}
+
+/// GPU에 upload한 texture와 texture를 읽기 위한 image view
+#[derive(Copy, Clone, Debug, Default)]
+struct Texture {
+    image: vk::Image,
+    memory: vk::DeviceMemory,
+    view: vk::ImageView,
+    mip_levels: u32,
+}
+
+/// fragment shader의 push constant block에서 model matrix 뒤에 이어지는 material factor  
+/// shader에서 선언한 순서와 같아야 함
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+struct MaterialConstants {
+    base_color_factor: [f32; 4],
+    metallic_factor: f32,
+    roughness_factor: f32,
+    normal_scale: f32,
+    occlusion_strength: f32,
+}
+
+impl MaterialConstants {
+    fn new(material: &Material) -> Self {
+        Self {
+            base_color_factor: material.base_color_factor,
+            metallic_factor: material.metallic_factor,
+            roughness_factor: material.roughness_factor,
+            normal_scale: material.normal_scale,
+            occlusion_strength: material.occlusion_strength,
+        }
+    }
+}
+
+/// fragment shader의 `PointLight`와 같은 layout을 가지는 구조체  
+/// std140 layout에서 vec3는 16 bytes로 정렬되므로 vec4를 사용함
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct PointLight {
+    position: Vec4,
+    // rgb는 색과 세기를 곱한 값
+    color: Vec4,
+}
+
+/// vertex shader와 fragment shader의 uniform block과 같은 layout을 가지는 구조체  
+/// vertex shader는 앞의 view, proj만 선언해서 사용함
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct UniformBufferObject {
+    view: Mat4,
+    proj: Mat4,
+    // specular를 계산하기 위한 camera의 world 좌표
+    camera_position: Vec4,
+    // 표면에서 directional light를 향하는 방향
+    light_direction: Vec4,
+    light_color: Vec4,
+    point_lights: [PointLight; POINT_LIGHT_COUNT],
+}
+
+/// vertex shader의 `JointMatrices` uniform block과 같은 layout을 가지는 구조체
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct JointMatrices {
+    joints: [Mat4; MAX_JOINTS],
+}
+
+fn main() -> Result<()> {
+    pretty_env_logger::init();
+
+    // Window
+    let event_loop = EventLoop::new()?;
+    let window = WindowBuilder::new()
+        .with_title(TITLE)
+        .with_inner_size(LogicalSize::new(1024, 768))
+        .build(&event_loop)?;
+
+    // App
+    let mut app = unsafe { App::create(&window)? };
+    // window가 최소화되어 크기가 0인 동안에는 그리지 않음
+    let mut minimized = false;
+    event_loop.run(move |event, elwt| {
+        match event {
+            // Request a redraw when all events were processed.
+            Event::AboutToWait => window.request_redraw(),
+            Event::WindowEvent { event, .. } => {
+                // camera 조작에 사용되는 keyboard와 mouse event를 controller에 전달
+                app.camera_controller.handle_window_event(&event);
+
+                match event {
+                    // window의 크기가 변경되면 다음 frame에서 swapchain을 다시 생성하도록 표시
+                    // 최소화되면 크기가 0이 되므로 복원될 때까지 render를 멈춤
+                    WindowEvent::Resized(size) => {
+                        if size.width == 0 || size.height == 0 {
+                            minimized = true;
+                        } else {
+                            minimized = false;
+                            app.resized = true;
+                        }
+                    }
+                    // Render a frame if our Vulkan app is not being destroyed.
+                    WindowEvent::RedrawRequested if !elwt.exiting() && !minimized => {
+                        // 복구할 수 있는 error는 swapchain이나 surface를 다시 생성하고 다음 frame에서 그림
+                        let result = match unsafe { app.render(&window) } {
+                            Err(RenderError::OutOfDate | RenderError::Suboptimal) => unsafe {
+                                app.recreate_swapchain(&window)
+                            },
+                            Err(RenderError::SurfaceLost) => {
+                                warn!("Surface was lost, recreating it.");
+                                unsafe { app.recreate_surface(&window) }
+                            }
+                            Err(RenderError::Fatal(error)) => Err(error),
+                            Ok(()) => Ok(()),
+                        };
+
+                        // 복구할 수 없는 error는 panic 대신 log를 남기고 앱을 종료함
+                        if let Err(error) = result {
+                            error!("Failed to render frame: {:?}", error);
+                            elwt.exit();
+                        }
+                    }
+                    // Destroy our Vulkan app.
+                    WindowEvent::CloseRequested => elwt.exit(),
+                    _ => {}
+                }
+            }
+            // event loop가 어떤 이유로 종료되든 한 번만 destroy되도록 여기서 정리함
+            Event::LoopExiting => unsafe {
+                // device가 lost 상태여도 남은 오브젝트는 파괴해야 하므로 error를 무시함
+                let _ = app.device.device_wait_idle();
+                app.destroy();
+            },
+            _ => {}
+        }
+    })?;
+
+    Ok(())
+}
@@ -335,11 +335,12 @@ impl App {
                 .destroy_debug_utils_messenger_ext(self.data.messenger, None);
         }
 
+        self.device.destroy_device(None);
+        // device가 파괴된 후에 instance를 파괴해야 함
         // 프로그램이 종료되면 instance가 파괴되기 전에 surface를 파괴해야 함
         self.instance.destroy_surface_khr(self.data.surface, None);
         // 프로그램이 종료되면 인스턴스를 파괴해야 함
         self.instance.destroy_instance(None);
-        self.device.destroy_device(None);
     }
 }
 
@@ -397,9 +398,7 @@ impl QueueFamilyIndices {
         if let (Some(graphics), Some(present)) = (graphics, present) {
             Ok(Self { graphics, present })
         } else {
-            Err(anyhow!(SuitabilityError(
-                "Missing required queue families."
-            )))
+            Err(anyhow!(SuitabilityError("required queue families")))
         }
     }
 }
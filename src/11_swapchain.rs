@@ -85,13 +85,14 @@ impl App {
                 .destroy_debug_utils_messenger_ext(self.data.messenger, None);
         }
 
+        // device전에 청소되어야 함
+        self.device.destroy_swapchain_khr(self.data.swapchain, None);
+        self.device.destroy_device(None);
+        // device가 파괴된 후에 instance를 파괴해야 함
         // 프로그램이 종료되면 instance가 파괴되기 전에 surface를 파괴해야 함
         self.instance.destroy_surface_khr(self.data.surface, None);
         // 프로그램이 종료되면 인스턴스를 파괴해야 함
         self.instance.destroy_instance(None);
-        // device전에 청소되어야 함
-        self.device.destroy_swapchain_khr(self.data.swapchain, None);
-        self.device.destroy_device(None);
     }
 }
 
@@ -157,9 +158,7 @@ impl QueueFamilyIndices {
         if let (Some(graphics), Some(present)) = (graphics, present) {
             Ok(Self { graphics, present })
         } else {
-            Err(anyhow!(SuitabilityError(
-                "Missing required queue families."
-            )))
+            Err(anyhow!(SuitabilityError("required queue families")))
         }
     }
 }
@@ -233,7 +232,7 @@ unsafe fn check_physical_device(
 
     let support = SwapchainSupport::get(instance, data, physical_device)?;
     if support.formats.is_empty() || support.present_modes.is_empty() {
-        return Err(anyhow!(SuitabilityError("Insufficient swapchain support.")));
+        return Err(anyhow!(SuitabilityError("sufficient swapchain support")));
     }
 
     Ok(())
@@ -349,9 +348,7 @@ unsafe fn check_physical_device_extensions(
     if DEVICE_EXTENSIONS.iter().all(|e| extensions.contains(e)) {
         Ok(())
     } else {
-        Err(anyhow!(SuitabilityError(
-            "Missing required device extensions."
-        )))
+        Err(anyhow!(SuitabilityError("required device extensions")))
     }
 }
 
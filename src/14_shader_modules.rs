@@ -94,13 +94,14 @@ impl App {
             .iter()
             .for_each(|v| self.device.destroy_image_view(*v, None));
 
+        // device전에 청소되어야 함
+        self.device.destroy_swapchain_khr(self.data.swapchain, None);
+        self.device.destroy_device(None);
+        // device가 파괴된 후에 instance를 파괴해야 함
         // 프로그램이 종료되면 instance가 파괴되기 전에 surface를 파괴해야 함
         self.instance.destroy_surface_khr(self.data.surface, None);
         // 프로그램이 종료되면 인스턴스를 파괴해야 함
         self.instance.destroy_instance(None);
-        // device전에 청소되어야 함
-        self.device.destroy_swapchain_khr(self.data.swapchain, None);
-        self.device.destroy_device(None);
     }
 }
 
@@ -168,9 +169,7 @@ impl QueueFamilyIndices {
         if let (Some(graphics), Some(present)) = (graphics, present) {
             Ok(Self { graphics, present })
         } else {
-            Err(anyhow!(SuitabilityError(
-                "Missing required queue families."
-            )))
+            Err(anyhow!(SuitabilityError("required queue families")))
         }
     }
 }
@@ -244,7 +243,7 @@ unsafe fn check_physical_device(
 
     let support = SwapchainSupport::get(instance, data, physical_device)?;
     if support.formats.is_empty() || support.present_modes.is_empty() {
-        return Err(anyhow!(SuitabilityError("Insufficient swapchain support.")));
+        return Err(anyhow!(SuitabilityError("sufficient swapchain support")));
     }
 
     Ok(())
@@ -382,8 +381,8 @@ unsafe fn create_swapchain_image_views(device: &Device, data: &mut AppData) -> R
 
 // pipeline 생성
 unsafe fn create_pipeline(device: &Device, data: &mut AppData) -> Result<()> {
-    let vert = include_bytes!("../shaders/vert.spv");
-    let frag = include_bytes!("../shaders/frag.spv");
+    let vert = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/vert.spv"));
+    let frag = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/frag.spv"));
 
     let vert_shader_module = create_shader_module(device, &vert[..])?;
     let frag_shader_module = create_shader_module(device, &frag[..])?;
@@ -428,9 +427,7 @@ unsafe fn check_physical_device_extensions(
     if DEVICE_EXTENSIONS.iter().all(|e| extensions.contains(e)) {
         Ok(())
     } else {
-        Err(anyhow!(SuitabilityError(
-            "Missing required device extensions."
-        )))
+        Err(anyhow!(SuitabilityError("required device extensions")))
     }
 }
 
@@ -118,7 +118,7 @@ impl App {
         let wait_semaphores = &[self.data.image_available_semaphores[self.frame]];
         let wait_stages = &[vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
         let command_buffers = &[self.data.command_buffers[image_index as usize]];
-        let signal_semaphores = &[self.data.render_finished_semaphores[self.frame]];
+        let signal_semaphores = &[self.data.render_finished_semaphores[image_index]];
         let submit_info = vk::SubmitInfo::builder()
             .wait_semaphores(wait_semaphores)
             .wait_dst_stage_mask(wait_stages)
@@ -291,9 +291,7 @@ impl QueueFamilyIndices {
         if let (Some(graphics), Some(present)) = (graphics, present) {
             Ok(Self { graphics, present })
         } else {
-            Err(anyhow!(SuitabilityError(
-                "Missing required queue families."
-            )))
+            Err(anyhow!(SuitabilityError("required queue families")))
         }
     }
 }
@@ -364,9 +362,7 @@ unsafe fn check_physical_device_extensions(
     if DEVICE_EXTENSIONS.iter().all(|e| extensions.contains(e)) {
         Ok(())
     } else {
-        Err(anyhow!(SuitabilityError(
-            "Missing required device extensions."
-        )))
+        Err(anyhow!(SuitabilityError("required device extensions")))
     }
 }
 
@@ -386,7 +382,7 @@ unsafe fn check_physical_device(
 
     let support = SwapchainSupport::get(instance, data, physical_device)?;
     if support.formats.is_empty() || support.present_modes.is_empty() {
-        return Err(anyhow!(SuitabilityError("Insufficient swapchain support.")));
+        return Err(anyhow!(SuitabilityError("sufficient swapchain support")));
     }
 
     Ok(())
@@ -754,8 +750,8 @@ unsafe fn create_render_pass(
 
 /// pipeline 생성
 unsafe fn create_pipeline(device: &Device, data: &mut AppData) -> Result<()> {
-    let vert = include_bytes!("../shaders/vert.spv");
-    let frag = include_bytes!("../shaders/frag.spv");
+    let vert = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/vert.spv"));
+    let frag = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/frag.spv"));
 
     let vert_shader_module = create_shader_module(device, &vert[..])?;
     let frag_shader_module = create_shader_module(device, &frag[..])?;
@@ -949,8 +945,6 @@ unsafe fn create_sync_objects(device: &Device, data: &mut AppData) -> Result<()>
     for _ in 0..MAX_FRAMES_IN_FLIGHT {
         data.image_available_semaphores
             .push(device.create_semaphore(&semaphore_info, None)?);
-        data.render_finished_semaphores
-            .push(device.create_semaphore(&semaphore_info, None)?);
 
         data.in_flight_fences
             .push(device.create_fence(&fence_info, None)?);
@@ -962,6 +956,28 @@ unsafe fn create_sync_objects(device: &Device, data: &mut AppData) -> Result<()>
         .map(|_| vk::Fence::null())
         .collect();
 
+    // render finished semaphore는 frame이 아닌 swapchain image마다 하나씩 존재
+    create_render_finished_semaphores(device, data)?;
+
+    Ok(())
+}
+
+/// swapchain image마다 하나씩 사용하는 render finished semaphore를 생성  
+/// present가 semaphore를 다 기다렸는지는 알 수 없으므로 frame마다 semaphore를 돌려쓰면 아직 present가 기다리는 semaphore를 다시 signal할 수 있음  
+/// 같은 image를 다시 acquire했다면 그 image의 이전 present는 끝났으므로 image마다 semaphore를 두면 안전함  
+/// swapchain을 다시 생성하면 image의 개수가 바뀔 수 있으므로 이전 semaphore를 파괴하고 새로 만듦
+unsafe fn create_render_finished_semaphores(device: &Device, data: &mut AppData) -> Result<()> {
+    data.render_finished_semaphores
+        .drain(..)
+        .for_each(|s| device.destroy_semaphore(s, None));
+
+    let semaphore_info = vk::SemaphoreCreateInfo::builder();
+    data.render_finished_semaphores = data
+        .swapchain_images
+        .iter()
+        .map(|_| device.create_semaphore(&semaphore_info, None))
+        .collect::<Result<_, _>>()?;
+
     Ok(())
 }
 
@@ -0,0 +1,639 @@
+#![allow(
+    dead_code,
+    unused_variables,
+    clippy::too_many_arguments,
+    clippy::unnecessary_wraps
+)]
+
+use anyhow::{anyhow, Result};
+use log::*;
+use vulkanalia::loader::{LibloadingLoader, LIBRARY};
+use vulkanalia::prelude::v1_0::*;
+
+use vulkan_tutorial::config;
+use vulkan_tutorial::device::{select_physical_device, SuitabilityError};
+use vulkan_tutorial::golden::{headless_frames, headless_output};
+use vulkan_tutorial::instance::{
+    create_headless_instance, PORTABILITY_MACOS_VERSION, VALIDATION_LAYER,
+};
+use vulkan_tutorial::pipeline::create_shader_module;
+
+use vulkanalia::vk::ExtDebugUtilsExtension;
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::ptr::copy_nonoverlapping as memcpy;
+
+/// offscreen image의 크기
+const WIDTH: u32 = 1024;
+const HEIGHT: u32 = 768;
+
+/// offscreen image의 format  
+/// PNG와 같은 RGBA 순서이므로 저장할 때 swizzle이 필요 없음
+const FORMAT: vk::Format = vk::Format::R8G8B8A8_SRGB;
+
+/// 그린 결과를 저장할 파일  
+/// golden image test에서는 `HEADLESS_OUTPUT_ENV`로 다른 경로를 지정함
+const OUTPUT_PATH: &str = "headless.png";
+
+/// Our Vulkan app.  
+/// window와 surface 없이 offscreen image에 한 번 그리고 결과를 파일로 저장함  
+/// display server가 없는 CI container 같은 환경에서도 실행할 수 있음
+#[derive(Clone, Debug)]
+struct App {
+    // vulkan entry point를 저장하기 위한 필드
+    entry: Entry,
+    // vulkan instance를 저장하기 위한 필드
+    instance: Instance,
+    data: AppData,
+    device: Device,
+}
+
+impl App {
+    /// Creates our Vulkan app.
+    unsafe fn create() -> Result<Self> {
+        // Vulkan command를 Vulkan shared library에서 로드하기 위해 사용됨
+        let loader = LibloadingLoader::new(LIBRARY)?;
+        let entry = Entry::new(loader).map_err(|b| anyhow!("{}", b))?;
+        let mut data = AppData::default();
+        let (instance, messenger) = create_headless_instance(&entry)?;
+        data.messenger = messenger;
+
+        // surface가 없으므로 present 지원 여부와 swapchain extension은 검사하지 않음
+        data.physical_device = select_physical_device(&instance, |p| {
+            get_graphics_queue_family(&instance, p).map(|_| ())
+        })?;
+        data.graphics_queue_family = get_graphics_queue_family(&instance, data.physical_device)?;
+
+        let device = create_logical_device(&entry, &instance, &mut data)?;
+        create_color_image(&instance, &device, &mut data)?;
+        create_render_pass(&device, &mut data)?;
+        create_pipeline(&device, &mut data)?;
+        create_framebuffer(&device, &mut data)?;
+        create_readback_buffer(&instance, &device, &mut data)?;
+        create_command_pool(&device, &mut data)?;
+        create_command_buffer(&device, &mut data)?;
+
+        Ok(Self {
+            entry,
+            instance,
+            data,
+            device,
+        })
+    }
+
+    /// offscreen image에 삼각형을 그리고 readback buffer로 복사한 뒤 GPU가 끝날 때까지 기다림
+    unsafe fn render(&mut self) -> Result<()> {
+        let command_buffer = self.data.command_buffer;
+
+        let info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+
+        self.device.begin_command_buffer(command_buffer, &info)?;
+
+        let render_area = vk::Rect2D::builder()
+            .offset(vk::Offset2D::default())
+            .extent(vk::Extent2D {
+                width: WIDTH,
+                height: HEIGHT,
+            });
+
+        let color_clear_value = vk::ClearValue {
+            color: vk::ClearColorValue {
+                float32: [0.0, 0.0, 0.0, 1.0],
+            },
+        };
+
+        let clear_values = &[color_clear_value];
+        let info = vk::RenderPassBeginInfo::builder()
+            .render_pass(self.data.render_pass)
+            .framebuffer(self.data.framebuffer)
+            .render_area(render_area)
+            .clear_values(clear_values);
+
+        self.device
+            .cmd_begin_render_pass(command_buffer, &info, vk::SubpassContents::INLINE);
+        self.device.cmd_bind_pipeline(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            self.data.pipeline,
+        );
+        self.device.cmd_draw(command_buffer, 3, 1, 0, 0);
+        self.device.cmd_end_render_pass(command_buffer);
+
+        // render pass가 끝나면 color image는 TRANSFER_SRC_OPTIMAL layout이 됨
+        let subresource = vk::ImageSubresourceLayers::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .mip_level(0)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        // row_length와 image_height가 0이면 pixel이 빈틈없이 채워지므로 PNG의 row와 같은 layout이 됨
+        let region = vk::BufferImageCopy::builder()
+            .buffer_offset(0)
+            .buffer_row_length(0)
+            .buffer_image_height(0)
+            .image_subresource(subresource)
+            .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
+            .image_extent(vk::Extent3D {
+                width: WIDTH,
+                height: HEIGHT,
+                depth: 1,
+            });
+
+        self.device.cmd_copy_image_to_buffer(
+            command_buffer,
+            self.data.color_image,
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            self.data.readback_buffer,
+            &[region],
+        );
+
+        // 복사가 끝난 뒤에 host에서 buffer를 읽을 수 있도록 함
+        let barrier = vk::BufferMemoryBarrier::builder()
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .buffer(self.data.readback_buffer)
+            .offset(0)
+            .size(vk::WHOLE_SIZE)
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::HOST_READ);
+
+        self.device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::HOST,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[barrier],
+            &[] as &[vk::ImageMemoryBarrier],
+        );
+
+        self.device.end_command_buffer(command_buffer)?;
+
+        // present하지 않으므로 semaphore 없이 제출하고 queue가 idle 상태가 될 때까지 기다림
+        let command_buffers = &[command_buffer];
+        let info = vk::SubmitInfo::builder().command_buffers(command_buffers);
+
+        self.device
+            .queue_submit(self.data.graphics_queue, &[info], vk::Fence::null())?;
+        self.device.queue_wait_idle(self.data.graphics_queue)?;
+
+        Ok(())
+    }
+
+    /// readback buffer에 복사된 offscreen image를 PNG 파일로 저장
+    unsafe fn save(&self, path: &Path) -> Result<()> {
+        let size = (WIDTH * HEIGHT * 4) as usize;
+        let mut pixels = vec![0u8; size];
+
+        let memory = self.device.map_memory(
+            self.data.readback_buffer_memory,
+            0,
+            size as u64,
+            vk::MemoryMapFlags::empty(),
+        )?;
+        memcpy(memory.cast(), pixels.as_mut_ptr(), size);
+        self.device.unmap_memory(self.data.readback_buffer_memory);
+
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, WIDTH, HEIGHT);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+
+        Ok(())
+    }
+
+    /// Destroys our Vulkan app.
+    unsafe fn destroy(&mut self) {
+        // command pool이 파괴되면 할당된 command buffer도 함께 해제됨
+        self.device
+            .destroy_command_pool(self.data.command_pool, None);
+        self.device.destroy_buffer(self.data.readback_buffer, None);
+        self.device
+            .free_memory(self.data.readback_buffer_memory, None);
+        self.device.destroy_framebuffer(self.data.framebuffer, None);
+        self.device.destroy_pipeline(self.data.pipeline, None);
+        self.device
+            .destroy_pipeline_layout(self.data.pipeline_layout, None);
+        self.device.destroy_render_pass(self.data.render_pass, None);
+        self.device
+            .destroy_image_view(self.data.color_image_view, None);
+        self.device.destroy_image(self.data.color_image, None);
+        self.device.free_memory(self.data.color_image_memory, None);
+
+        self.device.destroy_device(None);
+
+        if !self.data.messenger.is_null() {
+            // 프로그램이 종료되기 전에 디버그 메세지 핸들러를 파괴
+            self.instance
+                .destroy_debug_utils_messenger_ext(self.data.messenger, None);
+        }
+
+        // surface가 없으므로 device 다음에 바로 instance를 파괴
+        self.instance.destroy_instance(None);
+    }
+}
+
+/// The Vulkan handles and associated properties used by our Vulkan app.  
+/// Vulkan 리소스 컨테이너 역할
+#[derive(Clone, Debug, Default)]
+struct AppData {
+    // debug messenger 핸들
+    messenger: vk::DebugUtilsMessengerEXT,
+    // 선택된 physical device
+    physical_device: vk::PhysicalDevice,
+    // graphics queue family의 index
+    graphics_queue_family: u32,
+    // graphics command를 제출할 queue
+    graphics_queue: vk::Queue,
+    // swapchain image 대신 그릴 offscreen image
+    color_image: vk::Image,
+    color_image_memory: vk::DeviceMemory,
+    color_image_view: vk::ImageView,
+    // render pass 핸들
+    render_pass: vk::RenderPass,
+    // pipeline layout 핸들
+    pipeline_layout: vk::PipelineLayout,
+    // graphics pipeline 핸들
+    pipeline: vk::Pipeline,
+    // offscreen image를 attachment로 사용하는 framebuffer
+    framebuffer: vk::Framebuffer,
+    // offscreen image를 host에서 읽기 위해 복사해둘 buffer
+    readback_buffer: vk::Buffer,
+    readback_buffer_memory: vk::DeviceMemory,
+    // command pool 핸들
+    command_pool: vk::CommandPool,
+    // 한 번만 기록하고 제출할 command buffer
+    command_buffer: vk::CommandBuffer,
+}
+
+/// graphics command를 지원하는 queue family의 index를 반환  
+/// present를 하지 않으므로 surface 지원 여부는 확인하지 않음
+unsafe fn get_graphics_queue_family(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+) -> Result<u32> {
+    instance
+        .get_physical_device_queue_family_properties(physical_device)
+        .iter()
+        .position(|p| p.queue_flags.contains(vk::QueueFlags::GRAPHICS))
+        .map(|i| i as u32)
+        .ok_or_else(|| anyhow!(SuitabilityError("graphics queue family")))
+}
+
+/// graphics queue 하나만 가지는 logical device를 생성  
+/// swapchain을 사용하지 않으므로 swapchain extension도 활성화하지 않음
+unsafe fn create_logical_device(
+    entry: &Entry,
+    instance: &Instance,
+    data: &mut AppData,
+) -> Result<Device> {
+    let queue_priorities = &[1.0];
+    let queue_info = vk::DeviceQueueCreateInfo::builder()
+        .queue_family_index(data.graphics_queue_family)
+        .queue_priorities(queue_priorities);
+
+    let layers = if config::get()?.validation {
+        vec![VALIDATION_LAYER.as_ptr()]
+    } else {
+        vec![]
+    };
+
+    let mut extensions = vec![];
+
+    // Required by Vulkan SDK on macOS since 1.3.216.
+    if cfg!(target_os = "macos") && entry.version()? >= PORTABILITY_MACOS_VERSION {
+        extensions.push(vk::KHR_PORTABILITY_SUBSET_EXTENSION.name.as_ptr());
+    }
+
+    let features = vk::PhysicalDeviceFeatures::builder();
+
+    let queue_infos = &[queue_info];
+    let info = vk::DeviceCreateInfo::builder()
+        .queue_create_infos(queue_infos)
+        .enabled_layer_names(&layers)
+        .enabled_extension_names(&extensions)
+        .enabled_features(&features);
+
+    let device = instance.create_device(data.physical_device, &info, None)?;
+
+    data.graphics_queue = device.get_device_queue(data.graphics_queue_family, 0);
+
+    Ok(device)
+}
+
+unsafe fn get_memory_type(
+    instance: &Instance,
+    data: &AppData,
+    properties: vk::MemoryPropertyFlags,
+    requirements: vk::MemoryRequirements,
+) -> Result<u32> {
+    let memory = instance.get_physical_device_memory_properties(data.physical_device);
+
+    // memory_type_bits는 사용 가능한 memory type들을 bit field로 나타냄
+    (0..memory.memory_type_count)
+        .find(|i| {
+            let suitable = (requirements.memory_type_bits & (1 << i)) != 0;
+            let memory_type = memory.memory_types[*i as usize];
+            suitable && memory_type.property_flags.contains(properties)
+        })
+        .ok_or_else(|| anyhow!("Failed to find suitable memory type."))
+}
+
+/// swapchain image 대신 그릴 offscreen color image를 생성  
+/// 그린 뒤에 buffer로 복사해야 하므로 TRANSFER_SRC usage도 지정함
+unsafe fn create_color_image(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    let info = vk::ImageCreateInfo::builder()
+        .image_type(vk::ImageType::_2D)
+        .extent(vk::Extent3D {
+            width: WIDTH,
+            height: HEIGHT,
+            depth: 1,
+        })
+        .mip_levels(1)
+        .array_layers(1)
+        .format(FORMAT)
+        .tiling(vk::ImageTiling::OPTIMAL)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE)
+        .samples(vk::SampleCountFlags::_1);
+
+    data.color_image = device.create_image(&info, None)?;
+
+    let requirements = device.get_image_memory_requirements(data.color_image);
+
+    let info = vk::MemoryAllocateInfo::builder()
+        .allocation_size(requirements.size)
+        .memory_type_index(get_memory_type(
+            instance,
+            data,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            requirements,
+        )?);
+
+    data.color_image_memory = device.allocate_memory(&info, None)?;
+
+    device.bind_image_memory(data.color_image, data.color_image_memory, 0)?;
+
+    let subresource_range = vk::ImageSubresourceRange::builder()
+        .aspect_mask(vk::ImageAspectFlags::COLOR)
+        .base_mip_level(0)
+        .level_count(1)
+        .base_array_layer(0)
+        .layer_count(1);
+
+    let info = vk::ImageViewCreateInfo::builder()
+        .image(data.color_image)
+        .view_type(vk::ImageViewType::_2D)
+        .format(FORMAT)
+        .subresource_range(subresource_range);
+
+    data.color_image_view = device.create_image_view(&info, None)?;
+
+    Ok(())
+}
+
+/// offscreen image에 그리는 render pass를 생성  
+/// present하지 않으므로 final layout을 바로 복사할 수 있는 TRANSFER_SRC_OPTIMAL로 지정함
+unsafe fn create_render_pass(device: &Device, data: &mut AppData) -> Result<()> {
+    let color_attachment = vk::AttachmentDescription::builder()
+        .format(FORMAT)
+        .samples(vk::SampleCountFlags::_1)
+        .load_op(vk::AttachmentLoadOp::CLEAR)
+        .store_op(vk::AttachmentStoreOp::STORE)
+        .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+        .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .final_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL);
+
+    let color_attachment_ref = vk::AttachmentReference::builder()
+        .attachment(0)
+        .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+    let color_attachments = &[color_attachment_ref];
+    let subpass = vk::SubpassDescription::builder()
+        .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+        .color_attachments(color_attachments);
+
+    // render pass가 끝난 뒤의 복사가 color attachment 쓰기를 기다리도록 함
+    let dependency = vk::SubpassDependency::builder()
+        .src_subpass(0)
+        .dst_subpass(vk::SUBPASS_EXTERNAL)
+        .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+        .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+        .dst_stage_mask(vk::PipelineStageFlags::TRANSFER)
+        .dst_access_mask(vk::AccessFlags::TRANSFER_READ);
+
+    let attachments = &[color_attachment];
+    let subpasses = &[subpass];
+    let dependencies = &[dependency];
+    let info = vk::RenderPassCreateInfo::builder()
+        .attachments(attachments)
+        .subpasses(subpasses)
+        .dependencies(dependencies);
+
+    data.render_pass = device.create_render_pass(&info, None)?;
+
+    Ok(())
+}
+
+unsafe fn create_pipeline(device: &Device, data: &mut AppData) -> Result<()> {
+    let vert = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/39/vert.spv"));
+    let frag = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/39/frag.spv"));
+
+    let vert_shader_module = create_shader_module(device, &vert[..])?;
+    let frag_shader_module = create_shader_module(device, &frag[..])?;
+
+    let vert_stage = vk::PipelineShaderStageCreateInfo::builder()
+        .stage(vk::ShaderStageFlags::VERTEX)
+        .module(vert_shader_module)
+        .name(b"main\0");
+
+    let frag_stage = vk::PipelineShaderStageCreateInfo::builder()
+        .stage(vk::ShaderStageFlags::FRAGMENT)
+        .module(frag_shader_module)
+        .name(b"main\0");
+
+    // vertex는 shader 안에 있으므로 vertex input이 없음
+    let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder();
+
+    let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::builder()
+        .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+        .primitive_restart_enable(false);
+
+    // offscreen image의 크기는 바뀌지 않으므로 viewport와 scissor를 고정함
+    let viewport = vk::Viewport::builder()
+        .x(0.0)
+        .y(0.0)
+        .width(WIDTH as f32)
+        .height(HEIGHT as f32)
+        .min_depth(0.0)
+        .max_depth(1.0);
+
+    let scissor = vk::Rect2D::builder()
+        .offset(vk::Offset2D { x: 0, y: 0 })
+        .extent(vk::Extent2D {
+            width: WIDTH,
+            height: HEIGHT,
+        });
+
+    let viewports = &[viewport];
+    let scissors = &[scissor];
+    let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+        .viewports(viewports)
+        .scissors(scissors);
+
+    let rasterization_state = vk::PipelineRasterizationStateCreateInfo::builder()
+        .depth_clamp_enable(false)
+        .rasterizer_discard_enable(false)
+        .polygon_mode(vk::PolygonMode::FILL)
+        .line_width(1.0)
+        .cull_mode(vk::CullModeFlags::BACK)
+        .front_face(vk::FrontFace::CLOCKWISE)
+        .depth_bias_enable(false);
+
+    let multisample_state = vk::PipelineMultisampleStateCreateInfo::builder()
+        .sample_shading_enable(false)
+        .rasterization_samples(vk::SampleCountFlags::_1);
+
+    let attachment = vk::PipelineColorBlendAttachmentState::builder()
+        .color_write_mask(vk::ColorComponentFlags::all())
+        .blend_enable(false);
+
+    let attachments = &[attachment];
+    let color_blend_state = vk::PipelineColorBlendStateCreateInfo::builder()
+        .logic_op_enable(false)
+        .logic_op(vk::LogicOp::COPY)
+        .attachments(attachments)
+        .blend_constants([0.0, 0.0, 0.0, 0.0]);
+
+    let layout_info = vk::PipelineLayoutCreateInfo::builder();
+
+    data.pipeline_layout = device.create_pipeline_layout(&layout_info, None)?;
+
+    let stages = &[vert_stage, frag_stage];
+    let info = vk::GraphicsPipelineCreateInfo::builder()
+        .stages(stages)
+        .vertex_input_state(&vertex_input_state)
+        .input_assembly_state(&input_assembly_state)
+        .viewport_state(&viewport_state)
+        .rasterization_state(&rasterization_state)
+        .multisample_state(&multisample_state)
+        .color_blend_state(&color_blend_state)
+        .layout(data.pipeline_layout)
+        .render_pass(data.render_pass)
+        .subpass(0);
+
+    data.pipeline = device
+        .create_graphics_pipelines(vk::PipelineCache::null(), &[info], None)?
+        .0[0];
+
+    device.destroy_shader_module(vert_shader_module, None);
+    device.destroy_shader_module(frag_shader_module, None);
+
+    Ok(())
+}
+
+unsafe fn create_framebuffer(device: &Device, data: &mut AppData) -> Result<()> {
+    let attachments = &[data.color_image_view];
+    let info = vk::FramebufferCreateInfo::builder()
+        .render_pass(data.render_pass)
+        .attachments(attachments)
+        .width(WIDTH)
+        .height(HEIGHT)
+        .layers(1);
+
+    data.framebuffer = device.create_framebuffer(&info, None)?;
+
+    Ok(())
+}
+
+/// offscreen image를 복사해서 host에서 읽을 수 있는 buffer를 생성
+unsafe fn create_readback_buffer(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    let size = (WIDTH * HEIGHT * 4) as vk::DeviceSize;
+
+    let buffer_info = vk::BufferCreateInfo::builder()
+        .size(size)
+        .usage(vk::BufferUsageFlags::TRANSFER_DST)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+    data.readback_buffer = device.create_buffer(&buffer_info, None)?;
+
+    let requirements = device.get_buffer_memory_requirements(data.readback_buffer);
+
+    let memory_info = vk::MemoryAllocateInfo::builder()
+        .allocation_size(requirements.size)
+        .memory_type_index(get_memory_type(
+            instance,
+            data,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+            requirements,
+        )?);
+
+    data.readback_buffer_memory = device.allocate_memory(&memory_info, None)?;
+
+    device.bind_buffer_memory(data.readback_buffer, data.readback_buffer_memory, 0)?;
+
+    Ok(())
+}
+
+unsafe fn create_command_pool(device: &Device, data: &mut AppData) -> Result<()> {
+    // 여러 frame을 그릴 때 같은 command buffer를 다시 기록하므로 개별 reset을 허용함
+    let info = vk::CommandPoolCreateInfo::builder()
+        .flags(
+            vk::CommandPoolCreateFlags::TRANSIENT
+                | vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER,
+        )
+        .queue_family_index(data.graphics_queue_family);
+
+    data.command_pool = device.create_command_pool(&info, None)?;
+
+    Ok(())
+}
+
+unsafe fn create_command_buffer(device: &Device, data: &mut AppData) -> Result<()> {
+    let allocate_info = vk::CommandBufferAllocateInfo::builder()
+        .command_pool(data.command_pool)
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_buffer_count(1);
+
+    data.command_buffer = device.allocate_command_buffers(&allocate_info)?[0];
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    pretty_env_logger::init();
+
+    // golden image test는 여러 frame을 그린 뒤의 결과가 첫 frame과 같은지도 확인함
+    let output = headless_output(OUTPUT_PATH);
+    let frames = headless_frames();
+
+    // event loop와 window 없이 그리고 종료함
+    let mut app = unsafe { App::create()? };
+    let result = unsafe {
+        (0..frames)
+            .try_for_each(|_| app.render())
+            .and_then(|_| app.save(&output))
+    };
+    unsafe { app.destroy() };
+    result?;
+
+    info!("Saved offscreen rendering to `{}`.", output.display());
+
+    Ok(())
+}
@@ -0,0 +1,1065 @@
+#![allow(
+    dead_code,
+    unused_variables,
+    clippy::too_many_arguments,
+    clippy::unnecessary_wraps
+)]
+
+use anyhow::{anyhow, Result};
+use log::*;
+use vulkanalia::loader::{LibloadingLoader, LIBRARY};
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::window as vk_window;
+use winit::dpi::LogicalSize;
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoop;
+use winit::window::{Window, WindowBuilder};
+
+use vulkan_tutorial::allocator::{Allocation, Allocator};
+use vulkan_tutorial::device::{
+    create_logical_device, pick_physical_device, QueueFamilyIndices, SuitabilityError,
+};
+use vulkan_tutorial::error::RenderError;
+use vulkan_tutorial::instance::create_instance;
+use vulkan_tutorial::pipeline::create_shader_module;
+use vulkan_tutorial::swapchain::{
+    create_render_finished_semaphores, create_swapchain, create_swapchain_image_views, Swapchain,
+    SwapchainOptions,
+};
+use vulkan_tutorial::timer::FrameTimer;
+
+use vulkanalia::vk::ExtDebugUtilsExtension;
+use vulkanalia::vk::KhrSurfaceExtension;
+use vulkanalia::vk::KhrSwapchainExtension;
+
+use std::f32::consts::PI;
+use std::mem::size_of;
+use std::ptr::copy_nonoverlapping as memcpy;
+
+type Vec2 = cgmath::Vector2<f32>;
+type Vec4 = cgmath::Vector4<f32>;
+
+/// 동시에 실행될 frame의 수
+const MAX_FRAMES_IN_FLIGHT: usize = 2;
+
+/// 시뮬레이션할 particle의 수
+const PARTICLE_COUNT: u32 = 8192;
+
+/// compute shader의 `local_size_x`와 같아야 함
+const WORKGROUP_SIZE: u32 = 256;
+
+/// window title  
+/// 뒤에 frame 통계를 덧붙여서 표시함
+const TITLE: &str = "Vulkan Tutorial (Rust)";
+
+/// Our Vulkan app.  
+/// compute shader로 particle의 위치를 갱신하고, 같은 buffer를 vertex buffer로 사용해서 point로 그림
+#[derive(Clone, Debug)]
+struct App {
+    // vulkan entry point를 저장하기 위한 필드
+    entry: Entry,
+    // vulkan instance를 저장하기 위한 필드
+    instance: Instance,
+    data: AppData,
+    device: Device,
+    // frame track을 유지하기 위한 필드
+    frame: usize,
+    // window의 크기가 변경되었는지 추적하기 위한 필드
+    resized: bool,
+    // 시뮬레이션의 delta time을 계산하고 FPS를 측정하는 timer
+    frame_timer: FrameTimer,
+}
+
+impl App {
+    /// Creates our Vulkan app.
+    unsafe fn create(window: &Window) -> Result<Self> {
+        // Vulkan command를 Vulkan shared library에서 로드하기 위해 사용됨
+        let loader = LibloadingLoader::new(LIBRARY)?;
+        let entry = Entry::new(loader).map_err(|b| anyhow!("{}", b))?;
+        let mut data = AppData::default();
+        let (instance, messenger) = create_instance(window, &entry)?;
+        data.messenger = messenger;
+
+        data.surface = vk_window::create_surface(&instance, &window, &window)?;
+
+        let surface = data.surface;
+        data.physical_device = pick_physical_device(&instance, surface, |i, p| {
+            check_physical_device_features(i, surface, p)
+        })?;
+        data.allocator = Allocator::new(&instance, data.physical_device);
+
+        // vertex shader에서 gl_PointSize를 1보다 크게 지정하기 위해 활성화
+        let features = vk::PhysicalDeviceFeatures::builder().large_points(true);
+        let (device, graphics_queue, present_queue) = create_logical_device(
+            &entry,
+            &instance,
+            data.surface,
+            data.physical_device,
+            &features,
+        )?;
+        data.graphics_queue = graphics_queue;
+        data.present_queue = present_queue;
+        data.swapchain = create_swapchain(
+            window,
+            &instance,
+            &device,
+            data.surface,
+            data.physical_device,
+            &SwapchainOptions::from_config()?,
+        )?;
+        data.swapchain_image_views = create_swapchain_image_views(&device, &data.swapchain)?;
+        create_render_pass(&device, &mut data)?;
+        create_graphics_pipeline(&device, &mut data)?;
+        create_framebuffers(&device, &mut data)?;
+        create_command_pool(&instance, &device, &mut data)?;
+        create_particle_buffer(&device, &mut data)?;
+        create_descriptor_set_layout(&device, &mut data)?;
+        create_descriptor_pool(&device, &mut data)?;
+        create_descriptor_set(&device, &mut data)?;
+        create_compute_pipeline(&device, &mut data)?;
+        create_command_buffers(&device, &mut data)?;
+        create_sync_objects(&device, &mut data)?;
+
+        Ok(Self {
+            entry,
+            instance,
+            data,
+            device,
+            frame: 0,
+            resized: false,
+            frame_timer: FrameTimer::default(),
+        })
+    }
+
+    /// Renders a frame for our Vulkan app.  
+    /// swapchain이나 surface를 다시 생성해야 하는 경우 `RenderError`로 알려서 event loop에서 처리하도록 함
+    unsafe fn render(&mut self, window: &Window) -> Result<(), RenderError> {
+        // 크기가 0인 swapchain은 만들 수 없으므로 window가 복원될 때까지 건너뜀
+        let size = window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            return Ok(());
+        }
+
+        // 지난 frame 이후에 흐른 시간만큼 particle을 이동시킴
+        let dt = self.frame_timer.tick().as_secs_f32();
+
+        // frame이 끝날 때 까지 대기
+        self.device
+            .wait_for_fences(&[self.data.in_flight_fences[self.frame]], true, u64::MAX)?;
+
+        let result = self.device.acquire_next_image_khr(
+            self.data.swapchain.handle,
+            u64::MAX,
+            self.data.image_available_semaphores[self.frame],
+            vk::Fence::null(),
+        );
+
+        // swapchain이 surface와 더 이상 호환되지 않으면 RenderError::OutOfDate로 변환되어 반환됨
+        let image_index = result?.0 as usize;
+
+        if !self.data.images_in_flight[image_index].is_null() {
+            self.device.wait_for_fences(
+                &[self.data.images_in_flight[image_index]],
+                true,
+                u64::MAX,
+            )?;
+        }
+
+        self.data.images_in_flight[image_index] = self.data.in_flight_fences[self.frame];
+
+        // 현재 frame의 fence를 기다렸으므로 command buffer를 다시 기록할 수 있음
+        let command_buffer = self.update_command_buffer(image_index, dt)?;
+
+        // compute 작업은 swapchain image를 사용하지 않으므로 image를 기다리지 않고 먼저 실행될 수 있음
+        let wait_semaphores = &[self.data.image_available_semaphores[self.frame]];
+        let wait_stages = &[vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
+        let command_buffers = &[command_buffer];
+        let signal_semaphores = &[self.data.render_finished_semaphores[image_index]];
+        let submit_info = vk::SubmitInfo::builder()
+            .wait_semaphores(wait_semaphores)
+            .wait_dst_stage_mask(wait_stages)
+            .command_buffers(command_buffers)
+            .signal_semaphores(signal_semaphores);
+
+        self.device
+            .reset_fences(&[self.data.in_flight_fences[self.frame]])?;
+
+        self.device.queue_submit(
+            self.data.graphics_queue,
+            &[submit_info],
+            self.data.in_flight_fences[self.frame],
+        )?;
+
+        let swapchains = &[self.data.swapchain.handle];
+        let image_indices = &[image_index as u32];
+        let present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(signal_semaphores)
+            .swapchains(swapchains)
+            .image_indices(image_indices);
+
+        let result = self
+            .device
+            .queue_present_khr(self.data.present_queue, &present_info);
+
+        // command buffer는 이미 제출되었으므로 present의 결과와 관계없이 다음 frame으로 넘어감
+        self.frame = (self.frame + 1) % MAX_FRAMES_IN_FLIGHT;
+
+        // 1초마다 최근 frame들의 평균 통계를 window title에 표시
+        if let Some(stats) = self.frame_timer.report() {
+            window.set_title(&format!("{} - {}", TITLE, stats));
+        }
+
+        // present가 끝난 뒤에 확인해야 semaphore가 올바른 상태로 남음
+        if self.resized {
+            self.resized = false;
+            return Err(RenderError::OutOfDate);
+        }
+
+        match result? {
+            vk::SuccessCode::SUBOPTIMAL_KHR => Err(RenderError::Suboptimal),
+            _ => Ok(()),
+        }
+    }
+
+    /// 현재 frame의 command buffer에 particle 갱신과 rendering을 차례로 기록  
+    /// 같은 queue에서 실행되므로 compute와 graphics 사이의 동기화는 pipeline barrier로 충분함
+    unsafe fn update_command_buffer(
+        &mut self,
+        image_index: usize,
+        dt: f32,
+    ) -> Result<vk::CommandBuffer> {
+        let command_buffer = self.data.command_buffers[self.frame];
+        self.device
+            .reset_command_buffer(command_buffer, vk::CommandBufferResetFlags::empty())?;
+
+        let info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+
+        self.device.begin_command_buffer(command_buffer, &info)?;
+
+        // 이전 frame이 vertex buffer로 읽고 있는 particle을 덮어쓰지 않도록 기다림
+        // write-after-read hazard는 memory barrier 없이 execution dependency만으로 충분함
+        self.device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::VERTEX_INPUT,
+            vk::PipelineStageFlags::COMPUTE_SHADER,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[] as &[vk::ImageMemoryBarrier],
+        );
+
+        self.device.cmd_bind_pipeline(
+            command_buffer,
+            vk::PipelineBindPoint::COMPUTE,
+            self.data.compute_pipeline,
+        );
+        self.device.cmd_bind_descriptor_sets(
+            command_buffer,
+            vk::PipelineBindPoint::COMPUTE,
+            self.data.compute_pipeline_layout,
+            0,
+            &[self.data.descriptor_set],
+            &[],
+        );
+        self.device.cmd_push_constants(
+            command_buffer,
+            self.data.compute_pipeline_layout,
+            vk::ShaderStageFlags::COMPUTE,
+            0,
+            &dt.to_ne_bytes()[..],
+        );
+
+        // workgroup 하나가 WORKGROUP_SIZE개의 particle을 처리하므로 올림해서 dispatch
+        let group_count = PARTICLE_COUNT.div_ceil(WORKGROUP_SIZE);
+        self.device.cmd_dispatch(command_buffer, group_count, 1, 1);
+
+        // compute shader가 쓴 particle을 vertex input 단계에서 읽을 수 있도록 함
+        let barrier = vk::BufferMemoryBarrier::builder()
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .buffer(self.data.particle_buffer)
+            .offset(0)
+            .size(vk::WHOLE_SIZE)
+            .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+            .dst_access_mask(vk::AccessFlags::VERTEX_ATTRIBUTE_READ);
+
+        self.device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::COMPUTE_SHADER,
+            vk::PipelineStageFlags::VERTEX_INPUT,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[barrier],
+            &[] as &[vk::ImageMemoryBarrier],
+        );
+
+        let render_area = vk::Rect2D::builder()
+            .offset(vk::Offset2D::default())
+            .extent(self.data.swapchain.extent);
+
+        let color_clear_value = vk::ClearValue {
+            color: vk::ClearColorValue {
+                float32: [0.0, 0.0, 0.0, 1.0],
+            },
+        };
+
+        let clear_values = &[color_clear_value];
+        let info = vk::RenderPassBeginInfo::builder()
+            .render_pass(self.data.render_pass)
+            .framebuffer(self.data.framebuffers[image_index])
+            .render_area(render_area)
+            .clear_values(clear_values);
+
+        self.device
+            .cmd_begin_render_pass(command_buffer, &info, vk::SubpassContents::INLINE);
+
+        self.device.cmd_bind_pipeline(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            self.data.graphics_pipeline,
+        );
+
+        // pipeline에서 dynamic state로 지정한 viewport와 scissor를 현재 swapchain 크기에 맞게 설정
+        let viewport = vk::Viewport::builder()
+            .x(0.0)
+            .y(0.0)
+            .width(self.data.swapchain.extent.width as f32)
+            .height(self.data.swapchain.extent.height as f32)
+            .min_depth(0.0)
+            .max_depth(1.0);
+
+        let scissor = vk::Rect2D::builder()
+            .offset(vk::Offset2D { x: 0, y: 0 })
+            .extent(self.data.swapchain.extent);
+
+        self.device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+        self.device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+
+        // compute shader가 갱신한 storage buffer를 그대로 vertex buffer로 bind
+        self.device
+            .cmd_bind_vertex_buffers(command_buffer, 0, &[self.data.particle_buffer], &[0]);
+        self.device
+            .cmd_draw(command_buffer, PARTICLE_COUNT, 1, 0, 0);
+
+        self.device.cmd_end_render_pass(command_buffer);
+
+        self.device.end_command_buffer(command_buffer)?;
+
+        Ok(command_buffer)
+    }
+
+    /// swapchain과 swapchain에 의존하는 오브젝트들을 다시 생성
+    unsafe fn recreate_swapchain(&mut self, window: &Window) -> Result<()> {
+        // 사용중인 리소스를 건드리지 않도록 device가 idle상태가 될 때 까지 대기
+        self.device.device_wait_idle()?;
+        self.destroy_swapchain();
+        self.create_swapchain_objects(window)
+    }
+
+    /// surface와 surface에 의존하는 swapchain을 다시 생성
+    unsafe fn recreate_surface(&mut self, window: &Window) -> Result<()> {
+        self.device.device_wait_idle()?;
+        // swapchain은 surface에 의존하므로 surface보다 먼저 파괴해야 함
+        self.destroy_swapchain();
+        self.instance.destroy_surface_khr(self.data.surface, None);
+        self.data.surface = vk_window::create_surface(&self.instance, &window, &window)?;
+        self.create_swapchain_objects(window)
+    }
+
+    /// swapchain과 swapchain에 의존하는 오브젝트들을 생성  
+    /// particle buffer와 compute pipeline은 swapchain과 관계없으므로 그대로 사용함
+    unsafe fn create_swapchain_objects(&mut self, window: &Window) -> Result<()> {
+        let format = self.data.swapchain.format;
+        self.data.swapchain = create_swapchain(
+            window,
+            &self.instance,
+            &self.device,
+            self.data.surface,
+            self.data.physical_device,
+            &SwapchainOptions::from_config()?,
+        )?;
+        self.data.swapchain_image_views =
+            create_swapchain_image_views(&self.device, &self.data.swapchain)?;
+        // swapchain image format이 바뀐 경우에만 render pass와 graphics pipeline을 다시 생성
+        if self.data.swapchain.format != format {
+            self.destroy_graphics_pipeline();
+            create_render_pass(&self.device, &mut self.data)?;
+            create_graphics_pipeline(&self.device, &mut self.data)?;
+        }
+        create_framebuffers(&self.device, &mut self.data)?;
+        // swapchain image의 개수가 바뀔 수 있음
+        self.data
+            .images_in_flight
+            .resize(self.data.swapchain.images.len(), vk::Fence::null());
+        // swapchain image마다 존재하는 render finished semaphore도 image의 개수에 맞게 다시 생성
+        create_render_finished_semaphores(
+            &self.device,
+            &self.data.swapchain,
+            &mut self.data.render_finished_semaphores,
+        )?;
+        Ok(())
+    }
+
+    /// Destroys our Vulkan app.
+    unsafe fn destroy(&mut self) {
+        self.destroy_swapchain();
+        self.destroy_graphics_pipeline();
+
+        self.data
+            .render_finished_semaphores
+            .iter()
+            .for_each(|s| self.device.destroy_semaphore(*s, None));
+        self.data
+            .image_available_semaphores
+            .iter()
+            .for_each(|s| self.device.destroy_semaphore(*s, None));
+        self.data
+            .in_flight_fences
+            .iter()
+            .for_each(|f| self.device.destroy_fence(*f, None));
+
+        self.device
+            .destroy_pipeline(self.data.compute_pipeline, None);
+        self.device
+            .destroy_pipeline_layout(self.data.compute_pipeline_layout, None);
+        // descriptor pool이 파괴되면 할당된 descriptor set도 함께 해제됨
+        self.device
+            .destroy_descriptor_pool(self.data.descriptor_pool, None);
+        self.device
+            .destroy_descriptor_set_layout(self.data.descriptor_set_layout, None);
+
+        self.device.destroy_buffer(self.data.particle_buffer, None);
+        self.data.allocator.free(self.data.particle_buffer_memory);
+        self.data.allocator.destroy(&self.device);
+
+        self.device
+            .destroy_command_pool(self.data.command_pool, None);
+
+        self.device.destroy_device(None);
+
+        if !self.data.messenger.is_null() {
+            // 프로그램이 종료되기 전에 디버그 메세지 핸들러를 파괴
+            self.instance
+                .destroy_debug_utils_messenger_ext(self.data.messenger, None);
+        }
+
+        self.instance.destroy_surface_khr(self.data.surface, None);
+        self.instance.destroy_instance(None);
+    }
+
+    /// swapchain과 swapchain에 의존하는 오브젝트들을 파괴
+    unsafe fn destroy_swapchain(&mut self) {
+        self.data
+            .framebuffers
+            .iter()
+            .for_each(|f| self.device.destroy_framebuffer(*f, None));
+        self.data.framebuffers.clear();
+        self.data
+            .swapchain_image_views
+            .iter()
+            .for_each(|v| self.device.destroy_image_view(*v, None));
+        self.data.swapchain_image_views.clear();
+        self.device
+            .destroy_swapchain_khr(self.data.swapchain.handle, None);
+    }
+
+    /// graphics pipeline과 render pass를 파괴
+    unsafe fn destroy_graphics_pipeline(&mut self) {
+        self.device
+            .destroy_pipeline(self.data.graphics_pipeline, None);
+        self.device
+            .destroy_pipeline_layout(self.data.graphics_pipeline_layout, None);
+        self.device.destroy_render_pass(self.data.render_pass, None);
+    }
+}
+
+/// The Vulkan handles and associated properties used by our Vulkan app.  
+/// Vulkan 리소스 컨테이너 역할
+#[derive(Clone, Debug, Default)]
+struct AppData {
+    // surface
+    surface: vk::SurfaceKHR,
+    // 디버그 메세지를 처리하기 위한 messenger 핸들러
+    messenger: vk::DebugUtilsMessengerEXT,
+    // physical device 핸들
+    physical_device: vk::PhysicalDevice,
+    // compute와 graphics command를 함께 제출할 queue
+    graphics_queue: vk::Queue,
+    // present queue를 컨트롤하기 위한 핸들
+    present_queue: vk::Queue,
+    // swapchain 핸들과 swapchain image의 format, extent, image들
+    swapchain: Swapchain,
+    // swapchain image view
+    swapchain_image_views: Vec<vk::ImageView>,
+    // render pass 핸들
+    render_pass: vk::RenderPass,
+    // particle을 point로 그리는 graphics pipeline
+    graphics_pipeline_layout: vk::PipelineLayout,
+    graphics_pipeline: vk::Pipeline,
+    // framebuffer
+    framebuffers: Vec<vk::Framebuffer>,
+    // command pool 핸들
+    command_pool: vk::CommandPool,
+    // frame마다 다시 기록하는 command buffer
+    command_buffers: Vec<vk::CommandBuffer>,
+    // buffer memory를 나누어 할당하는 allocator
+    allocator: Allocator,
+    // compute shader에서는 storage buffer로, vertex shader에서는 vertex buffer로 사용되는 buffer
+    particle_buffer: vk::Buffer,
+    particle_buffer_memory: Allocation,
+    // compute shader에서 particle buffer에 접근하기 위한 descriptor
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+    descriptor_set: vk::DescriptorSet,
+    // particle을 갱신하는 compute pipeline
+    compute_pipeline_layout: vk::PipelineLayout,
+    compute_pipeline: vk::Pipeline,
+    // swapchain image를 얻었음을 알리는 semaphore
+    image_available_semaphores: Vec<vk::Semaphore>,
+    // rendering이 끝났음을 알리는 semaphore
+    render_finished_semaphores: Vec<vk::Semaphore>,
+    // frame마다 CPU와 GPU 동기화를 위한 fence
+    in_flight_fences: Vec<vk::Fence>,
+    // swapchain image가 사용중인지 추적하기위한 필드
+    images_in_flight: Vec<vk::Fence>,
+}
+
+/// large point를 지원하고 graphics queue family에서 compute command도 실행할 수 있는지 확인  
+/// compute와 graphics를 같은 command buffer에 기록하므로 두 작업을 모두 지원하는 queue family가 필요함
+unsafe fn check_physical_device_features(
+    instance: &Instance,
+    surface: vk::SurfaceKHR,
+    physical_device: vk::PhysicalDevice,
+) -> Result<()> {
+    let features = instance.get_physical_device_features(physical_device);
+    if features.large_points != vk::TRUE {
+        return Err(anyhow!(SuitabilityError("large points feature")));
+    }
+
+    let indices = QueueFamilyIndices::get(instance, surface, physical_device)?;
+    let properties = instance.get_physical_device_queue_family_properties(physical_device);
+    if !properties[indices.graphics as usize]
+        .queue_flags
+        .contains(vk::QueueFlags::COMPUTE)
+    {
+        return Err(anyhow!(SuitabilityError(
+            "compute support in graphics queue family"
+        )));
+    }
+
+    Ok(())
+}
+
+/// swapchain image에 clear한 뒤 particle을 그리는 render pass 생성
+unsafe fn create_render_pass(device: &Device, data: &mut AppData) -> Result<()> {
+    let color_attachment = vk::AttachmentDescription::builder()
+        .format(data.swapchain.format)
+        .samples(vk::SampleCountFlags::_1)
+        .load_op(vk::AttachmentLoadOp::CLEAR)
+        .store_op(vk::AttachmentStoreOp::STORE)
+        .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+        .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .final_layout(vk::ImageLayout::PRESENT_SRC_KHR);
+
+    let color_attachment_ref = vk::AttachmentReference::builder()
+        .attachment(0)
+        .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+    let color_attachments = &[color_attachment_ref];
+    let subpass = vk::SubpassDescription::builder()
+        .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+        .color_attachments(color_attachments);
+
+    // swapchain image를 얻은 뒤에 attachment에 쓰기 시작하도록 함
+    let dependency = vk::SubpassDependency::builder()
+        .src_subpass(vk::SUBPASS_EXTERNAL)
+        .dst_subpass(0)
+        .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+        .src_access_mask(vk::AccessFlags::empty())
+        .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+        .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE);
+
+    let attachments = &[color_attachment];
+    let subpasses = &[subpass];
+    let dependencies = &[dependency];
+    let info = vk::RenderPassCreateInfo::builder()
+        .attachments(attachments)
+        .subpasses(subpasses)
+        .dependencies(dependencies);
+
+    data.render_pass = device.create_render_pass(&info, None)?;
+
+    Ok(())
+}
+
+/// particle buffer를 vertex buffer로 읽어서 point로 그리는 graphics pipeline 생성
+unsafe fn create_graphics_pipeline(device: &Device, data: &mut AppData) -> Result<()> {
+    let vert = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/43/vert.spv"));
+    let frag = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/43/frag.spv"));
+
+    let vert_shader_module = create_shader_module(device, &vert[..])?;
+    let frag_shader_module = create_shader_module(device, &frag[..])?;
+
+    let vert_stage = vk::PipelineShaderStageCreateInfo::builder()
+        .stage(vk::ShaderStageFlags::VERTEX)
+        .module(vert_shader_module)
+        .name(b"main\0");
+
+    let frag_stage = vk::PipelineShaderStageCreateInfo::builder()
+        .stage(vk::ShaderStageFlags::FRAGMENT)
+        .module(frag_shader_module)
+        .name(b"main\0");
+
+    let binding_descriptions = &[Particle::binding_description()];
+    let attribute_descriptions = Particle::attribute_descriptions();
+    let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
+        .vertex_binding_descriptions(binding_descriptions)
+        .vertex_attribute_descriptions(&attribute_descriptions);
+
+    // particle 하나를 vertex 하나로 보고 point로 그림
+    let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::builder()
+        .topology(vk::PrimitiveTopology::POINT_LIST)
+        .primitive_restart_enable(false);
+
+    // 실제 값은 dynamic state로 지정하므로 개수만 맞춰줌
+    let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+        .viewport_count(1)
+        .scissor_count(1);
+
+    let rasterization_state = vk::PipelineRasterizationStateCreateInfo::builder()
+        .depth_clamp_enable(false)
+        .rasterizer_discard_enable(false)
+        .polygon_mode(vk::PolygonMode::FILL)
+        .line_width(1.0)
+        .cull_mode(vk::CullModeFlags::NONE)
+        .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+        .depth_bias_enable(false);
+
+    let multisample_state = vk::PipelineMultisampleStateCreateInfo::builder()
+        .sample_shading_enable(false)
+        .rasterization_samples(vk::SampleCountFlags::_1);
+
+    // 겹친 particle이 더 밝게 보이도록 additive blending을 사용
+    let attachment = vk::PipelineColorBlendAttachmentState::builder()
+        .color_write_mask(vk::ColorComponentFlags::all())
+        .blend_enable(true)
+        .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
+        .dst_color_blend_factor(vk::BlendFactor::ONE)
+        .color_blend_op(vk::BlendOp::ADD)
+        .src_alpha_blend_factor(vk::BlendFactor::ONE)
+        .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
+        .alpha_blend_op(vk::BlendOp::ADD);
+
+    let attachments = &[attachment];
+    let color_blend_state = vk::PipelineColorBlendStateCreateInfo::builder()
+        .logic_op_enable(false)
+        .logic_op(vk::LogicOp::COPY)
+        .attachments(attachments)
+        .blend_constants([0.0, 0.0, 0.0, 0.0]);
+
+    let layout_info = vk::PipelineLayoutCreateInfo::builder();
+    data.graphics_pipeline_layout = device.create_pipeline_layout(&layout_info, None)?;
+
+    let dynamic_states = &[vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+    let dynamic_state =
+        vk::PipelineDynamicStateCreateInfo::builder().dynamic_states(dynamic_states);
+
+    let stages = &[vert_stage, frag_stage];
+    let info = vk::GraphicsPipelineCreateInfo::builder()
+        .stages(stages)
+        .vertex_input_state(&vertex_input_state)
+        .input_assembly_state(&input_assembly_state)
+        .viewport_state(&viewport_state)
+        .rasterization_state(&rasterization_state)
+        .multisample_state(&multisample_state)
+        .color_blend_state(&color_blend_state)
+        .dynamic_state(&dynamic_state)
+        .layout(data.graphics_pipeline_layout)
+        .render_pass(data.render_pass)
+        .subpass(0);
+
+    data.graphics_pipeline = device
+        .create_graphics_pipelines(vk::PipelineCache::null(), &[info], None)?
+        .0[0];
+
+    device.destroy_shader_module(vert_shader_module, None);
+    device.destroy_shader_module(frag_shader_module, None);
+
+    Ok(())
+}
+
+/// framebuffer 생성
+unsafe fn create_framebuffers(device: &Device, data: &mut AppData) -> Result<()> {
+    data.framebuffers = data
+        .swapchain_image_views
+        .iter()
+        .map(|i| {
+            let attachments = &[*i];
+            let create_info = vk::FramebufferCreateInfo::builder()
+                .render_pass(data.render_pass)
+                .attachments(attachments)
+                .width(data.swapchain.extent.width)
+                .height(data.swapchain.extent.height)
+                .layers(1);
+
+            device.create_framebuffer(&create_info, None)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(())
+}
+
+/// command pool 생성  
+/// command buffer를 frame마다 다시 기록하므로 개별적으로 reset할 수 있도록 함
+unsafe fn create_command_pool(
+    instance: &Instance,
+    device: &Device,
+    data: &mut AppData,
+) -> Result<()> {
+    let indices = QueueFamilyIndices::get(instance, data.surface, data.physical_device)?;
+
+    let info = vk::CommandPoolCreateInfo::builder()
+        .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER)
+        .queue_family_index(indices.graphics);
+
+    data.command_pool = device.create_command_pool(&info, None)?;
+
+    Ok(())
+}
+
+/// 초기 particle 데이터를 DEVICE_LOCAL particle buffer에 업로드
+unsafe fn create_particle_buffer(device: &Device, data: &mut AppData) -> Result<()> {
+    let particles = create_particles();
+    let size = (size_of::<Particle>() * particles.len()) as u64;
+
+    // compute shader는 storage buffer로 쓰고 vertex shader는 vertex buffer로 읽음
+    let info = vk::BufferCreateInfo::builder()
+        .size(size)
+        .usage(
+            vk::BufferUsageFlags::STORAGE_BUFFER
+                | vk::BufferUsageFlags::VERTEX_BUFFER
+                | vk::BufferUsageFlags::TRANSFER_DST,
+        )
+        .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+    let (particle_buffer, particle_buffer_memory) =
+        data.allocator
+            .create_buffer(device, &info, vk::MemoryPropertyFlags::DEVICE_LOCAL)?;
+    data.particle_buffer = particle_buffer;
+    data.particle_buffer_memory = particle_buffer_memory;
+
+    // staging buffer 생성
+    let info = vk::BufferCreateInfo::builder()
+        .size(size)
+        .usage(vk::BufferUsageFlags::TRANSFER_SRC)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+    let (staging_buffer, staging_buffer_memory) = data.allocator.create_buffer(
+        device,
+        &info,
+        vk::MemoryPropertyFlags::HOST_COHERENT | vk::MemoryPropertyFlags::HOST_VISIBLE,
+    )?;
+
+    let memory = staging_buffer_memory.mapped_ptr()?;
+    memcpy(particles.as_ptr(), memory.cast(), particles.len());
+
+    // 한 번만 실행하는 복사이므로 queue가 idle 상태가 될 때까지 기다림
+    let info = vk::CommandBufferAllocateInfo::builder()
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_pool(data.command_pool)
+        .command_buffer_count(1);
+
+    let command_buffer = device.allocate_command_buffers(&info)?[0];
+
+    let info =
+        vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+
+    device.begin_command_buffer(command_buffer, &info)?;
+
+    let region = vk::BufferCopy::builder().size(size);
+    device.cmd_copy_buffer(command_buffer, staging_buffer, particle_buffer, &[region]);
+
+    device.end_command_buffer(command_buffer)?;
+
+    let command_buffers = &[command_buffer];
+    let info = vk::SubmitInfo::builder().command_buffers(command_buffers);
+
+    device.queue_submit(data.graphics_queue, &[info], vk::Fence::null())?;
+    device.queue_wait_idle(data.graphics_queue)?;
+
+    device.free_command_buffers(data.command_pool, &[command_buffer]);
+
+    // 복사가 끝났으므로 staging buffer는 더 이상 필요없음
+    device.destroy_buffer(staging_buffer, None);
+    data.allocator.free(staging_buffer_memory);
+
+    Ok(())
+}
+
+/// 화면 중앙의 원 안에 particle을 고르게 배치하고 바깥쪽으로 향하는 속도를 줌  
+/// 난수 대신 golden angle을 사용해서 매번 같은 모양으로 시작함
+fn create_particles() -> Vec<Particle> {
+    let golden_angle = PI * (3.0 - 5.0f32.sqrt());
+
+    (0..PARTICLE_COUNT)
+        .map(|i| {
+            let t = (i as f32 + 0.5) / PARTICLE_COUNT as f32;
+            let angle = i as f32 * golden_angle;
+            let direction = Vec2::new(angle.cos(), angle.sin());
+
+            Particle {
+                position: direction * 0.25 * t.sqrt(),
+                velocity: direction * (0.1 + 0.4 * t),
+                color: Vec4::new(
+                    0.5 + 0.5 * angle.cos(),
+                    0.5 + 0.5 * (angle + 2.0 * PI / 3.0).cos(),
+                    0.5 + 0.5 * (angle + 4.0 * PI / 3.0).cos(),
+                    1.0,
+                ),
+            }
+        })
+        .collect()
+}
+
+/// compute shader가 particle buffer를 storage buffer로 사용하기 위한 descriptor set layout 생성
+unsafe fn create_descriptor_set_layout(device: &Device, data: &mut AppData) -> Result<()> {
+    let particle_binding = vk::DescriptorSetLayoutBinding::builder()
+        .binding(0)
+        .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+        .descriptor_count(1)
+        .stage_flags(vk::ShaderStageFlags::COMPUTE);
+
+    let bindings = &[particle_binding];
+    let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(bindings);
+
+    data.descriptor_set_layout = device.create_descriptor_set_layout(&info, None)?;
+
+    Ok(())
+}
+
+/// descriptor pool 생성  
+/// particle buffer는 하나뿐이므로 descriptor set도 하나만 필요함
+unsafe fn create_descriptor_pool(device: &Device, data: &mut AppData) -> Result<()> {
+    let particle_size = vk::DescriptorPoolSize::builder()
+        .type_(vk::DescriptorType::STORAGE_BUFFER)
+        .descriptor_count(1);
+
+    let pool_sizes = &[particle_size];
+    let info = vk::DescriptorPoolCreateInfo::builder()
+        .pool_sizes(pool_sizes)
+        .max_sets(1);
+
+    data.descriptor_pool = device.create_descriptor_pool(&info, None)?;
+
+    Ok(())
+}
+
+/// particle buffer를 가리키는 descriptor set을 할당
+unsafe fn create_descriptor_set(device: &Device, data: &mut AppData) -> Result<()> {
+    let layouts = &[data.descriptor_set_layout];
+    let info = vk::DescriptorSetAllocateInfo::builder()
+        .descriptor_pool(data.descriptor_pool)
+        .set_layouts(layouts);
+
+    data.descriptor_set = device.allocate_descriptor_sets(&info)?[0];
+
+    let info = vk::DescriptorBufferInfo::builder()
+        .buffer(data.particle_buffer)
+        .offset(0)
+        .range(vk::WHOLE_SIZE);
+
+    let buffer_info = &[info];
+    let particle_write = vk::WriteDescriptorSet::builder()
+        .dst_set(data.descriptor_set)
+        .dst_binding(0)
+        .dst_array_element(0)
+        .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+        .buffer_info(buffer_info);
+
+    device.update_descriptor_sets(&[particle_write], &[] as &[vk::CopyDescriptorSet]);
+
+    Ok(())
+}
+
+/// particle의 위치를 갱신하는 compute pipeline 생성  
+/// compute pipeline은 shader stage 하나와 pipeline layout만으로 구성됨
+unsafe fn create_compute_pipeline(device: &Device, data: &mut AppData) -> Result<()> {
+    let comp = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/43/comp.spv"));
+    let comp_shader_module = create_shader_module(device, &comp[..])?;
+
+    let stage = vk::PipelineShaderStageCreateInfo::builder()
+        .stage(vk::ShaderStageFlags::COMPUTE)
+        .module(comp_shader_module)
+        .name(b"main\0");
+
+    // delta time은 frame마다 바뀌므로 push constant로 전달
+    let push_constant_range = vk::PushConstantRange::builder()
+        .stage_flags(vk::ShaderStageFlags::COMPUTE)
+        .offset(0)
+        .size(size_of::<f32>() as u32);
+
+    let set_layouts = &[data.descriptor_set_layout];
+    let push_constant_ranges = &[push_constant_range];
+    let layout_info = vk::PipelineLayoutCreateInfo::builder()
+        .set_layouts(set_layouts)
+        .push_constant_ranges(push_constant_ranges);
+
+    data.compute_pipeline_layout = device.create_pipeline_layout(&layout_info, None)?;
+
+    let info = vk::ComputePipelineCreateInfo::builder()
+        .stage(stage)
+        .layout(data.compute_pipeline_layout);
+
+    data.compute_pipeline = device
+        .create_compute_pipelines(vk::PipelineCache::null(), &[info], None)?
+        .0[0];
+
+    device.destroy_shader_module(comp_shader_module, None);
+
+    Ok(())
+}
+
+/// frame마다 사용할 command buffer 할당
+unsafe fn create_command_buffers(device: &Device, data: &mut AppData) -> Result<()> {
+    let info = vk::CommandBufferAllocateInfo::builder()
+        .command_pool(data.command_pool)
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_buffer_count(MAX_FRAMES_IN_FLIGHT as u32);
+
+    data.command_buffers = device.allocate_command_buffers(&info)?;
+
+    Ok(())
+}
+
+/// semaphore와 fence 생성
+unsafe fn create_sync_objects(device: &Device, data: &mut AppData) -> Result<()> {
+    let semaphore_info = vk::SemaphoreCreateInfo::builder();
+    let fence_info = vk::FenceCreateInfo::builder().flags(vk::FenceCreateFlags::SIGNALED);
+
+    for _ in 0..MAX_FRAMES_IN_FLIGHT {
+        data.image_available_semaphores
+            .push(device.create_semaphore(&semaphore_info, None)?);
+
+        data.in_flight_fences
+            .push(device.create_fence(&fence_info, None)?);
+    }
+
+    data.images_in_flight = data
+        .swapchain
+        .images
+        .iter()
+        .map(|_| vk::Fence::null())
+        .collect();
+
+    // render finished semaphore는 frame이 아닌 swapchain image마다 하나씩 존재
+    create_render_finished_semaphores(
+        device,
+        &data.swapchain,
+        &mut data.render_finished_semaphores,
+    )?;
+
+    Ok(())
+}
+
+/// compute shader의 `Particle` 구조체와 같은 layout을 가지는 구조체  
+/// std430 layout에서 vec4는 16 bytes 단위로 정렬되므로 vec2 두 개 뒤에 바로 color가 옴
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct Particle {
+    position: Vec2,
+    velocity: Vec2,
+    color: Vec4,
+}
+
+impl Particle {
+    /// particle 하나를 vertex 하나로 읽음
+    fn binding_description() -> vk::VertexInputBindingDescription {
+        vk::VertexInputBindingDescription::builder()
+            .binding(0)
+            .stride(size_of::<Particle>() as u32)
+            .input_rate(vk::VertexInputRate::VERTEX)
+            .build()
+    }
+
+    /// vertex shader는 position과 color만 사용하고 velocity는 건너뜀
+    fn attribute_descriptions() -> [vk::VertexInputAttributeDescription; 2] {
+        let position = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(0)
+            .format(vk::Format::R32G32_SFLOAT)
+            .offset(0)
+            .build();
+        let color = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(1)
+            .format(vk::Format::R32G32B32A32_SFLOAT)
+            .offset((size_of::<Vec2>() + size_of::<Vec2>()) as u32)
+            .build();
+        [position, color]
+    }
+}
+
+fn main() -> Result<()> {
+    pretty_env_logger::init();
+
+    // Window
+    let event_loop = EventLoop::new()?;
+    let window = WindowBuilder::new()
+        .with_title(TITLE)
+        .with_inner_size(LogicalSize::new(1024, 768))
+        .build(&event_loop)?;
+
+    // App
+    let mut app = unsafe { App::create(&window)? };
+    // window가 최소화되어 크기가 0인 동안에는 그리지 않음
+    let mut minimized = false;
+    event_loop.run(move |event, elwt| {
+        match event {
+            // Request a redraw when all events were processed.
+            Event::AboutToWait => window.request_redraw(),
+            Event::WindowEvent { event, .. } => match event {
+                // window의 크기가 변경되면 다음 frame에서 swapchain을 다시 생성하도록 표시
+                WindowEvent::Resized(size) => {
+                    if size.width == 0 || size.height == 0 {
+                        minimized = true;
+                    } else {
+                        minimized = false;
+                        app.resized = true;
+                    }
+                }
+                // Render a frame if our Vulkan app is not being destroyed.
+                WindowEvent::RedrawRequested if !elwt.exiting() && !minimized => {
+                    let result = match unsafe { app.render(&window) } {
+                        Err(RenderError::OutOfDate | RenderError::Suboptimal) => unsafe {
+                            app.recreate_swapchain(&window)
+                        },
+                        Err(RenderError::SurfaceLost) => {
+                            warn!("Surface was lost, recreating it.");
+                            unsafe { app.recreate_surface(&window) }
+                        }
+                        Err(RenderError::Fatal(error)) => Err(error),
+                        Ok(()) => Ok(()),
+                    };
+
+                    if let Err(error) = result {
+                        error!("Failed to render frame: {:?}", error);
+                        elwt.exit();
+                    }
+                }
+                // Destroy our Vulkan app.
+                WindowEvent::CloseRequested => elwt.exit(),
+                _ => {}
+            },
+            // event loop가 어떤 이유로 종료되든 한 번만 destroy되도록 여기서 정리함
+            Event::LoopExiting => unsafe {
+                let _ = app.device.device_wait_idle();
+                app.destroy();
+            },
+            _ => {}
+        }
+    })?;
+
+    Ok(())
+}
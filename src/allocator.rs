@@ -0,0 +1,361 @@
+//! 큰 device memory block을 할당해두고 buffer와 image에 나누어 주는 memory allocator  
+//! buffer나 image마다 `allocate_memory`를 호출하면 `maxMemoryAllocationCount` 제한(보통 4096)에 금방 도달하므로  
+//! memory type마다 block을 할당하고 그 안에서 suballocation 함
+
+use anyhow::{anyhow, Result};
+use vulkanalia::prelude::v1_0::*;
+
+use std::os::raw::c_void;
+use std::ptr::NonNull;
+
+/// 새로 할당하는 block의 크기
+const BLOCK_SIZE: vk::DeviceSize = 64 * 1024 * 1024;
+
+/// block 안에서 나누어 준 memory 영역
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Allocation {
+    /// 영역이 속한 block의 device memory
+    pub memory: vk::DeviceMemory,
+    /// block 안에서 영역이 시작하는 위치
+    pub offset: vk::DeviceSize,
+    /// 영역의 크기
+    pub size: vk::DeviceSize,
+    block: usize,
+    // HOST_VISIBLE이고 HOST_COHERENT인 block은 계속 map된 상태로 유지하므로 영역의 시작 주소를 저장함
+    mapped: Option<NonNull<u8>>,
+}
+
+impl Allocation {
+    /// host에서 영역에 접근할 수 있는 pointer를 반환  
+    /// 같은 device memory를 여러 번 map할 수 없으므로 `map_memory` 대신 이 함수를 사용해야 함
+    pub fn mapped_ptr(&self) -> Result<*mut c_void> {
+        self.mapped
+            .map(|p| p.as_ptr().cast())
+            .ok_or_else(|| anyhow!("Allocation is not host visible and coherent."))
+    }
+}
+
+/// 하나의 `allocate_memory`로 할당한 memory
+#[derive(Clone, Debug)]
+struct Block {
+    memory: vk::DeviceMemory,
+    memory_type: u32,
+    size: vk::DeviceSize,
+    mapped: Option<NonNull<u8>>,
+    free: FreeList,
+}
+
+/// block 안에서 비어있는 영역을 관리하는 free list
+#[derive(Clone, Debug)]
+struct FreeList {
+    // offset 순서로 정렬된 비어있는 영역의 (offset, size)
+    ranges: Vec<(vk::DeviceSize, vk::DeviceSize)>,
+}
+
+impl FreeList {
+    /// `size` 전체가 비어있는 free list를 생성
+    fn new(size: vk::DeviceSize) -> Self {
+        Self {
+            ranges: vec![(0, size)],
+        }
+    }
+
+    /// `alignment`에 맞춘 `size`만큼의 영역을 first-fit으로 찾아서 offset을 반환
+    fn allocate(
+        &mut self,
+        size: vk::DeviceSize,
+        alignment: vk::DeviceSize,
+    ) -> Option<vk::DeviceSize> {
+        let (index, offset) = self
+            .ranges
+            .iter()
+            .enumerate()
+            .find_map(|(i, (offset, free))| {
+                let aligned = align(*offset, alignment);
+                (aligned + size <= offset + free).then_some((i, aligned))
+            })?;
+
+        // 할당한 영역의 앞뒤에 남는 공간은 다시 비어있는 영역으로 돌려놓음
+        let (free_offset, free_size) = self.ranges.remove(index);
+        let end = offset + size;
+        let free_end = free_offset + free_size;
+        if end < free_end {
+            self.ranges.insert(index, (end, free_end - end));
+        }
+        if free_offset < offset {
+            self.ranges
+                .insert(index, (free_offset, offset - free_offset));
+        }
+
+        Some(offset)
+    }
+
+    /// 영역을 비어있는 영역으로 돌려놓고 인접한 영역과 합침
+    fn free(&mut self, offset: vk::DeviceSize, size: vk::DeviceSize) {
+        let index = self.ranges.partition_point(|(o, _)| *o < offset);
+        self.ranges.insert(index, (offset, size));
+
+        // 뒤의 영역과 이어지면 합침
+        if index + 1 < self.ranges.len() && offset + size == self.ranges[index + 1].0 {
+            self.ranges[index].1 += self.ranges.remove(index + 1).1;
+        }
+
+        // 앞의 영역과 이어지면 합침
+        if index > 0 {
+            let (previous_offset, previous_size) = self.ranges[index - 1];
+            if previous_offset + previous_size == offset {
+                self.ranges[index - 1].1 += self.ranges.remove(index).1;
+            }
+        }
+    }
+}
+
+/// memory type마다 block을 할당하고 buffer와 image에 영역을 나누어 주는 allocator
+#[derive(Clone, Debug, Default)]
+pub struct Allocator {
+    memory_properties: vk::PhysicalDeviceMemoryProperties,
+    // linear resource(buffer)와 optimal image가 같은 page를 공유하지 않도록 맞춰야 하는 크기
+    granularity: vk::DeviceSize,
+    blocks: Vec<Block>,
+}
+
+impl Allocator {
+    pub unsafe fn new(instance: &Instance, physical_device: vk::PhysicalDevice) -> Self {
+        let properties = instance.get_physical_device_properties(physical_device);
+
+        Self {
+            memory_properties: instance.get_physical_device_memory_properties(physical_device),
+            granularity: properties.limits.buffer_image_granularity,
+            blocks: vec![],
+        }
+    }
+
+    /// `requirements`와 `properties`를 만족하는 memory 영역을 할당  
+    /// HOST_VISIBLE을 요청하면 flush나 invalidate 없이 `mapped_ptr`로 접근할 수 있도록 HOST_COHERENT도 요구함
+    pub unsafe fn allocate(
+        &mut self,
+        device: &Device,
+        requirements: vk::MemoryRequirements,
+        mut properties: vk::MemoryPropertyFlags,
+    ) -> Result<Allocation> {
+        // buffer의 memory_type_bits에는 HOST_VISIBLE과 HOST_COHERENT를 모두 가진 memory type이 항상 포함됨
+        if properties.contains(vk::MemoryPropertyFlags::HOST_VISIBLE) {
+            properties |= vk::MemoryPropertyFlags::HOST_COHERENT;
+        }
+
+        let memory_type = get_memory_type(&self.memory_properties, properties, requirements)?;
+
+        // buffer와 image를 구분하지 않고 같은 block에 넣으므로 항상 granularity에 맞춤
+        let alignment = requirements.alignment.max(self.granularity);
+
+        // 같은 memory type의 기존 block에 빈 공간이 있으면 사용
+        for (index, block) in self.blocks.iter_mut().enumerate() {
+            if block.memory_type != memory_type {
+                continue;
+            }
+
+            if let Some(offset) = block.free.allocate(requirements.size, alignment) {
+                return Ok(Self::allocation(block, index, offset, requirements.size));
+            }
+        }
+
+        // BLOCK_SIZE보다 큰 resource는 전용 block을 할당함
+        let size = requirements.size.max(BLOCK_SIZE);
+        let mut block = self.allocate_block(device, memory_type, size)?;
+        let offset = block
+            .free
+            .allocate(requirements.size, alignment)
+            .ok_or_else(|| anyhow!("Failed to allocate from a new memory block."))?;
+
+        let index = self.blocks.len();
+        let allocation = Self::allocation(&block, index, offset, requirements.size);
+        self.blocks.push(block);
+
+        Ok(allocation)
+    }
+
+    /// 할당한 영역을 해제  
+    /// block은 allocator가 파괴될 때까지 유지되므로 다음 할당에서 다시 사용됨
+    pub fn free(&mut self, allocation: Allocation) {
+        if allocation.memory.is_null() {
+            return;
+        }
+
+        self.blocks[allocation.block]
+            .free
+            .free(allocation.offset, allocation.size);
+    }
+
+    /// buffer를 생성하고 memory 영역을 할당해서 bind
+    pub unsafe fn create_buffer(
+        &mut self,
+        device: &Device,
+        info: &vk::BufferCreateInfo,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<(vk::Buffer, Allocation)> {
+        let buffer = device.create_buffer(info, None)?;
+        let requirements = device.get_buffer_memory_requirements(buffer);
+        let allocation = self.allocate(device, requirements, properties)?;
+
+        device.bind_buffer_memory(buffer, allocation.memory, allocation.offset)?;
+
+        Ok((buffer, allocation))
+    }
+
+    /// image를 생성하고 memory 영역을 할당해서 bind
+    pub unsafe fn create_image(
+        &mut self,
+        device: &Device,
+        info: &vk::ImageCreateInfo,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<(vk::Image, Allocation)> {
+        let image = device.create_image(info, None)?;
+        let requirements = device.get_image_memory_requirements(image);
+        let allocation = self.allocate(device, requirements, properties)?;
+
+        device.bind_image_memory(image, allocation.memory, allocation.offset)?;
+
+        Ok((image, allocation))
+    }
+
+    /// 모든 block을 해제  
+    /// 이 allocator로 할당한 buffer와 image를 모두 파괴한 뒤에 호출해야 함
+    pub unsafe fn destroy(&mut self, device: &Device) {
+        // HOST_VISIBLE block은 free_memory가 암묵적으로 unmap함
+        self.blocks
+            .iter()
+            .for_each(|b| device.free_memory(b.memory, None));
+        self.blocks.clear();
+    }
+
+    /// 지금까지 할당한 block의 수를 반환
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// memory heap마다 이 allocator가 할당한 block 크기의 합을 반환  
+    /// block 안에서 아직 사용하지 않은 공간도 driver에게는 할당된 memory이므로 함께 포함됨
+    pub fn heap_usage(&self) -> Vec<vk::DeviceSize> {
+        let mut usage = vec![0; self.memory_properties.memory_heap_count as usize];
+        for block in &self.blocks {
+            let heap = self.memory_properties.memory_types[block.memory_type as usize].heap_index;
+            usage[heap as usize] += block.size;
+        }
+        usage
+    }
+
+    unsafe fn allocate_block(
+        &self,
+        device: &Device,
+        memory_type: u32,
+        size: vk::DeviceSize,
+    ) -> Result<Block> {
+        let info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(size)
+            .memory_type_index(memory_type);
+
+        let memory = device.allocate_memory(&info, None)?;
+
+        // HOST_COHERENT인 HOST_VISIBLE memory는 할당할 때 한 번만 map해두고 block이 해제될 때까지 유지함
+        // coherent하지 않은 memory는 flush와 invalidate가 필요하므로 map하지 않음
+        let flags = self.memory_properties.memory_types[memory_type as usize].property_flags;
+        let host_coherent =
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT;
+        let mapped = if flags.contains(host_coherent) {
+            let ptr = device.map_memory(memory, 0, size, vk::MemoryMapFlags::empty())?;
+            NonNull::new(ptr.cast())
+        } else {
+            None
+        };
+
+        Ok(Block {
+            memory,
+            memory_type,
+            size,
+            mapped,
+            free: FreeList::new(size),
+        })
+    }
+
+    fn allocation(
+        block: &Block,
+        index: usize,
+        offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> Allocation {
+        Allocation {
+            memory: block.memory,
+            offset,
+            size,
+            block: index,
+            mapped: block
+                .mapped
+                .map(|p| unsafe { NonNull::new_unchecked(p.as_ptr().add(offset as usize)) }),
+        }
+    }
+}
+
+/// `value`를 `alignment`의 배수로 올림
+fn align(value: vk::DeviceSize, alignment: vk::DeviceSize) -> vk::DeviceSize {
+    value.div_ceil(alignment) * alignment
+}
+
+/// resource가 요구하는 memory type과 properties를 모두 만족하는 memory type의 index를 찾음
+pub(crate) fn get_memory_type(
+    memory: &vk::PhysicalDeviceMemoryProperties,
+    properties: vk::MemoryPropertyFlags,
+    requirements: vk::MemoryRequirements,
+) -> Result<u32> {
+    // memory_type_bits는 사용 가능한 memory type들을 bit field로 나타냄
+    (0..memory.memory_type_count)
+        .find(|i| {
+            let suitable = (requirements.memory_type_bits & (1 << i)) != 0;
+            let memory_type = memory.memory_types[*i as usize];
+            suitable && memory_type.property_flags.contains(properties)
+        })
+        .ok_or_else(|| anyhow!("Failed to find suitable memory type."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allocate_splits_free_range() {
+        let mut free = FreeList::new(1024);
+
+        assert_eq!(free.allocate(256, 1), Some(0));
+        assert_eq!(free.allocate(256, 1), Some(256));
+        assert_eq!(free.ranges, vec![(512, 512)]);
+        assert_eq!(free.allocate(1024, 1), None);
+    }
+
+    #[test]
+    fn allocate_keeps_alignment_padding() {
+        let mut free = FreeList::new(1024);
+
+        assert_eq!(free.allocate(100, 1), Some(0));
+        assert_eq!(free.allocate(100, 256), Some(256));
+        // 정렬하느라 건너뛴 앞부분도 다시 사용할 수 있음
+        assert_eq!(free.ranges, vec![(100, 156), (356, 668)]);
+        assert_eq!(free.allocate(156, 4), Some(100));
+        assert_eq!(free.ranges, vec![(356, 668)]);
+    }
+
+    #[test]
+    fn free_coalesces_neighbors() {
+        let mut free = FreeList::new(1024);
+        let a = free.allocate(256, 1).unwrap();
+        let b = free.allocate(256, 1).unwrap();
+        let c = free.allocate(256, 1).unwrap();
+
+        free.free(a, 256);
+        free.free(c, 256);
+        assert_eq!(free.ranges, vec![(0, 256), (512, 512)]);
+
+        // 앞뒤 영역과 모두 이어지므로 하나로 합쳐짐
+        free.free(b, 256);
+        assert_eq!(free.ranges, vec![(0, 1024)]);
+        assert_eq!(free.allocate(1024, 1), Some(0));
+    }
+}
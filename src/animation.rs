@@ -0,0 +1,258 @@
+//! glTF skin의 node hierarchy와 keyframe animation  
+//! animation의 특정 시점에서 node의 transform을 계산해서 vertex shader에 전달할 joint matrix와 morph target weight를 만듦
+
+use cgmath::prelude::*;
+use cgmath::{Matrix4, Quaternion, Vector3};
+
+use std::ops::{Add, Mul};
+
+/// translation, rotation, scale로 나누어 저장한 node의 local transform  
+/// matrix로 저장하면 keyframe 사이를 보간할 수 없으므로 성분별로 저장함
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    pub translation: Vector3<f32>,
+    pub rotation: Quaternion<f32>,
+    pub scale: Vector3<f32>,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            translation: Vector3::zero(),
+            rotation: Quaternion::one(),
+            scale: Vector3::new(1.0, 1.0, 1.0),
+        }
+    }
+}
+
+impl Transform {
+    /// scale, rotation, translation 순서로 적용하는 matrix
+    pub fn matrix(&self) -> Matrix4<f32> {
+        Matrix4::from_translation(self.translation)
+            * Matrix4::from(self.rotation)
+            * Matrix4::from_nonuniform_scale(self.scale.x, self.scale.y, self.scale.z)
+    }
+}
+
+/// skeleton을 이루는 node
+#[derive(Clone, Debug)]
+pub struct Node {
+    pub name: Option<String>,
+    /// 부모 node의 `Skeleton::nodes`에서의 index  
+    /// scene의 최상위 node이면 `None`
+    pub parent: Option<usize>,
+    /// animation이 없을 때의 local transform
+    pub transform: Transform,
+}
+
+/// skin이 참조하는 joint와 joint의 transform을 계산하기 위한 node hierarchy  
+/// joint가 아닌 부모 node의 transform도 joint에 적용되므로 scene의 모든 node를 저장함
+#[derive(Clone, Debug)]
+pub struct Skeleton {
+    /// 부모 node가 항상 자식 node보다 앞에 오도록 정렬한 node
+    pub nodes: Vec<Node>,
+    /// joint마다 해당하는 node의 index  
+    /// vertex의 joint index는 이 배열의 index임
+    pub joints: Vec<usize>,
+    /// mesh의 좌표를 joint의 좌표계로 옮기는 matrix
+    pub inverse_bind_matrices: Vec<Matrix4<f32>>,
+}
+
+impl Skeleton {
+    /// joint의 수
+    pub fn joint_count(&self) -> usize {
+        self.joints.len()
+    }
+
+    /// `animation`의 `time`초 시점에서 joint마다 vertex에 적용할 matrix  
+    /// vertex를 joint의 weight로 섞은 matrix로 변환하면 animation이 적용된 model 좌표가 됨  
+    /// `animation`이 없으면 rest pose의 matrix를 반환함
+    pub fn joint_matrices(&self, animation: Option<&Animation>, time: f32) -> Vec<Matrix4<f32>> {
+        let mut transforms = self.nodes.iter().map(|n| n.transform).collect::<Vec<_>>();
+        if let Some(animation) = animation {
+            animation.apply(time, &mut transforms);
+        }
+
+        // 부모 node가 먼저 오므로 한 번 순회하면 모든 node의 global transform을 구할 수 있음
+        let mut globals: Vec<Matrix4<f32>> = Vec::with_capacity(self.nodes.len());
+        for (node, transform) in self.nodes.iter().zip(&transforms) {
+            let global = match node.parent {
+                Some(parent) => globals[parent] * transform.matrix(),
+                None => transform.matrix(),
+            };
+            globals.push(global);
+        }
+
+        self.joints
+            .iter()
+            .zip(&self.inverse_bind_matrices)
+            .map(|(&joint, &inverse_bind)| globals[joint] * inverse_bind)
+            .collect()
+    }
+}
+
+/// keyframe 사이의 값을 구하는 방법
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Interpolation {
+    /// 다음 keyframe까지 이전 keyframe의 값을 유지
+    Step,
+    /// 직선으로 보간하고, rotation은 구면 선형 보간함
+    Linear,
+    /// keyframe마다 저장된 tangent로 Hermite spline 보간함
+    CubicSpline,
+}
+
+/// channel이 바꾸는 transform의 성분이나 morph target weight와 keyframe의 값  
+/// `Interpolation::CubicSpline`이면 keyframe마다 in-tangent, 값, out-tangent 순서로 3개씩 저장됨
+#[derive(Clone, Debug)]
+pub enum Keyframes {
+    Translation(Vec<Vector3<f32>>),
+    Rotation(Vec<Quaternion<f32>>),
+    Scale(Vec<Vector3<f32>>),
+    /// 값 하나마다 morph target의 수만큼 weight가 이어서 저장됨
+    Weights(Vec<f32>),
+}
+
+/// 하나의 node의 한 성분을 시간에 따라 바꾸는 animation channel
+#[derive(Clone, Debug)]
+pub struct Channel {
+    /// `Skeleton::nodes`에서의 index
+    pub node: usize,
+    pub interpolation: Interpolation,
+    /// 오름차순으로 정렬된 keyframe의 시간(초)
+    pub times: Vec<f32>,
+    pub keyframes: Keyframes,
+}
+
+impl Channel {
+    /// `time`초 시점의 값  
+    /// `values(i)`는 저장된 i번째 값을 반환하며, 첫 keyframe 이전과 마지막 keyframe 이후에는 가장 가까운 keyframe의 값을 사용함
+    fn sample<T: Keyframe>(&self, time: f32, values: impl Fn(usize) -> T) -> T {
+        let cubic = self.interpolation == Interpolation::CubicSpline;
+        let value = |i: usize| if cubic { values(i * 3 + 1) } else { values(i) };
+
+        let last = self.times.len() - 1;
+        if time <= self.times[0] {
+            return value(0);
+        }
+        if time >= self.times[last] {
+            return value(last);
+        }
+
+        // time보다 큰 첫 keyframe과 그 이전 keyframe 사이를 보간함
+        let next = self.times.partition_point(|&t| t <= time);
+        let previous = next - 1;
+        let dt = self.times[next] - self.times[previous];
+        let t = (time - self.times[previous]) / dt;
+
+        match self.interpolation {
+            Interpolation::Step => value(previous),
+            Interpolation::Linear => T::linear(values(previous), values(next), t),
+            Interpolation::CubicSpline => {
+                let (t2, t3) = (t * t, t * t * t);
+                // tangent는 초당 변화량이므로 keyframe 사이의 시간을 곱해서 사용함
+                let p0 = values(previous * 3 + 1);
+                let m0 = values(previous * 3 + 2) * dt;
+                let p1 = values(next * 3 + 1);
+                let m1 = values(next * 3) * dt;
+
+                let value = p0 * (2.0 * t3 - 3.0 * t2 + 1.0)
+                    + m0 * (t3 - 2.0 * t2 + t)
+                    + p1 * (-2.0 * t3 + 3.0 * t2)
+                    + m1 * (t3 - t2);
+                value.renormalize()
+            }
+        }
+    }
+}
+
+/// 여러 channel로 이루어진 하나의 animation clip
+#[derive(Clone, Debug)]
+pub struct Animation {
+    pub name: Option<String>,
+    /// 가장 늦은 keyframe의 시간(초)
+    pub duration: f32,
+    pub channels: Vec<Channel>,
+}
+
+impl Animation {
+    /// `transforms`에서 channel이 가리키는 node의 성분을 `time`초 시점의 값으로 바꿈  
+    /// `time`이 `duration`을 넘으면 처음부터 반복함
+    pub fn apply(&self, time: f32, transforms: &mut [Transform]) {
+        let time = self.wrap(time);
+
+        for channel in &self.channels {
+            let transform = &mut transforms[channel.node];
+            match &channel.keyframes {
+                Keyframes::Translation(values) => {
+                    transform.translation = channel.sample(time, |i| values[i])
+                }
+                Keyframes::Rotation(values) => {
+                    transform.rotation = channel.sample(time, |i| values[i])
+                }
+                Keyframes::Scale(values) => transform.scale = channel.sample(time, |i| values[i]),
+                Keyframes::Weights(_) => {}
+            }
+        }
+    }
+
+    /// morph target weight를 바꾸는 channel이 있으면 `weights`를 `time`초 시점의 값으로 바꿈  
+    /// `weights`는 morph target의 수와 길이가 같아야 함
+    pub fn apply_weights(&self, time: f32, weights: &mut [f32]) {
+        let time = self.wrap(time);
+        let count = weights.len();
+
+        for channel in &self.channels {
+            if let Keyframes::Weights(values) = &channel.keyframes {
+                // morph target마다 따로 보간함
+                for (target, weight) in weights.iter_mut().enumerate() {
+                    *weight = channel.sample(time, |i| values[i * count + target]);
+                }
+            }
+        }
+    }
+
+    /// 반복 재생할 때의 animation 안에서의 시간
+    fn wrap(&self, time: f32) -> f32 {
+        if self.duration > 0.0 {
+            time.rem_euclid(self.duration)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// keyframe 값으로 사용할 수 있는 type  
+/// cubic spline 보간은 덧셈과 scalar 곱셈만으로 계산함
+trait Keyframe: Copy + Add<Output = Self> + Mul<f32, Output = Self> {
+    /// `a`에서 `b`까지 `t`만큼 선형 보간한 값
+    fn linear(a: Self, b: Self, t: f32) -> Self;
+
+    /// 보간한 값이 올바른 값이 되도록 보정
+    fn renormalize(self) -> Self {
+        self
+    }
+}
+
+impl Keyframe for f32 {
+    fn linear(a: Self, b: Self, t: f32) -> Self {
+        a + (b - a) * t
+    }
+}
+
+impl Keyframe for Vector3<f32> {
+    fn linear(a: Self, b: Self, t: f32) -> Self {
+        a + (b - a) * t
+    }
+}
+
+impl Keyframe for Quaternion<f32> {
+    fn linear(a: Self, b: Self, t: f32) -> Self {
+        a.slerp(b, t)
+    }
+
+    /// 회전을 나타내려면 단위 quaternion이어야 함
+    fn renormalize(self) -> Self {
+        self.normalize()
+    }
+}
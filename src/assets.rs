@@ -0,0 +1,643 @@
+//! worker thread에서 asset을 decode하고 transfer queue로 upload하는 비동기 asset loader  
+//! 파일을 읽고 decode하는 작업은 Vulkan 오브젝트를 사용하지 않으므로 worker thread에서 실행하고,  
+//! decode가 끝난 데이터는 main thread에서 `poll`을 호출할 때 transfer queue에 복사 command로 제출함  
+//! 제출한 upload는 fence로 완료를 확인하므로 main thread는 기다리지 않고 계속 frame을 그릴 수 있음
+
+use anyhow::{anyhow, Result};
+use log::*;
+use vulkanalia::prelude::v1_0::*;
+
+use std::fs::File;
+use std::mem::size_of_val;
+use std::path::Path;
+use std::ptr::copy_nonoverlapping as memcpy;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::allocator::get_memory_type;
+
+/// asset을 decode하는 worker thread의 최대 수
+pub const MAX_ASSET_WORKERS: usize = 4;
+
+/// `AssetLoader`에 요청한 asset을 구분하기 위한 id
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AssetId(u64);
+
+/// worker thread에서 decode한 asset의 데이터
+#[derive(Clone, Debug)]
+enum DecodedAsset {
+    /// RGBA8 pixel
+    Image {
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    },
+    /// vertex 데이터와 u32 index
+    Mesh {
+        vertices: Vec<u8>,
+        indices: Vec<u32>,
+    },
+}
+
+/// worker thread가 실행하는 decode 작업
+type Job = Box<dyn FnOnce() -> (AssetId, Result<DecodedAsset>) + Send>;
+
+/// upload가 끝난 texture image  
+/// layout은 SHADER_READ_ONLY_OPTIMAL이고 mip level은 하나임
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LoadedImage {
+    pub image: vk::Image,
+    pub memory: vk::DeviceMemory,
+    pub format: vk::Format,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// upload가 끝난 vertex buffer와 index buffer
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LoadedMesh {
+    pub vertex_buffer: vk::Buffer,
+    pub vertex_memory: vk::DeviceMemory,
+    pub index_buffer: vk::Buffer,
+    pub index_memory: vk::DeviceMemory,
+    pub index_count: u32,
+}
+
+/// upload가 끝난 asset
+#[derive(Copy, Clone, Debug)]
+pub enum LoadedAsset {
+    Image(LoadedImage),
+    Mesh(LoadedMesh),
+}
+
+/// transfer queue에서 upload가 끝난 asset  
+/// graphics queue에서 asset을 사용하는 첫 submit은 `semaphore`를 기다려야 transfer queue가 쓴 데이터를 볼 수 있음  
+/// `semaphore`는 그 submit이 끝난 뒤에 호출한 쪽에서 파괴해야 함
+#[derive(Copy, Clone, Debug)]
+pub struct CompletedAsset {
+    pub id: AssetId,
+    pub asset: LoadedAsset,
+    pub semaphore: vk::Semaphore,
+}
+
+/// transfer queue에 제출한 뒤 아직 완료를 확인하지 않은 upload  
+/// 실행이 끝날 때까지 staging buffer와 command buffer를 유지해야 함
+#[derive(Copy, Clone, Debug)]
+struct PendingUpload {
+    id: AssetId,
+    asset: LoadedAsset,
+    staging_buffer: vk::Buffer,
+    staging_memory: vk::DeviceMemory,
+    command_buffer: vk::CommandBuffer,
+    fence: vk::Fence,
+    semaphore: vk::Semaphore,
+}
+
+/// worker thread에서 asset을 decode하고 transfer queue로 upload하는 loader  
+/// Vulkan 오브젝트는 모두 `poll`을 호출하는 thread에서만 생성하고 사용함
+#[derive(Debug)]
+pub struct AssetLoader {
+    // worker thread에 decode 작업을 보내는 channel, 파괴할 때 닫아서 worker를 종료시킴
+    jobs: Option<Sender<Job>>,
+    // worker thread가 decode한 결과를 받는 channel
+    results: Receiver<(AssetId, Result<DecodedAsset>)>,
+    workers: Vec<JoinHandle<()>>,
+    queue: vk::Queue,
+    command_pool: vk::CommandPool,
+    // transfer queue family와 graphics queue family가 다르면 두 family가 함께 사용하도록 CONCURRENT로 생성함
+    queue_family_indices: Vec<u32>,
+    memory_properties: vk::PhysicalDeviceMemoryProperties,
+    next_id: u64,
+    // 요청한 뒤 아직 반환하지 않은 asset의 수
+    outstanding: usize,
+    uploads: Vec<PendingUpload>,
+}
+
+impl AssetLoader {
+    /// `transfer_queue_family`의 `transfer_queue`로 upload하는 loader를 생성하고 worker thread를 시작  
+    /// upload한 resource는 `graphics_queue_family`에서 ownership transfer 없이 사용할 수 있음
+    pub unsafe fn new(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        transfer_queue_family: u32,
+        transfer_queue: vk::Queue,
+        graphics_queue_family: u32,
+    ) -> Result<Self> {
+        let info = vk::CommandPoolCreateInfo::builder()
+            .flags(vk::CommandPoolCreateFlags::TRANSIENT)
+            .queue_family_index(transfer_queue_family);
+
+        let command_pool = device.create_command_pool(&info, None)?;
+
+        let queue_family_indices = if transfer_queue_family != graphics_queue_family {
+            vec![transfer_queue_family, graphics_queue_family]
+        } else {
+            vec![]
+        };
+
+        let (jobs, job_receiver) = channel::<Job>();
+        let (results_sender, results) = channel();
+
+        // 여러 worker가 하나의 channel에서 작업을 나누어 받음
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let count = thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .clamp(1, MAX_ASSET_WORKERS);
+        let workers = (0..count)
+            .map(|i| {
+                let jobs = job_receiver.clone();
+                let results = results_sender.clone();
+                thread::Builder::new()
+                    .name(format!("asset worker {}", i))
+                    .spawn(move || worker(jobs, results))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        info!("Decoding assets on {} worker threads.", count);
+
+        Ok(Self {
+            jobs: Some(jobs),
+            results,
+            workers,
+            queue: transfer_queue,
+            command_pool,
+            queue_family_indices,
+            memory_properties: instance.get_physical_device_memory_properties(physical_device),
+            next_id: 0,
+            outstanding: 0,
+            uploads: vec![],
+        })
+    }
+
+    /// `path`의 RGBA PNG 파일을 texture image로 읽도록 요청  
+    /// PNG의 pixel은 sRGB로 저장되어 있으므로 R8G8B8A8_SRGB format의 image가 됨
+    pub fn load_image(&mut self, path: impl AsRef<Path>) -> Result<AssetId> {
+        let path = path.as_ref().to_path_buf();
+        self.submit_job(move || decode_png(&path))
+    }
+
+    /// `decode`가 만든 vertex 데이터와 index를 vertex buffer와 index buffer로 읽도록 요청  
+    /// `decode`는 worker thread에서 실행되므로 파일을 읽거나 vertex를 만드는 오래 걸리는 작업을 해도 됨
+    pub fn load_mesh<F>(&mut self, decode: F) -> Result<AssetId>
+    where
+        F: FnOnce() -> Result<(Vec<u8>, Vec<u32>)> + Send + 'static,
+    {
+        self.submit_job(move || {
+            decode().map(|(vertices, indices)| DecodedAsset::Mesh { vertices, indices })
+        })
+    }
+
+    /// decode가 끝난 asset의 upload를 제출하고 upload가 끝난 asset을 반환  
+    /// 기다리지 않고 바로 반환하므로 매 frame 호출해야 함  
+    /// decode에 실패한 asset은 log만 남기고 반환하지 않음
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub unsafe fn poll(&mut self, device: &Device) -> Result<Vec<CompletedAsset>> {
+        while let Ok((id, result)) = self.results.try_recv() {
+            match result {
+                Ok(decoded) => self.submit_upload(device, id, decoded)?,
+                Err(error) => {
+                    warn!("Failed to load asset {:?}: {:?}", id, error);
+                    self.outstanding -= 1;
+                }
+            }
+        }
+
+        let mut completed = vec![];
+        let mut index = 0;
+        while index < self.uploads.len() {
+            let upload = self.uploads[index];
+            if device.get_fence_status(upload.fence)? != vk::SuccessCode::SUCCESS {
+                index += 1;
+                continue;
+            }
+
+            self.uploads.swap_remove(index);
+            self.destroy_upload(device, &upload);
+            self.outstanding -= 1;
+            completed.push(CompletedAsset {
+                id: upload.id,
+                asset: upload.asset,
+                semaphore: upload.semaphore,
+            });
+        }
+
+        Ok(completed)
+    }
+
+    /// decode나 upload가 끝나지 않은 asset이 있는지 여부
+    pub fn is_busy(&self) -> bool {
+        self.outstanding > 0
+    }
+
+    /// worker thread를 종료하고 loader가 만든 오브젝트를 파괴  
+    /// 아직 반환하지 않은 asset의 resource도 함께 파괴함
+    pub unsafe fn destroy(&mut self, device: &Device) {
+        // channel을 닫으면 worker가 진행중인 작업을 끝낸 뒤에 종료됨
+        self.jobs = None;
+        for worker in self.workers.drain(..) {
+            if worker.join().is_err() {
+                warn!("Asset worker thread panicked.");
+            }
+        }
+
+        for upload in std::mem::take(&mut self.uploads) {
+            let _ = device.wait_for_fences(&[upload.fence], true, u64::MAX);
+            self.destroy_upload(device, &upload);
+            device.destroy_semaphore(upload.semaphore, None);
+            destroy_asset(device, upload.asset);
+        }
+
+        // command pool이 파괴되면 할당된 command buffer도 함께 해제됨
+        device.destroy_command_pool(self.command_pool, None);
+    }
+
+    /// worker thread에 `decode`를 보내고 결과를 구분할 id를 반환
+    fn submit_job<F>(&mut self, decode: F) -> Result<AssetId>
+    where
+        F: FnOnce() -> Result<DecodedAsset> + Send + 'static,
+    {
+        let id = AssetId(self.next_id);
+        self.next_id += 1;
+
+        let jobs = self
+            .jobs
+            .as_ref()
+            .ok_or_else(|| anyhow!("Asset loader is destroyed."))?;
+        jobs.send(Box::new(move || (id, decode())))
+            .map_err(|_| anyhow!("Asset worker threads have stopped."))?;
+        self.outstanding += 1;
+
+        Ok(id)
+    }
+
+    /// decode한 데이터를 staging buffer에 쓰고 resource로 복사하는 command를 transfer queue에 제출
+    #[tracing::instrument(skip_all, fields(id = id.0))]
+    unsafe fn submit_upload(
+        &mut self,
+        device: &Device,
+        id: AssetId,
+        decoded: DecodedAsset,
+    ) -> Result<()> {
+        let info = vk::CommandBufferAllocateInfo::builder()
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_pool(self.command_pool)
+            .command_buffer_count(1);
+
+        let command_buffer = device.allocate_command_buffers(&info)?[0];
+
+        let info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+
+        device.begin_command_buffer(command_buffer, &info)?;
+
+        let (asset, staging_buffer, staging_memory) = match decoded {
+            DecodedAsset::Image {
+                width,
+                height,
+                pixels,
+            } => {
+                let (staging_buffer, staging_memory) =
+                    self.create_staging_buffer(device, &pixels)?;
+                let image = self.record_image_upload(
+                    device,
+                    command_buffer,
+                    staging_buffer,
+                    width,
+                    height,
+                )?;
+                (LoadedAsset::Image(image), staging_buffer, staging_memory)
+            }
+            DecodedAsset::Mesh { vertices, indices } => {
+                // vertex 데이터와 index를 하나의 staging buffer에 이어서 씀
+                // index의 시작 위치는 u32 크기의 배수여야 복사할 때 그대로 사용할 수 있음
+                let vertices_size = vertices.len() as vk::DeviceSize;
+                let index_offset = vertices.len().next_multiple_of(4);
+                let mut bytes = vertices;
+                bytes.resize(index_offset, 0);
+                bytes.extend(indices.iter().flat_map(|i| i.to_ne_bytes()));
+
+                let (staging_buffer, staging_memory) =
+                    self.create_staging_buffer(device, &bytes)?;
+                let (vertex_buffer, vertex_memory) = self.create_buffer(
+                    device,
+                    vertices_size,
+                    vk::BufferUsageFlags::TRANSFER_DST | vk::BufferUsageFlags::VERTEX_BUFFER,
+                    vk::MemoryPropertyFlags::DEVICE_LOCAL,
+                )?;
+                let (index_buffer, index_memory) = self.create_buffer(
+                    device,
+                    size_of_val(indices.as_slice()) as vk::DeviceSize,
+                    vk::BufferUsageFlags::TRANSFER_DST | vk::BufferUsageFlags::INDEX_BUFFER,
+                    vk::MemoryPropertyFlags::DEVICE_LOCAL,
+                )?;
+
+                let region = vk::BufferCopy::builder()
+                    .src_offset(0)
+                    .dst_offset(0)
+                    .size(vertices_size);
+                device.cmd_copy_buffer(command_buffer, staging_buffer, vertex_buffer, &[region]);
+
+                let region = vk::BufferCopy::builder()
+                    .src_offset(index_offset as vk::DeviceSize)
+                    .dst_offset(0)
+                    .size(size_of_val(indices.as_slice()) as vk::DeviceSize);
+                device.cmd_copy_buffer(command_buffer, staging_buffer, index_buffer, &[region]);
+
+                let mesh = LoadedMesh {
+                    vertex_buffer,
+                    vertex_memory,
+                    index_buffer,
+                    index_memory,
+                    index_count: indices.len() as u32,
+                };
+                (LoadedAsset::Mesh(mesh), staging_buffer, staging_memory)
+            }
+        };
+
+        device.end_command_buffer(command_buffer)?;
+
+        // fence로 host에서 완료를 확인하고, semaphore로 graphics queue의 첫 사용과 동기화함
+        let fence = device.create_fence(&vk::FenceCreateInfo::builder(), None)?;
+        let semaphore = device.create_semaphore(&vk::SemaphoreCreateInfo::builder(), None)?;
+
+        let command_buffers = &[command_buffer];
+        let signal_semaphores = &[semaphore];
+        let info = vk::SubmitInfo::builder()
+            .command_buffers(command_buffers)
+            .signal_semaphores(signal_semaphores);
+        device.queue_submit(self.queue, &[info], fence)?;
+
+        self.uploads.push(PendingUpload {
+            id,
+            asset,
+            staging_buffer,
+            staging_memory,
+            command_buffer,
+            fence,
+            semaphore,
+        });
+
+        Ok(())
+    }
+
+    /// `staging_buffer`의 pixel을 새로 만든 image로 복사하는 command를 기록  
+    /// transfer queue는 fragment shader stage를 지원하지 않을 수 있으므로 dst stage는 BOTTOM_OF_PIPE로 두고  
+    /// shader에서 읽는 것과의 동기화는 graphics queue가 기다리는 semaphore에 맡김
+    unsafe fn record_image_upload(
+        &self,
+        device: &Device,
+        command_buffer: vk::CommandBuffer,
+        staging_buffer: vk::Buffer,
+        width: u32,
+        height: u32,
+    ) -> Result<LoadedImage> {
+        let format = vk::Format::R8G8B8A8_SRGB;
+        let sharing_mode = if self.queue_family_indices.is_empty() {
+            vk::SharingMode::EXCLUSIVE
+        } else {
+            vk::SharingMode::CONCURRENT
+        };
+
+        let info = vk::ImageCreateInfo::builder()
+            .image_type(vk::ImageType::_2D)
+            .extent(vk::Extent3D {
+                width,
+                height,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(format)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST)
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(&self.queue_family_indices)
+            .samples(vk::SampleCountFlags::_1);
+
+        let image = device.create_image(&info, None)?;
+
+        let requirements = device.get_image_memory_requirements(image);
+        let info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(requirements.size)
+            .memory_type_index(get_memory_type(
+                &self.memory_properties,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+                requirements,
+            )?);
+
+        let memory = device.allocate_memory(&info, None)?;
+        device.bind_image_memory(image, memory, 0)?;
+
+        let subresource = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(0)
+            .level_count(1)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::UNDEFINED)
+            .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(subresource)
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TOP_OF_PIPE,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+
+        let subresource_layers = vk::ImageSubresourceLayers::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .mip_level(0)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        let region = vk::BufferImageCopy::builder()
+            .buffer_offset(0)
+            .buffer_row_length(0)
+            .buffer_image_height(0)
+            .image_subresource(subresource_layers)
+            .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
+            .image_extent(vk::Extent3D {
+                width,
+                height,
+                depth: 1,
+            });
+
+        device.cmd_copy_buffer_to_image(
+            command_buffer,
+            staging_buffer,
+            image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[region],
+        );
+
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(subresource)
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::empty());
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+
+        Ok(LoadedImage {
+            image,
+            memory,
+            format,
+            width,
+            height,
+        })
+    }
+
+    /// `data`를 담은 HOST_VISIBLE staging buffer를 생성
+    unsafe fn create_staging_buffer(
+        &self,
+        device: &Device,
+        data: &[u8],
+    ) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+        let size = data.len() as vk::DeviceSize;
+        let (buffer, memory) = self.create_buffer(
+            device,
+            size,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            vk::MemoryPropertyFlags::HOST_COHERENT | vk::MemoryPropertyFlags::HOST_VISIBLE,
+        )?;
+
+        let mapped = device.map_memory(memory, 0, size, vk::MemoryMapFlags::empty())?;
+        memcpy(data.as_ptr(), mapped.cast(), data.len());
+        device.unmap_memory(memory);
+
+        Ok((buffer, memory))
+    }
+
+    /// buffer를 생성하고 `properties`를 만족하는 memory를 할당해서 bind  
+    /// transfer queue family와 graphics queue family가 다르면 CONCURRENT로 생성함
+    unsafe fn create_buffer(
+        &self,
+        device: &Device,
+        size: vk::DeviceSize,
+        usage: vk::BufferUsageFlags,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+        let sharing_mode = if self.queue_family_indices.is_empty() {
+            vk::SharingMode::EXCLUSIVE
+        } else {
+            vk::SharingMode::CONCURRENT
+        };
+
+        let info = vk::BufferCreateInfo::builder()
+            .size(size)
+            .usage(usage)
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(&self.queue_family_indices);
+
+        let buffer = device.create_buffer(&info, None)?;
+
+        let requirements = device.get_buffer_memory_requirements(buffer);
+        let info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(requirements.size)
+            .memory_type_index(get_memory_type(
+                &self.memory_properties,
+                properties,
+                requirements,
+            )?);
+
+        let memory = device.allocate_memory(&info, None)?;
+        device.bind_buffer_memory(buffer, memory, 0)?;
+
+        Ok((buffer, memory))
+    }
+
+    /// upload에 사용한 staging buffer와 command buffer, fence를 파괴
+    unsafe fn destroy_upload(&self, device: &Device, upload: &PendingUpload) {
+        device.destroy_buffer(upload.staging_buffer, None);
+        device.free_memory(upload.staging_memory, None);
+        device.free_command_buffers(self.command_pool, &[upload.command_buffer]);
+        device.destroy_fence(upload.fence, None);
+    }
+}
+
+/// upload가 끝난 asset의 resource를 파괴
+pub unsafe fn destroy_asset(device: &Device, asset: LoadedAsset) {
+    match asset {
+        LoadedAsset::Image(image) => {
+            device.destroy_image(image.image, None);
+            device.free_memory(image.memory, None);
+        }
+        LoadedAsset::Mesh(mesh) => {
+            device.destroy_buffer(mesh.vertex_buffer, None);
+            device.free_memory(mesh.vertex_memory, None);
+            device.destroy_buffer(mesh.index_buffer, None);
+            device.free_memory(mesh.index_memory, None);
+        }
+    }
+}
+
+/// channel이 닫힐 때까지 작업을 받아서 실행하고 결과를 보냄
+fn worker(jobs: Arc<Mutex<Receiver<Job>>>, results: Sender<(AssetId, Result<DecodedAsset>)>) {
+    loop {
+        // 작업을 받는 동안에만 lock을 잡고, decode는 lock 없이 실행함
+        let job = match jobs.lock() {
+            Ok(jobs) => jobs.recv(),
+            Err(_) => return,
+        };
+
+        let Ok(job) = job else {
+            return;
+        };
+
+        let result = {
+            let _span = tracing::info_span!("decode_asset").entered();
+            job()
+        };
+
+        if results.send(result).is_err() {
+            return;
+        }
+    }
+}
+
+/// RGBA PNG 파일을 읽어서 pixel로 decode
+fn decode_png(path: &Path) -> Result<DecodedAsset> {
+    let decoder = png::Decoder::new(File::open(path)?);
+    let mut reader = decoder.read_info()?;
+
+    // RGBA 형식의 PNG만 지원함
+    if reader.info().color_type != png::ColorType::Rgba {
+        return Err(anyhow!("Texture image must be RGBA."));
+    }
+
+    let mut pixels = vec![0; reader.info().raw_bytes()];
+    reader.next_frame(&mut pixels)?;
+    let (width, height) = reader.info().size();
+
+    Ok(DecodedAsset::Image {
+        width,
+        height,
+        pixels,
+    })
+}
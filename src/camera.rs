@@ -0,0 +1,233 @@
+//! 키보드와 마우스, gamepad로 조작할 수 있는 fly camera  
+//! world 좌표계는 tutorial의 다른 chapter들과 같이 z축이 위쪽을 향함
+
+use cgmath::prelude::*;
+use cgmath::{vec3, Deg, Matrix4, Point3, Rad, Vector3};
+use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+/// world 좌표계에서 위쪽 방향
+const UP: Vector3<f32> = Vector3::new(0.0, 0.0, 1.0);
+
+/// 위아래를 완전히 바라보면 view matrix의 up vector와 평행해지므로 pitch를 이 값으로 제한함
+const MAX_PITCH: Deg<f32> = Deg(89.0);
+
+/// 위치와 바라보는 방향(yaw, pitch)으로 표현되는 camera
+#[derive(Copy, Clone, Debug)]
+pub struct Camera {
+    pub position: Point3<f32>,
+    /// x축을 기준으로 z축을 중심으로 회전한 각도
+    pub yaw: Rad<f32>,
+    /// xy 평면을 기준으로 위아래로 회전한 각도
+    pub pitch: Rad<f32>,
+    /// 세로 방향 시야각
+    pub fovy: Deg<f32>,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Camera {
+    /// `position`에서 `target`을 바라보는 camera를 생성
+    pub fn look_at(position: Point3<f32>, target: Point3<f32>) -> Self {
+        let direction = (target - position).normalize();
+
+        Self {
+            position,
+            yaw: Rad(direction.y.atan2(direction.x)),
+            pitch: Rad(direction.z.asin()),
+            fovy: Deg(45.0),
+            near: 0.1,
+            far: 100.0,
+        }
+    }
+
+    /// camera가 바라보는 방향의 단위 vector
+    pub fn forward(&self) -> Vector3<f32> {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        vec3(cos_pitch * cos_yaw, cos_pitch * sin_yaw, sin_pitch)
+    }
+
+    /// camera의 오른쪽 방향의 단위 vector
+    pub fn right(&self) -> Vector3<f32> {
+        self.forward().cross(UP).normalize()
+    }
+
+    /// world 좌표를 camera 좌표로 변환하는 view matrix
+    pub fn view(&self) -> Matrix4<f32> {
+        Matrix4::look_to_rh(self.position, self.forward(), UP)
+    }
+
+    /// Vulkan의 clip space에 맞게 보정된 projection matrix
+    pub fn projection(&self, aspect: f32) -> Matrix4<f32> {
+        // cgmath는 OpenGL의 clip space를 기준으로 하므로 Vulkan에 맞게 보정
+        // Vulkan은 y축이 아래를 향하고 depth 범위가 0.0 ~ 1.0임
+        #[rustfmt::skip]
+        let correction = Matrix4::new(
+            1.0,  0.0,       0.0, 0.0,
+            0.0, -1.0,       0.0, 0.0,
+            0.0,  0.0, 1.0 / 2.0, 0.0,
+            0.0,  0.0, 1.0 / 2.0, 1.0,
+        );
+
+        correction * cgmath::perspective(self.fovy, aspect, self.near, self.far)
+    }
+}
+
+/// window event를 받아서 camera를 움직이는 controller  
+/// WASD로 앞뒤좌우, Space와 Shift로 위아래로 이동하고 마우스 오른쪽 버튼을 누른 채로 움직이면 주위를 둘러봄  
+/// gamepad같은 analog 입력은 `set_analog_input`으로 함께 전달할 수 있음
+#[derive(Clone, Debug)]
+pub struct CameraController {
+    /// 초당 이동 거리
+    pub speed: f32,
+    /// 마우스가 1 pixel 움직일 때 회전하는 각도
+    pub sensitivity: Rad<f32>,
+    /// analog stick을 끝까지 기울였을 때 초당 회전하는 각도
+    pub look_speed: Rad<f32>,
+    forward: bool,
+    backward: bool,
+    left: bool,
+    right: bool,
+    up: bool,
+    down: bool,
+    looking: bool,
+    cursor: Option<(f64, f64)>,
+    // 다음 update까지 누적된 마우스 이동량
+    delta: (f64, f64),
+    // analog 입력의 이동 방향 (x: 오른쪽, y: 앞쪽, z: 위쪽), 각 성분은 -1.0 ~ 1.0
+    analog_movement: Vector3<f32>,
+    // analog 입력의 회전 (yaw, pitch), 각 성분은 -1.0 ~ 1.0
+    analog_look: (f32, f32),
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            speed: 2.0,
+            sensitivity: Deg(0.2).into(),
+            look_speed: Deg(120.0).into(),
+            forward: false,
+            backward: false,
+            left: false,
+            right: false,
+            up: false,
+            down: false,
+            looking: false,
+            cursor: None,
+            delta: (0.0, 0.0),
+            analog_movement: Vector3::zero(),
+            analog_look: (0.0, 0.0),
+        }
+    }
+}
+
+impl CameraController {
+    /// window event를 처리하고, camera 조작에 사용된 event면 `true`를 반환
+    pub fn handle_window_event(&mut self, event: &WindowEvent) -> bool {
+        match event {
+            WindowEvent::KeyboardInput { event, .. } => {
+                let pressed = event.state == ElementState::Pressed;
+                let PhysicalKey::Code(code) = event.physical_key else {
+                    return false;
+                };
+
+                match code {
+                    KeyCode::KeyW => self.forward = pressed,
+                    KeyCode::KeyS => self.backward = pressed,
+                    KeyCode::KeyA => self.left = pressed,
+                    KeyCode::KeyD => self.right = pressed,
+                    KeyCode::Space => self.up = pressed,
+                    KeyCode::ShiftLeft => self.down = pressed,
+                    _ => return false,
+                }
+
+                true
+            }
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Right,
+                ..
+            } => {
+                self.looking = *state == ElementState::Pressed;
+                true
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                // 버튼을 누르지 않은 동안에도 위치를 기록해야 누르는 순간 camera가 튀지 않음
+                if let (true, Some((x, y))) = (self.looking, self.cursor) {
+                    self.delta.0 += position.x - x;
+                    self.delta.1 += position.y - y;
+                }
+                self.cursor = Some((position.x, position.y));
+                self.looking
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.cursor = None;
+                false
+            }
+            // focus를 잃으면 key release event를 받지 못하므로 모든 입력을 초기화
+            WindowEvent::Focused(false) => {
+                *self = Self {
+                    speed: self.speed,
+                    sensitivity: self.sensitivity,
+                    look_speed: self.look_speed,
+                    ..Self::default()
+                };
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// gamepad의 analog stick같은 연속적인 입력을 설정  
+    /// `movement`는 (오른쪽, 앞쪽, 위쪽), `look`은 (오른쪽, 위쪽)으로 기울인 정도이고 각 성분은 -1.0 ~ 1.0이어야 함  
+    /// 입력이 바뀔 때마다가 아니라 현재 상태를 매 frame 설정해야 함
+    pub fn set_analog_input(&mut self, movement: Vector3<f32>, look: (f32, f32)) {
+        self.analog_movement = movement;
+        self.analog_look = look;
+    }
+
+    /// 지난 update 이후 `dt`초 동안의 입력을 camera에 반영
+    pub fn update(&mut self, camera: &mut Camera, dt: f32) {
+        let (dx, dy) = std::mem::take(&mut self.delta);
+        camera.yaw -= self.sensitivity * dx as f32;
+        camera.pitch -= self.sensitivity * dy as f32;
+        // 마우스와 달리 analog stick은 기울인 정도가 회전 속도이므로 dt를 곱함
+        let (look_x, look_y) = self.analog_look;
+        camera.yaw -= self.look_speed * look_x * dt;
+        camera.pitch += self.look_speed * look_y * dt;
+        let max_pitch = Rad::from(MAX_PITCH);
+        camera.pitch = Rad(camera.pitch.0.clamp(-max_pitch.0, max_pitch.0));
+
+        // 바닥과 평행하게 이동하도록 pitch는 무시함
+        let (sin_yaw, cos_yaw) = camera.yaw.sin_cos();
+        let forward = vec3(cos_yaw, sin_yaw, 0.0);
+        let right = forward.cross(UP);
+
+        // 반대 방향의 key를 함께 누르면 서로 상쇄됨
+        let axis = |positive: bool, negative: bool| match (positive, negative) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            _ => 0.0,
+        };
+        let direction = forward * axis(self.forward, self.backward)
+            + right * axis(self.right, self.left)
+            + UP * axis(self.up, self.down);
+        let mut velocity = if direction.magnitude2() > 0.0 {
+            direction.normalize()
+        } else {
+            Vector3::zero()
+        };
+
+        // stick을 조금만 기울이면 천천히 움직이도록 analog 입력은 정규화하지 않음
+        velocity += right * self.analog_movement.x
+            + forward * self.analog_movement.y
+            + UP * self.analog_movement.z;
+        // key와 stick을 함께 사용해도 최대 속도를 넘지 않음
+        if velocity.magnitude2() > 1.0 {
+            velocity = velocity.normalize();
+        }
+
+        camera.position += velocity * self.speed * dt;
+    }
+}
@@ -0,0 +1,115 @@
+//! RenderDoc in-application API로 코드에서 frame capture를 시작하는 기능  
+//! RenderDoc UI의 capture 방법을 익히지 않아도 hotkey나 첫 frame에서 바로 capture를 남길 수 있음  
+//! `renderdoc` feature를 끄면 아무것도 하지 않으므로 chapter는 `cfg` 없이 사용할 수 있음
+
+use log::*;
+use winit::event::{ElementState, WindowEvent};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+#[cfg(feature = "renderdoc")]
+use renderdoc::{RenderDoc, V141};
+
+use std::fmt;
+
+/// 다음 frame의 capture를 시작하는 key  
+/// RenderDoc이 직접 처리하는 F12, PrintScreen과 겹치지 않도록 다른 key를 사용함
+pub const CAPTURE_KEY: KeyCode = KeyCode::F9;
+
+/// 첫 frame을 capture하기 위한 환경 변수 (`--capture-first-frame`과 같음)
+pub const CAPTURE_FIRST_FRAME_ENV: &str = "VK_TUTORIAL_CAPTURE_FIRST_FRAME";
+
+/// RenderDoc API를 통해 frame capture를 시작하는 구조체  
+/// RenderDoc에서 실행하지 않았거나 library를 찾지 못하면 아무것도 하지 않음
+pub struct FrameCapture {
+    #[cfg(feature = "renderdoc")]
+    api: Option<RenderDoc<V141>>,
+}
+
+impl FrameCapture {
+    /// RenderDoc API를 불러옴  
+    /// RenderDoc의 Vulkan layer가 instance 생성을 가로챌 수 있도록 instance를 만들기 전에 호출해야 함  
+    /// `capture_first_frame`이면 처음 present하는 frame을 capture함
+    pub fn new(capture_first_frame: bool) -> Self {
+        #[cfg(feature = "renderdoc")]
+        {
+            let api = match RenderDoc::<V141>::new() {
+                Ok(api) => {
+                    let (major, minor, patch) = api.get_api_version();
+                    info!(
+                        "RenderDoc API {}.{}.{} loaded, press {:?} to capture a frame.",
+                        major, minor, patch, CAPTURE_KEY
+                    );
+                    Some(api)
+                }
+                Err(error) => {
+                    warn!("Failed to load RenderDoc API: {}", error);
+                    None
+                }
+            };
+
+            let mut capture = Self { api };
+            if capture_first_frame {
+                capture.trigger();
+            }
+            capture
+        }
+
+        #[cfg(not(feature = "renderdoc"))]
+        {
+            if capture_first_frame {
+                warn!("Frame capture requires the `renderdoc` feature.");
+            }
+            Self {}
+        }
+    }
+
+    /// RenderDoc API를 사용할 수 있는지 여부
+    pub fn is_available(&self) -> bool {
+        #[cfg(feature = "renderdoc")]
+        {
+            self.api.is_some()
+        }
+
+        #[cfg(not(feature = "renderdoc"))]
+        {
+            false
+        }
+    }
+
+    /// 다음에 present하는 frame을 capture  
+    /// capture 파일은 RenderDoc이 정한 위치에 저장되고 RenderDoc UI의 capture 목록에 나타남
+    pub fn trigger(&mut self) {
+        #[cfg(feature = "renderdoc")]
+        if let Some(api) = self.api.as_mut() {
+            api.trigger_capture();
+            info!("Capturing the next frame with RenderDoc.");
+        }
+    }
+
+    /// window event를 처리하고, `CAPTURE_KEY`를 눌러서 capture를 시작했으면 `true`를 반환
+    pub fn handle_window_event(&mut self, event: &WindowEvent) -> bool {
+        let WindowEvent::KeyboardInput { event, .. } = event else {
+            return false;
+        };
+
+        // key를 누르고 있는 동안 반복되는 event는 무시함
+        if event.state != ElementState::Pressed
+            || event.repeat
+            || event.physical_key != PhysicalKey::Code(CAPTURE_KEY)
+            || !self.is_available()
+        {
+            return false;
+        }
+
+        self.trigger();
+        true
+    }
+}
+
+impl fmt::Debug for FrameCapture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FrameCapture")
+            .field("available", &self.is_available())
+            .finish()
+    }
+}
@@ -0,0 +1,521 @@
+//! command line 인자와 TOML 설정 파일로 tutorial의 동작을 바꾸는 설정  
+//! 다시 컴파일하지 않고 present mode, GPU, validation, resource 디렉토리 등을 바꿔가며 실험할 수 있음  
+//! 이 항목들은 공유 모듈(`swapchain`, `device`, `instance`)과 resource를 읽는 chapter가 사용하므로 모든 chapter에 적용되지만,  
+//! window 크기, fullscreen, MSAA는 chapter마다 window와 render target을 직접 만드므로 `94_config` chapter에서만 적용됨  
+//! 같은 항목을 여러 곳에서 지정하면 command line 인자, 환경 변수, 설정 파일, 기본값 순서로 우선함
+
+use anyhow::{anyhow, Result};
+use clap::{CommandFactory, Parser};
+use log::*;
+use serde::Deserialize;
+use vulkanalia::prelude::v1_0::*;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::capture::CAPTURE_FIRST_FRAME_ENV;
+use crate::device::{GPU_INDEX_ENV, GPU_INDEX_FLAG, SOFTWARE_RENDERING_ENV};
+use crate::instance::{
+    BEST_PRACTICES_ENV, GPU_ASSISTED_VALIDATION_ENV, SYNC_VALIDATION_ENV, VALIDATION_ENABLED,
+};
+use crate::swapchain::{PRESENT_MODES, PRESENT_MODE_FLAG, SHARING_MODES, SHARING_MODE_FLAG};
+
+/// `--config`를 지정하지 않았을 때 현재 디렉토리에서 찾는 설정 파일
+pub const DEFAULT_CONFIG_PATH: &str = "vulkan_tutorial.toml";
+
+/// 설정 파일의 경로를 지정하기 위한 환경 변수
+pub const CONFIG_ENV: &str = "VK_TUTORIAL_CONFIG";
+
+/// window의 기본 크기
+pub const DEFAULT_WINDOW_SIZE: (u32, u32) = (1024, 768);
+
+/// model과 texture를 읽어오는 기본 디렉토리
+pub const DEFAULT_RESOURCE_DIR: &str = "resources";
+
+/// 설정할 수 있는 MSAA sample 수
+const MSAA_SAMPLES: &[(u32, vk::SampleCountFlags)] = &[
+    (1, vk::SampleCountFlags::_1),
+    (2, vk::SampleCountFlags::_2),
+    (4, vk::SampleCountFlags::_4),
+    (8, vk::SampleCountFlags::_8),
+    (16, vk::SampleCountFlags::_16),
+    (32, vk::SampleCountFlags::_32),
+    (64, vk::SampleCountFlags::_64),
+];
+
+/// 처음 `get`을 호출할 때 한 번만 읽어오는 설정
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Vulkan tutorial  
+/// 여기서 지정하지 않은 항목은 설정 파일의 값을 사용함
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Args {
+    /// TOML 설정 파일의 경로
+    #[arg(long, env = CONFIG_ENV)]
+    config: Option<PathBuf>,
+    /// window의 너비
+    #[arg(long)]
+    width: Option<u32>,
+    /// window의 높이
+    #[arg(long)]
+    height: Option<u32>,
+    /// borderless fullscreen으로 시작 (`--fullscreen` 또는 `--fullscreen=<bool>`)
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    fullscreen: Option<bool>,
+    /// present mode (`fifo`, `fifo_relaxed`, `mailbox`, `immediate`)
+    #[arg(long = PRESENT_MODE_FLAG.trim_start_matches('-'))]
+    present_mode: Option<String>,
+    /// present mode를 지정하지 않았을 때 vsync를 사용할지 여부 (true면 FIFO, false면 IMMEDIATE)
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    vsync: Option<bool>,
+    /// graphics와 present queue family가 다를 때 swapchain image의 sharing mode (`concurrent`, `exclusive`)
+    #[arg(long = SHARING_MODE_FLAG.trim_start_matches('-'))]
+    sharing_mode: Option<String>,
+    /// MSAA sample 수 (1, 2, 4, 8, 16, 32, 64)
+    #[arg(long)]
+    msaa: Option<u32>,
+    /// 사용할 GPU의 index (`enumerate_physical_devices`가 반환하는 순서)
+    #[arg(long = GPU_INDEX_FLAG.trim_start_matches('-'), env = GPU_INDEX_ENV)]
+    gpu: Option<usize>,
+    /// hardware GPU 대신 lavapipe나 SwiftShader같은 CPU 구현을 사용할지 여부
+    #[arg(long, num_args = 0..=1, default_missing_value = "true", env = SOFTWARE_RENDERING_ENV)]
+    software: Option<bool>,
+    /// validation layer를 사용할지 여부 (`--validation` 또는 `--validation=<bool>`)
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    validation: Option<bool>,
+    /// shader를 계측해서 out-of-bounds 접근을 검사하는 GPU-assisted validation을 사용할지 여부
+    #[arg(long, num_args = 0..=1, default_missing_value = "true", env = GPU_ASSISTED_VALIDATION_ENV)]
+    gpu_assisted_validation: Option<bool>,
+    /// 올바르지만 느린 API 사용을 경고하는 best practices 검사를 사용할지 여부
+    #[arg(long, num_args = 0..=1, default_missing_value = "true", env = BEST_PRACTICES_ENV)]
+    best_practices: Option<bool>,
+    /// 빠진 barrier를 찾는 synchronization validation을 사용할지 여부
+    #[arg(long, num_args = 0..=1, default_missing_value = "true", env = SYNC_VALIDATION_ENV)]
+    sync_validation: Option<bool>,
+    /// 이 id의 debug 메세지만 출력 (여러 번 지정할 수 있음)
+    #[arg(long = "message-filter")]
+    message_filter: Vec<String>,
+    /// 이 id의 debug 메세지를 출력하지 않음 (여러 번 지정할 수 있음)
+    #[arg(long = "mute-message")]
+    mute_messages: Vec<String>,
+    /// ERROR 심각도의 validation 메세지를 받으면 프로그램을 중단할지 여부
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    abort_on_validation_error: Option<bool>,
+    /// `renderdoc` feature로 빌드했을 때 첫 frame을 RenderDoc으로 capture할지 여부
+    #[arg(long, num_args = 0..=1, default_missing_value = "true", env = CAPTURE_FIRST_FRAME_ENV)]
+    capture_first_frame: Option<bool>,
+    /// model과 texture를 읽어오는 디렉토리
+    #[arg(long)]
+    resources: Option<PathBuf>,
+}
+
+/// TOML 설정 파일의 내용  
+/// 모든 항목은 생략할 수 있고, 이름은 command line flag와 같음 (여러 번 지정하는 flag는 배열로 지정함)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct ConfigFile {
+    width: Option<u32>,
+    height: Option<u32>,
+    fullscreen: Option<bool>,
+    present_mode: Option<String>,
+    vsync: Option<bool>,
+    sharing_mode: Option<String>,
+    msaa: Option<u32>,
+    gpu: Option<usize>,
+    software: Option<bool>,
+    validation: Option<bool>,
+    gpu_assisted_validation: Option<bool>,
+    best_practices: Option<bool>,
+    sync_validation: Option<bool>,
+    message_filter: Vec<String>,
+    #[serde(rename = "mute-message")]
+    mute_messages: Vec<String>,
+    abort_on_validation_error: Option<bool>,
+    capture_first_frame: Option<bool>,
+    resources: Option<PathBuf>,
+}
+
+impl ConfigFile {
+    /// `path`의 TOML 파일을 읽어옴
+    fn read(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read config file `{}`: {}", path.display(), e))?;
+
+        toml::from_str(&text)
+            .map_err(|e| anyhow!("Invalid config file `{}`: {}", path.display(), e))
+    }
+}
+
+/// command line 인자와 설정 파일을 합쳐서 검증한 설정
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// window의 너비
+    pub width: u32,
+    /// window의 높이
+    pub height: u32,
+    /// borderless fullscreen으로 시작할지 여부
+    pub fullscreen: bool,
+    /// 사용할 present mode  
+    /// 지정하지 않으면 chapter가 정한 present mode를 사용함
+    pub present_mode: Option<vk::PresentModeKHR>,
+    /// graphics와 present queue family가 다를 때 사용할 swapchain image의 sharing mode  
+    /// 지정하지 않으면 chapter가 정한 sharing mode를 사용함
+    pub sharing_mode: Option<vk::SharingMode>,
+    /// 사용할 MSAA sample 수  
+    /// 지정하지 않으면 device가 지원하는 최대 sample 수를 사용함
+    pub msaa_samples: Option<vk::SampleCountFlags>,
+    /// 사용할 GPU의 index  
+    /// 지정하지 않으면 점수가 가장 높은 GPU를 사용함
+    pub gpu_index: Option<usize>,
+    /// hardware GPU 대신 CPU 구현만 사용할지 여부  
+    /// 꺼져 있어도 적합한 hardware GPU가 없으면 CPU 구현을 사용함
+    pub software: bool,
+    /// validation layer를 사용할지 여부  
+    /// 기본값은 `VALIDATION_ENABLED`임
+    pub validation: bool,
+    /// GPU-assisted validation을 사용할지 여부  
+    /// `validation`이 꺼져 있으면 무시됨
+    pub gpu_assisted_validation: bool,
+    /// best practices 검사를 사용할지 여부  
+    /// `validation`이 꺼져 있으면 무시됨
+    pub best_practices: bool,
+    /// synchronization validation을 사용할지 여부  
+    /// `validation`이 꺼져 있으면 무시됨
+    pub sync_validation: bool,
+    /// 비어있지 않으면 이 id의 debug 메세지만 출력함  
+    /// id는 `VUID-...`같은 message id 이름이나 `0x`로 시작하는 message id 번호임
+    pub message_filter: Vec<String>,
+    /// 출력하지 않을 debug 메세지의 id  
+    /// 설정 파일과 command line에서 지정한 id를 모두 사용함
+    pub mute_messages: Vec<String>,
+    /// ERROR 심각도의 validation 메세지를 받으면 프로그램을 중단할지 여부
+    pub abort_on_validation_error: bool,
+    /// 첫 frame을 RenderDoc으로 capture할지 여부
+    pub capture_first_frame: bool,
+    /// model과 texture를 읽어오는 디렉토리
+    pub resource_dir: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_WINDOW_SIZE.0,
+            height: DEFAULT_WINDOW_SIZE.1,
+            fullscreen: false,
+            present_mode: None,
+            sharing_mode: None,
+            msaa_samples: None,
+            gpu_index: None,
+            software: false,
+            validation: VALIDATION_ENABLED,
+            gpu_assisted_validation: false,
+            best_practices: false,
+            sync_validation: false,
+            message_filter: vec![],
+            mute_messages: vec![],
+            abort_on_validation_error: false,
+            capture_first_frame: false,
+            resource_dir: PathBuf::from(DEFAULT_RESOURCE_DIR),
+        }
+    }
+}
+
+impl Config {
+    /// command line 인자와 설정 파일을 읽어서 설정을 생성  
+    /// `--config`를 지정하지 않았으면 `DEFAULT_CONFIG_PATH`가 있을 때만 읽음
+    pub fn load() -> Result<Self> {
+        let args = parse_args();
+
+        let file = match &args.config {
+            Some(path) => ConfigFile::read(path)?,
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => {
+                ConfigFile::read(Path::new(DEFAULT_CONFIG_PATH))?
+            }
+            None => ConfigFile::default(),
+        };
+
+        Self::merge(args, file)
+    }
+
+    /// command line 인자가 설정 파일보다 우선하도록 합침
+    fn merge(args: Args, file: ConfigFile) -> Result<Self> {
+        let default = Self::default();
+
+        // present mode를 직접 지정하면 vsync는 무시함
+        let present_mode = match args.present_mode.or(file.present_mode) {
+            Some(name) => Some(parse_present_mode(&name)?),
+            None => args.vsync.or(file.vsync).map(|vsync| {
+                if vsync {
+                    vk::PresentModeKHR::FIFO
+                } else {
+                    vk::PresentModeKHR::IMMEDIATE
+                }
+            }),
+        };
+
+        let sharing_mode = match args.sharing_mode.or(file.sharing_mode) {
+            Some(name) => Some(parse_sharing_mode(&name)?),
+            None => None,
+        };
+
+        let msaa_samples = match args.msaa.or(file.msaa) {
+            Some(samples) => Some(parse_msaa_samples(samples)?),
+            None => None,
+        };
+
+        let width = args.width.or(file.width).unwrap_or(default.width);
+        let height = args.height.or(file.height).unwrap_or(default.height);
+        if width == 0 || height == 0 {
+            return Err(anyhow!("Invalid window size ({}x{}).", width, height));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            fullscreen: args
+                .fullscreen
+                .or(file.fullscreen)
+                .unwrap_or(default.fullscreen),
+            present_mode,
+            sharing_mode,
+            msaa_samples,
+            gpu_index: args.gpu.or(file.gpu),
+            software: args.software.or(file.software).unwrap_or(default.software),
+            validation: args
+                .validation
+                .or(file.validation)
+                .unwrap_or(default.validation),
+            gpu_assisted_validation: args
+                .gpu_assisted_validation
+                .or(file.gpu_assisted_validation)
+                .unwrap_or(default.gpu_assisted_validation),
+            best_practices: args
+                .best_practices
+                .or(file.best_practices)
+                .unwrap_or(default.best_practices),
+            sync_validation: args
+                .sync_validation
+                .or(file.sync_validation)
+                .unwrap_or(default.sync_validation),
+            // command line에서 지정하면 설정 파일의 filter를 대체함
+            message_filter: if args.message_filter.is_empty() {
+                file.message_filter
+            } else {
+                args.message_filter
+            },
+            mute_messages: file
+                .mute_messages
+                .into_iter()
+                .chain(args.mute_messages)
+                .collect(),
+            abort_on_validation_error: args
+                .abort_on_validation_error
+                .or(file.abort_on_validation_error)
+                .unwrap_or(default.abort_on_validation_error),
+            capture_first_frame: args
+                .capture_first_frame
+                .or(file.capture_first_frame)
+                .unwrap_or(default.capture_first_frame),
+            resource_dir: args
+                .resources
+                .or(file.resources)
+                .unwrap_or(default.resource_dir),
+        })
+    }
+
+    /// resource 디렉토리 안의 `name` 파일의 경로
+    pub fn resource(&self, name: impl AsRef<Path>) -> PathBuf {
+        self.resource_dir.join(name)
+    }
+
+    /// 설정한 MSAA sample 수를 device가 지원하는 최대 sample 수 `max`로 제한해서 반환  
+    /// 설정하지 않았으면 `max`를 그대로 반환함
+    pub fn msaa_samples(&self, max: vk::SampleCountFlags) -> vk::SampleCountFlags {
+        match self.msaa_samples {
+            Some(samples) if samples.bits() > max.bits() => {
+                info!(
+                    "MSAA {:?} is not supported, falling back to {:?}.",
+                    samples, max
+                );
+                max
+            }
+            Some(samples) => samples,
+            None => max,
+        }
+    }
+}
+
+/// 설정을 반환  
+/// 처음 호출할 때 command line 인자와 설정 파일을 읽고, 이후에는 같은 설정을 반환함
+pub fn get() -> Result<&'static Config> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+
+    let config = Config::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// command line 인자 중에서 `Args`가 정의한 flag만 골라서 parse  
+/// `--help`나 잘못된 값이 주어지면 clap이 메세지를 출력하고 프로그램을 종료함
+fn parse_args() -> Args {
+    Args::parse_from(known_args(std::env::args()))
+}
+
+/// `args` 중에서 `Args`가 정의한 flag와 그 값만 반환  
+/// chapter가 직접 읽는 flag(`--hdr`, `--transparent` 등)는 clap이 모르는 인자이므로 넘기지 않음
+fn known_args(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut command = Args::command();
+    command.build();
+
+    let mut args = args.into_iter().peekable();
+    let mut known = args.next().into_iter().collect::<Vec<_>>();
+    while let Some(arg) = args.next() {
+        let name = arg.split_once('=').map_or(arg.as_str(), |(n, _)| n);
+        let defined = command
+            .get_arguments()
+            .find(|a| match name.strip_prefix("--") {
+                Some(long) => a.get_long() == Some(long),
+                None => name
+                    .strip_prefix('-')
+                    .and_then(|s| s.parse::<char>().ok())
+                    .is_some_and(|short| a.get_short() == Some(short)),
+            });
+
+        let Some(defined) = defined else {
+            continue;
+        };
+
+        // `flag=<value>`가 아니면 다음 인자가 값일 수 있음
+        // 값이 꼭 필요한 flag면 다음 인자를 항상 값으로 사용하고,
+        // `--fullscreen true`처럼 값을 생략할 수 있는 flag면 다음 인자가 다른 flag가 아닐 때만 값으로 사용함
+        let num_args = defined.get_num_args().filter(|_| !arg.contains('='));
+        known.push(arg);
+        match num_args {
+            Some(n) if n.min_values() > 0 => known.extend(args.next()),
+            Some(n) if n.max_values() > 0 => known.extend(args.next_if(|a| !a.starts_with('-'))),
+            _ => {}
+        }
+    }
+
+    known
+}
+
+/// `PRESENT_MODES`의 이름을 present mode로 변환
+fn parse_present_mode(name: &str) -> Result<vk::PresentModeKHR> {
+    PRESENT_MODES
+        .iter()
+        .find(|(_, n)| n.eq_ignore_ascii_case(name.trim()))
+        .map(|(m, _)| *m)
+        .ok_or_else(|| anyhow!("Invalid present mode (`{}`).", name))
+}
+
+/// `SHARING_MODES`의 이름을 sharing mode로 변환
+fn parse_sharing_mode(name: &str) -> Result<vk::SharingMode> {
+    SHARING_MODES
+        .iter()
+        .find(|(_, n)| n.eq_ignore_ascii_case(name.trim()))
+        .map(|(m, _)| *m)
+        .ok_or_else(|| anyhow!("Invalid sharing mode (`{}`).", name))
+}
+
+/// sample 수를 `vk::SampleCountFlags`로 변환
+fn parse_msaa_samples(samples: u32) -> Result<vk::SampleCountFlags> {
+    MSAA_SAMPLES
+        .iter()
+        .find(|(n, _)| *n == samples)
+        .map(|(_, s)| *s)
+        .ok_or_else(|| anyhow!("Invalid MSAA sample count ({}).", samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        let args = std::iter::once("vulkan_tutorial")
+            .chain(args.iter().copied())
+            .map(String::from);
+        Args::parse_from(known_args(args))
+    }
+
+    fn file(text: &str) -> ConfigFile {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn args_override_file() {
+        let config = Config::merge(
+            args(&["--width", "800", "--present-mode=fifo"]),
+            file("width = 640\nheight = 480\npresent-mode = \"mailbox\""),
+        )
+        .unwrap();
+
+        assert_eq!(config.width, 800);
+        assert_eq!(config.height, 480);
+        assert_eq!(config.present_mode, Some(vk::PresentModeKHR::FIFO));
+    }
+
+    #[test]
+    fn file_overrides_default() {
+        let config = Config::merge(
+            args(&[]),
+            file("fullscreen = true\nmsaa = 4\nresources = \"assets\""),
+        )
+        .unwrap();
+
+        assert!(config.fullscreen);
+        assert_eq!(config.width, DEFAULT_WINDOW_SIZE.0);
+        assert_eq!(config.msaa_samples, Some(vk::SampleCountFlags::_4));
+        assert_eq!(config.resource("font.ttf"), Path::new("assets/font.ttf"));
+    }
+
+    #[test]
+    fn repeated_flags_in_file() {
+        let config = Config::merge(
+            args(&["--mute-message", "0x1", "--message-filter=VUID-a"]),
+            file("mute-message = [\"0x2\"]\nmessage-filter = [\"VUID-b\"]"),
+        )
+        .unwrap();
+
+        assert_eq!(config.mute_messages, ["0x2", "0x1"]);
+        assert_eq!(config.message_filter, ["VUID-a"]);
+    }
+
+    #[test]
+    fn unknown_field_is_rejected() {
+        assert!(toml::from_str::<ConfigFile>("widht = 800").is_err());
+    }
+
+    #[test]
+    fn optional_value_after_space() {
+        let config = Config::merge(
+            args(&["--fullscreen", "true", "--validation", "false"]),
+            file("fullscreen = false\nvalidation = true"),
+        )
+        .unwrap();
+
+        assert!(config.fullscreen);
+        assert!(!config.validation);
+    }
+
+    #[test]
+    fn optional_value_omitted() {
+        let config = Config::merge(
+            args(&["--fullscreen", "--hdr", "--validation=false"]),
+            ConfigFile::default(),
+        )
+        .unwrap();
+
+        assert!(config.fullscreen);
+        assert!(!config.validation);
+    }
+
+    #[test]
+    fn invalid_value_is_rejected() {
+        assert!(Config::merge(args(&["--present-mode", "vsync"]), ConfigFile::default()).is_err());
+        assert!(Config::merge(args(&["--msaa", "3"]), ConfigFile::default()).is_err());
+    }
+}
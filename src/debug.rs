@@ -0,0 +1,73 @@
+//! `VK_EXT_debug_utils`를 사용해서 오브젝트에 이름을 붙이고 command buffer에 label을 기록  
+//! validation layer 메세지나 RenderDoc 같은 도구에서 핸들 대신 이름으로 오브젝트를 구분할 수 있음  
+//! extension은 설정의 `validation`이 켜진 경우에만 활성화되므로 그렇지 않으면 아무것도 하지 않음
+
+use anyhow::Result;
+use vulkanalia::prelude::v1_0::*;
+
+use vulkanalia::vk::ExtDebugUtilsExtension;
+
+use std::ffi::CString;
+
+use crate::config;
+use crate::instance::VALIDATION_ENABLED;
+
+/// `handle`에 `name`이라는 이름을 붙임
+pub unsafe fn set_object_name<H: vk::Handle<Repr = u64>>(
+    instance: &Instance,
+    device: &Device,
+    handle: H,
+    name: &str,
+) -> Result<()> {
+    if !debug_utils_enabled() {
+        return Ok(());
+    }
+
+    // Vulkan은 NULL로 끝나는 문자열을 사용함
+    let name = CString::new(name)?;
+    let info = vk::DebugUtilsObjectNameInfoEXT::builder()
+        .object_type(H::TYPE)
+        .object_handle(handle.as_raw())
+        .object_name(name.as_bytes_with_nul());
+
+    instance.set_debug_utils_object_name_ext(device.handle(), &info)?;
+
+    Ok(())
+}
+
+/// command buffer에 `name`이라는 label 구간을 시작  
+/// `cmd_end_label`과 짝을 이루어야 하며 구간은 중첩될 수 있음
+pub unsafe fn cmd_begin_label(
+    instance: &Instance,
+    command_buffer: vk::CommandBuffer,
+    name: &str,
+    color: [f32; 4],
+) -> Result<()> {
+    if !debug_utils_enabled() {
+        return Ok(());
+    }
+
+    let name = CString::new(name)?;
+    let label = vk::DebugUtilsLabelEXT::builder()
+        .label_name(name.as_bytes_with_nul())
+        .color(color);
+
+    instance.cmd_begin_debug_utils_label_ext(command_buffer, &label);
+
+    Ok(())
+}
+
+/// `cmd_begin_label`로 시작한 가장 최근의 label 구간을 끝냄
+pub unsafe fn cmd_end_label(instance: &Instance, command_buffer: vk::CommandBuffer) {
+    if !debug_utils_enabled() {
+        return;
+    }
+
+    instance.cmd_end_debug_utils_label_ext(command_buffer);
+}
+
+/// `create_instance`가 `VK_EXT_debug_utils`를 활성화했는지 여부  
+/// instance를 만들 때 이미 설정을 읽었으므로 실패하지 않지만, 실패하면 기본값을 사용함
+fn debug_utils_enabled() -> bool {
+    config::get().map_or(VALIDATION_ENABLED, |c| c.validation)
+}
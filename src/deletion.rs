@@ -0,0 +1,142 @@
+//! frame의 fence가 signal된 뒤에 resource를 파괴하는 deletion queue  
+//! swapchain을 다시 만들거나 임시 buffer를 사용할 때마다 `device_wait_idle`로 모든 작업을 기다리는 대신  
+//! resource를 마지막으로 사용한 frame에 등록해두고, 같은 frame의 fence를 다시 기다린 시점에 파괴함
+
+use vulkanalia::prelude::v1_0::*;
+
+use vulkanalia::vk::KhrSwapchainExtension;
+
+/// deletion queue에 등록할 수 있는 Vulkan 오브젝트
+#[derive(Copy, Clone, Debug)]
+pub enum Resource {
+    Buffer(vk::Buffer),
+    DeviceMemory(vk::DeviceMemory),
+    Image(vk::Image),
+    ImageView(vk::ImageView),
+    Sampler(vk::Sampler),
+    Framebuffer(vk::Framebuffer),
+    RenderPass(vk::RenderPass),
+    PipelineLayout(vk::PipelineLayout),
+    Pipeline(vk::Pipeline),
+    /// pool에서 할당된 descriptor set은 pool이 파괴될 때 함께 해제됨
+    DescriptorPool(vk::DescriptorPool),
+    DescriptorSetLayout(vk::DescriptorSetLayout),
+    /// pool에서 할당된 command buffer는 pool이 파괴될 때 함께 해제됨
+    CommandPool(vk::CommandPool),
+    Semaphore(vk::Semaphore),
+    /// 새 swapchain의 `old_swapchain`으로 전달해서 retire된 swapchain
+    Swapchain(vk::SwapchainKHR),
+}
+
+impl Resource {
+    /// 오브젝트를 파괴
+    unsafe fn destroy(self, device: &Device) {
+        match self {
+            Self::Buffer(h) => device.destroy_buffer(h, None),
+            Self::DeviceMemory(h) => device.free_memory(h, None),
+            Self::Image(h) => device.destroy_image(h, None),
+            Self::ImageView(h) => device.destroy_image_view(h, None),
+            Self::Sampler(h) => device.destroy_sampler(h, None),
+            Self::Framebuffer(h) => device.destroy_framebuffer(h, None),
+            Self::RenderPass(h) => device.destroy_render_pass(h, None),
+            Self::PipelineLayout(h) => device.destroy_pipeline_layout(h, None),
+            Self::Pipeline(h) => device.destroy_pipeline(h, None),
+            Self::DescriptorPool(h) => device.destroy_descriptor_pool(h, None),
+            Self::DescriptorSetLayout(h) => device.destroy_descriptor_set_layout(h, None),
+            Self::CommandPool(h) => device.destroy_command_pool(h, None),
+            Self::Semaphore(h) => device.destroy_semaphore(h, None),
+            Self::Swapchain(h) => device.destroy_swapchain_khr(h, None),
+        }
+    }
+}
+
+/// `$handle`을 `Resource::$variant`로 변환하는 `From`을 구현
+macro_rules! impl_from_handle {
+    ($($variant:ident($handle:ty)),* $(,)?) => {
+        $(
+            impl From<$handle> for Resource {
+                fn from(handle: $handle) -> Self {
+                    Self::$variant(handle)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_handle!(
+    Buffer(vk::Buffer),
+    DeviceMemory(vk::DeviceMemory),
+    Image(vk::Image),
+    ImageView(vk::ImageView),
+    Sampler(vk::Sampler),
+    Framebuffer(vk::Framebuffer),
+    RenderPass(vk::RenderPass),
+    PipelineLayout(vk::PipelineLayout),
+    Pipeline(vk::Pipeline),
+    DescriptorPool(vk::DescriptorPool),
+    DescriptorSetLayout(vk::DescriptorSetLayout),
+    CommandPool(vk::CommandPool),
+    Semaphore(vk::Semaphore),
+    Swapchain(vk::SwapchainKHR),
+);
+
+/// frame in flight마다 파괴할 resource를 모아두는 queue  
+/// resource는 등록한 frame이 제출한 command가 마지막으로 사용한 것이어야 하고,  
+/// 같은 frame의 fence를 기다린 뒤에 `flush`를 호출하면 등록한 순서대로 파괴됨
+#[derive(Clone, Debug, Default)]
+pub struct DeletionQueue {
+    frames: Vec<Vec<Resource>>,
+}
+
+impl DeletionQueue {
+    /// `frames`개의 frame in flight를 위한 queue를 생성
+    pub fn new(frames: usize) -> Self {
+        Self {
+            frames: vec![vec![]; frames],
+        }
+    }
+
+    /// `frame`의 fence가 signal된 뒤에 파괴할 resource를 등록  
+    /// null 핸들은 파괴할 필요가 없으므로 무시함
+    pub fn push<H: vk::Handle + Into<Resource>>(&mut self, frame: usize, handle: H) {
+        if !handle.is_null() {
+            let index = frame % self.frames.len();
+            self.frames[index].push(handle.into());
+        }
+    }
+
+    /// `handles`를 모두 `frame`에 등록
+    pub fn extend<H: vk::Handle + Into<Resource>>(
+        &mut self,
+        frame: usize,
+        handles: impl IntoIterator<Item = H>,
+    ) {
+        handles.into_iter().for_each(|h| self.push(frame, h));
+    }
+
+    /// `frame`에 등록된 resource를 등록한 순서대로 파괴  
+    /// `frame`의 fence를 기다린 뒤에 호출해야 함
+    pub unsafe fn flush(&mut self, device: &Device, frame: usize) {
+        let index = frame % self.frames.len();
+        self.frames[index].drain(..).for_each(|r| r.destroy(device));
+    }
+
+    /// 모든 frame에 등록된 resource를 파괴  
+    /// device가 idle 상태일 때만 호출해야 함
+    pub unsafe fn flush_all(&mut self, device: &Device) {
+        self.frames
+            .iter_mut()
+            .flat_map(|f| f.drain(..))
+            .for_each(|r| r.destroy(device));
+    }
+
+    /// 아직 파괴되지 않은 resource의 수
+    pub fn len(&self) -> usize {
+        self.frames.iter().map(|f| f.len()).sum()
+    }
+
+    /// 파괴를 기다리는 resource가 없는지 여부
+    pub fn is_empty(&self) -> bool {
+        self.frames.iter().all(|f| f.is_empty())
+    }
+}
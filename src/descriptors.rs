@@ -0,0 +1,194 @@
+//! descriptor set을 할당하는 allocator와 descriptor set layout을 재사용하는 cache  
+//! swapchain image의 개수에 맞춰 pool의 크기를 미리 계산하는 대신 pool이 부족해지면 새 pool을 만들어서 할당함
+
+use anyhow::Result;
+use vulkanalia::prelude::v1_0::*;
+
+use std::collections::HashMap;
+
+/// 처음 만드는 pool에서 할당할 수 있는 descriptor set의 수
+const INITIAL_SETS_PER_POOL: u32 = 16;
+
+/// pool 하나에서 할당할 수 있는 descriptor set의 최대 수
+const MAX_SETS_PER_POOL: u32 = 4096;
+
+/// descriptor pool을 필요할 때 만들어서 descriptor set을 할당하는 allocator  
+/// pool이 가득 차면 이전보다 큰 pool을 새로 만들고, 할당한 set은 `reset`이나 `destroy`로 한꺼번에 해제함
+#[derive(Clone, Debug, Default)]
+pub struct DescriptorAllocator {
+    // descriptor set 하나에 필요한 descriptor type별 개수의 비율
+    ratios: Vec<(vk::DescriptorType, f32)>,
+    // 다음에 만들 pool에서 할당할 수 있는 descriptor set의 수
+    sets_per_pool: u32,
+    // 가득 차서 더 이상 할당할 수 없는 pool
+    full_pools: Vec<vk::DescriptorPool>,
+    // 할당할 수 있는 공간이 남아있는 pool
+    ready_pools: Vec<vk::DescriptorPool>,
+}
+
+impl DescriptorAllocator {
+    /// `ratios`는 descriptor set 하나에 평균적으로 필요한 descriptor type별 개수  
+    /// pool을 만들 때 이 비율에 pool의 set 수를 곱한 만큼 descriptor를 준비함
+    pub fn new(ratios: &[(vk::DescriptorType, f32)]) -> Self {
+        Self {
+            ratios: ratios.to_vec(),
+            sets_per_pool: INITIAL_SETS_PER_POOL,
+            full_pools: vec![],
+            ready_pools: vec![],
+        }
+    }
+
+    /// `layout`으로 descriptor set을 할당  
+    /// 현재 pool에 공간이 부족하면 새 pool을 만들어서 다시 시도함
+    pub unsafe fn allocate(
+        &mut self,
+        device: &Device,
+        layout: vk::DescriptorSetLayout,
+    ) -> Result<vk::DescriptorSet> {
+        let mut pool = self.get_pool(device)?;
+
+        let layouts = &[layout];
+        let info = vk::DescriptorSetAllocateInfo::builder()
+            .descriptor_pool(pool)
+            .set_layouts(layouts);
+
+        let result = match device.allocate_descriptor_sets(&info) {
+            // pool에 남은 descriptor나 set이 부족한 경우 새 pool에서 한 번 더 시도
+            Err(vk::ErrorCode::OUT_OF_POOL_MEMORY | vk::ErrorCode::FRAGMENTED_POOL) => {
+                self.full_pools.push(pool);
+                pool = self.get_pool(device)?;
+
+                let info = vk::DescriptorSetAllocateInfo::builder()
+                    .descriptor_pool(pool)
+                    .set_layouts(layouts);
+
+                device.allocate_descriptor_sets(&info)
+            }
+            result => result,
+        };
+
+        // 할당에 실패해도 pool을 잃어버리지 않도록 결과를 확인하기 전에 돌려놓음
+        self.ready_pools.push(pool);
+
+        Ok(result?[0])
+    }
+
+    /// 모든 pool을 reset해서 할당한 descriptor set을 한꺼번에 해제  
+    /// pool은 파괴하지 않고 다음 할당에 다시 사용함
+    pub unsafe fn reset(&mut self, device: &Device) -> Result<()> {
+        self.ready_pools.append(&mut self.full_pools);
+        for pool in &self.ready_pools {
+            device.reset_descriptor_pool(*pool, vk::DescriptorPoolResetFlags::empty())?;
+        }
+
+        Ok(())
+    }
+
+    /// 모든 pool을 파괴  
+    /// pool에서 할당한 descriptor set도 함께 해제됨
+    pub unsafe fn destroy(&mut self, device: &Device) {
+        self.ready_pools
+            .drain(..)
+            .chain(self.full_pools.drain(..))
+            .for_each(|p| device.destroy_descriptor_pool(p, None));
+    }
+
+    /// 지금까지 만든 pool의 수
+    pub fn pool_count(&self) -> usize {
+        self.ready_pools.len() + self.full_pools.len()
+    }
+
+    /// 공간이 남아있는 pool을 꺼내고, 없으면 새 pool을 생성
+    unsafe fn get_pool(&mut self, device: &Device) -> Result<vk::DescriptorPool> {
+        if let Some(pool) = self.ready_pools.pop() {
+            return Ok(pool);
+        }
+
+        let pool = self.create_pool(device, self.sets_per_pool)?;
+
+        // pool이 자주 부족해지지 않도록 다음 pool은 더 크게 만듦
+        self.sets_per_pool = (self.sets_per_pool * 2).min(MAX_SETS_PER_POOL);
+
+        Ok(pool)
+    }
+
+    unsafe fn create_pool(&self, device: &Device, max_sets: u32) -> Result<vk::DescriptorPool> {
+        let pool_sizes = self
+            .ratios
+            .iter()
+            .map(|(type_, ratio)| {
+                vk::DescriptorPoolSize::builder()
+                    .type_(*type_)
+                    .descriptor_count(((ratio * max_sets as f32).ceil() as u32).max(1))
+                    .build()
+            })
+            .collect::<Vec<_>>();
+
+        let info = vk::DescriptorPoolCreateInfo::builder()
+            .pool_sizes(&pool_sizes)
+            .max_sets(max_sets);
+
+        Ok(device.create_descriptor_pool(&info, None)?)
+    }
+}
+
+/// `DescriptorLayoutCache`에서 layout을 찾기 위한 key  
+/// binding 번호 순서로 정렬한 (binding, descriptor type, descriptor count, shader stage)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct LayoutKey(Vec<(u32, i32, u32, u32)>);
+
+impl LayoutKey {
+    fn new(bindings: &[vk::DescriptorSetLayoutBinding]) -> Self {
+        let mut key = bindings
+            .iter()
+            .map(|b| {
+                (
+                    b.binding,
+                    b.descriptor_type.as_raw(),
+                    b.descriptor_count,
+                    b.stage_flags.bits(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // binding을 나열한 순서가 달라도 같은 layout이므로 정렬해서 비교함
+        key.sort_unstable();
+
+        Self(key)
+    }
+}
+
+/// 같은 binding으로 만든 descriptor set layout을 재사용하는 cache  
+/// immutable sampler는 key에 포함되지 않으므로 immutable sampler를 사용하는 layout은 cache하면 안 됨
+#[derive(Clone, Debug, Default)]
+pub struct DescriptorLayoutCache {
+    layouts: HashMap<LayoutKey, vk::DescriptorSetLayout>,
+}
+
+impl DescriptorLayoutCache {
+    /// `bindings`로 만든 layout이 이미 있으면 반환하고, 없으면 새로 생성  
+    /// 반환한 layout은 cache가 소유하므로 직접 파괴하면 안 됨
+    pub unsafe fn create_layout(
+        &mut self,
+        device: &Device,
+        bindings: &[vk::DescriptorSetLayoutBinding],
+    ) -> Result<vk::DescriptorSetLayout> {
+        let key = LayoutKey::new(bindings);
+        if let Some(layout) = self.layouts.get(&key) {
+            return Ok(*layout);
+        }
+
+        let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(bindings);
+        let layout = device.create_descriptor_set_layout(&info, None)?;
+        self.layouts.insert(key, layout);
+
+        Ok(layout)
+    }
+
+    /// cache가 가지고 있는 모든 layout을 파괴
+    pub unsafe fn destroy(&mut self, device: &Device) {
+        self.layouts
+            .drain()
+            .for_each(|(_, l)| device.destroy_descriptor_set_layout(l, None));
+    }
+}
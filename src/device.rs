@@ -0,0 +1,933 @@
+//! physical device 선택과 logical device 생성
+
+use anyhow::{anyhow, Result};
+use log::*;
+use thiserror::Error;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::Version;
+
+use vulkanalia::vk::InstanceV1_1;
+use vulkanalia::vk::KhrGetPhysicalDeviceProperties2Extension;
+use vulkanalia::vk::KhrSurfaceExtension;
+
+use std::collections::HashSet;
+use std::os::raw::c_char;
+
+use crate::config;
+use crate::instance::{PORTABILITY_MACOS_VERSION, VALIDATION_LAYER};
+
+pub const DEVICE_EXTENSIONS: &[vk::ExtensionName] = &[vk::KHR_SWAPCHAIN_EXTENSION.name];
+
+/// Vulkan 1.3 baseline mode에서 instance와 device에 요구하는 버전
+pub const VULKAN_1_3_VERSION: Version = Version::new(1, 3, 0);
+
+#[derive(Debug, Error)]
+#[error("Missing {0}.")]
+pub struct SuitabilityError(pub &'static str);
+
+#[derive(Copy, Clone, Debug)]
+pub struct QueueFamilyIndices {
+    pub graphics: u32,
+    // graphics queue family와 겹치지 않을 수 있으므로 present queue family를 따로 저장
+    pub present: u32,
+    // graphics를 지원하지 않고 transfer만 전담하는 queue family
+    // 보통 DMA engine에 대응하므로 rendering과 동시에 복사를 실행할 수 있음
+    pub transfer: Option<u32>,
+}
+
+impl QueueFamilyIndices {
+    pub unsafe fn get(
+        instance: &Instance,
+        surface: vk::SurfaceKHR,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<Self> {
+        // 장치의 queue family 속성을 가져옴
+        let properties = instance.get_physical_device_queue_family_properties(physical_device);
+
+        let graphics = properties
+            .iter()
+            .position(|p| p.queue_flags.contains(vk::QueueFlags::GRAPHICS))
+            .map(|i| i as u32);
+
+        let mut present = None;
+        for (index, properties) in properties.iter().enumerate() {
+            if instance.get_physical_device_surface_support_khr(
+                physical_device,
+                index as u32,
+                surface,
+            )? {
+                present = Some(index as u32);
+                break;
+            }
+        }
+        // compute도 지원하지 않는 family가 transfer 전용일 가능성이 가장 높으므로 우선 사용
+        let transfer_only = |flags: vk::QueueFlags| {
+            flags.contains(vk::QueueFlags::TRANSFER) && !flags.contains(vk::QueueFlags::GRAPHICS)
+        };
+        let transfer = properties
+            .iter()
+            .position(|p| {
+                transfer_only(p.queue_flags) && !p.queue_flags.contains(vk::QueueFlags::COMPUTE)
+            })
+            .or_else(|| properties.iter().position(|p| transfer_only(p.queue_flags)))
+            .map(|i| i as u32);
+
+        if let (Some(graphics), Some(present)) = (graphics, present) {
+            Ok(Self {
+                graphics,
+                present,
+                transfer,
+            })
+        } else {
+            Err(anyhow!(SuitabilityError("required queue families")))
+        }
+    }
+}
+
+/// swapchain이 window surface와 호환되는지 확인하기 위해 사용할 프로퍼티들을 담는 구조체
+#[derive(Clone, Debug)]
+pub struct SwapchainSupport {
+    pub capabilities: vk::SurfaceCapabilitiesKHR,
+    pub formats: Vec<vk::SurfaceFormatKHR>,
+    pub present_modes: Vec<vk::PresentModeKHR>,
+}
+
+impl SwapchainSupport {
+    pub unsafe fn get(
+        instance: &Instance,
+        surface: vk::SurfaceKHR,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<Self> {
+        Ok(Self {
+            capabilities: instance
+                .get_physical_device_surface_capabilities_khr(physical_device, surface)?,
+            formats: instance.get_physical_device_surface_formats_khr(physical_device, surface)?,
+            present_modes: instance
+                .get_physical_device_surface_present_modes_khr(physical_device, surface)?,
+        })
+    }
+}
+
+/// physical device의 extensions을 검사
+pub unsafe fn check_physical_device_extensions(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+) -> Result<()> {
+    let extensions = instance
+        .enumerate_device_extension_properties(physical_device, None)?
+        .iter()
+        .map(|e| e.extension_name)
+        .collect::<HashSet<_>>();
+    if DEVICE_EXTENSIONS.iter().all(|e| extensions.contains(e)) {
+        Ok(())
+    } else {
+        Err(anyhow!(SuitabilityError("required device extensions")))
+    }
+}
+
+/// physical device가 `extension`을 지원하는지 확인  
+/// 지원하지 않아도 실행할 수 있고, 지원하는 경우에만 활성화하는 extension을 확인할 때 사용함
+pub unsafe fn supports_device_extension(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    extension: vk::ExtensionName,
+) -> Result<bool> {
+    Ok(instance
+        .enumerate_device_extension_properties(physical_device, None)?
+        .iter()
+        .any(|e| e.extension_name == extension))
+}
+
+/// chapter가 `RequestedFeatures`로 요청할 수 있는 `vk::PhysicalDeviceFeatures`의 feature
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Feature {
+    SamplerAnisotropy,
+    GeometryShader,
+    TessellationShader,
+    WideLines,
+    LargePoints,
+    FillModeNonSolid,
+    SampleRateShading,
+    MultiViewport,
+    MultiDrawIndirect,
+    DrawIndirectFirstInstance,
+    TextureCompressionBc,
+    PipelineStatisticsQuery,
+}
+
+impl Feature {
+    /// device가 지원하지 않을 때 `SuitabilityError`에 표시할 이름
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SamplerAnisotropy => "sampler anisotropy feature",
+            Self::GeometryShader => "geometry shader feature",
+            Self::TessellationShader => "tessellation shader feature",
+            Self::WideLines => "wide lines feature",
+            Self::LargePoints => "large points feature",
+            Self::FillModeNonSolid => "non-solid fill mode feature",
+            Self::SampleRateShading => "sample rate shading feature",
+            Self::MultiViewport => "multi viewport feature",
+            Self::MultiDrawIndirect => "multi draw indirect feature",
+            Self::DrawIndirectFirstInstance => "draw indirect first instance feature",
+            Self::TextureCompressionBc => "BC texture compression feature",
+            Self::PipelineStatisticsQuery => "pipeline statistics query feature",
+        }
+    }
+
+    /// `features`에서 이 feature에 해당하는 field
+    fn field(self, features: &mut vk::PhysicalDeviceFeatures) -> &mut vk::Bool32 {
+        match self {
+            Self::SamplerAnisotropy => &mut features.sampler_anisotropy,
+            Self::GeometryShader => &mut features.geometry_shader,
+            Self::TessellationShader => &mut features.tessellation_shader,
+            Self::WideLines => &mut features.wide_lines,
+            Self::LargePoints => &mut features.large_points,
+            Self::FillModeNonSolid => &mut features.fill_mode_non_solid,
+            Self::SampleRateShading => &mut features.sample_rate_shading,
+            Self::MultiViewport => &mut features.multi_viewport,
+            Self::MultiDrawIndirect => &mut features.multi_draw_indirect,
+            Self::DrawIndirectFirstInstance => &mut features.draw_indirect_first_instance,
+            Self::TextureCompressionBc => &mut features.texture_compression_bc,
+            Self::PipelineStatisticsQuery => &mut features.pipeline_statistics_query,
+        }
+    }
+
+    /// `features`에 이 feature가 포함되어 있는지 확인
+    pub fn is_supported(self, features: &vk::PhysicalDeviceFeatures) -> bool {
+        let mut features = *features;
+        *self.field(&mut features) == vk::TRUE
+    }
+}
+
+/// chapter가 logical device를 만들 때 요청하는 feature  
+/// `required`의 feature를 지원하지 않는 device는 사용하지 않고  
+/// `optional`의 feature는 지원하는 경우에만 활성화함
+#[derive(Clone, Debug, Default)]
+pub struct RequestedFeatures {
+    pub required: Vec<Feature>,
+    pub optional: Vec<Feature>,
+}
+
+impl RequestedFeatures {
+    /// 반드시 필요한 feature를 추가
+    pub fn require(mut self, feature: Feature) -> Self {
+        self.required.push(feature);
+        self
+    }
+
+    /// 지원하는 경우에만 활성화할 feature를 추가
+    pub fn request(mut self, feature: Feature) -> Self {
+        self.optional.push(feature);
+        self
+    }
+
+    /// physical device가 지원하는 feature와 비교해서 활성화할 feature를 결정  
+    /// `required`의 feature를 하나라도 지원하지 않으면 `SuitabilityError`를 반환함
+    pub unsafe fn negotiate(
+        &self,
+        instance: &Instance,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<EnabledFeatures> {
+        let supported = instance.get_physical_device_features(physical_device);
+
+        if let Some(missing) = self.required.iter().find(|f| !f.is_supported(&supported)) {
+            return Err(anyhow!(SuitabilityError(missing.name())));
+        }
+
+        let mut enabled = EnabledFeatures::default();
+        let optional = self.optional.iter().filter(|f| f.is_supported(&supported));
+        for feature in self.required.iter().chain(optional) {
+            *feature.field(&mut enabled.features) = vk::TRUE;
+        }
+
+        Ok(enabled)
+    }
+}
+
+/// `RequestedFeatures`와 physical device의 지원 여부를 비교해서 결정한 feature  
+/// `features`를 그대로 `create_logical_device`에 전달하면 되고, optional feature의 활성화 여부는 `is_enabled`로 확인함
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EnabledFeatures {
+    pub features: vk::PhysicalDeviceFeatures,
+}
+
+impl EnabledFeatures {
+    /// `feature`가 활성화되었는지 확인
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        feature.is_supported(&self.features)
+    }
+}
+
+/// physical device를 검사하고 적합한지 확인  
+/// queue family, extension, swapchain 지원 여부와 `requested`의 required feature는 공통으로 검사하고  
+/// chapter마다 다른 그 외의 요구사항은 `check_features`로 검사함  
+/// 적합한 경우 활성화할 required, optional feature를 반환함
+pub unsafe fn check_physical_device(
+    instance: &Instance,
+    surface: vk::SurfaceKHR,
+    physical_device: vk::PhysicalDevice,
+    requested: &RequestedFeatures,
+    check_features: impl Fn(&Instance, vk::PhysicalDevice) -> Result<()>,
+) -> Result<EnabledFeatures> {
+    let enabled = requested.negotiate(instance, physical_device)?;
+    check_features(instance, physical_device)?;
+
+    QueueFamilyIndices::get(instance, surface, physical_device)?;
+    check_physical_device_extensions(instance, physical_device)?;
+
+    let support = SwapchainSupport::get(instance, surface, physical_device)?;
+    if support.formats.is_empty() || support.present_modes.is_empty() {
+        return Err(anyhow!(SuitabilityError("sufficient swapchain support")));
+    }
+
+    Ok(enabled)
+}
+
+/// 다시 생성한 surface를 기존 device로 계속 사용할 수 있는지 확인  
+/// driver가 reset되거나 display 구성이 바뀌면 새 surface의 present 지원 여부와 swapchain support가 달라질 수 있으므로 다시 조회함  
+/// logical device는 `present_queue_family`의 queue로 present하므로 같은 queue family가 새 surface를 지원해야 함
+pub unsafe fn check_surface_support(
+    instance: &Instance,
+    surface: vk::SurfaceKHR,
+    physical_device: vk::PhysicalDevice,
+    present_queue_family: u32,
+) -> Result<SwapchainSupport> {
+    if !instance.get_physical_device_surface_support_khr(
+        physical_device,
+        present_queue_family,
+        surface,
+    )? {
+        return Err(anyhow!(SuitabilityError("present support for new surface")));
+    }
+
+    let support = SwapchainSupport::get(instance, surface, physical_device)?;
+    if support.formats.is_empty() || support.present_modes.is_empty() {
+        return Err(anyhow!(SuitabilityError(
+            "swapchain support for new surface"
+        )));
+    }
+
+    Ok(support)
+}
+
+/// 사용할 GPU를 직접 지정하기 위한 환경 변수  
+/// 값은 `enumerate_physical_devices`가 반환하는 순서의 index이며, 설정 파일의 `gpu`보다 우선함
+pub const GPU_INDEX_ENV: &str = "VK_TUTORIAL_GPU_INDEX";
+
+/// 사용할 GPU를 직접 지정하기 위한 command line flag (`--gpu <index>` 또는 `--gpu=<index>`)
+pub const GPU_INDEX_FLAG: &str = "--gpu";
+
+/// hardware GPU 대신 CPU에서 실행되는 Vulkan 구현을 사용하기 위한 환경 변수 (`--software`와 같음)
+pub const SOFTWARE_RENDERING_ENV: &str = "VK_TUTORIAL_SOFTWARE";
+
+/// physical device가 lavapipe나 SwiftShader처럼 CPU에서 실행되는 software 구현인지 여부
+pub unsafe fn is_software_device(instance: &Instance, physical_device: vk::PhysicalDevice) -> bool {
+    let properties = instance.get_physical_device_properties(physical_device);
+    properties.device_type == vk::PhysicalDeviceType::CPU
+}
+
+/// physical device의 종류가 `software`와 맞는지 확인  
+/// `software`이면 CPU 구현만, 아니면 hardware GPU만 허용함
+pub unsafe fn check_device_type(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    software: bool,
+) -> Result<()> {
+    match (software, is_software_device(instance, physical_device)) {
+        (true, false) => Err(anyhow!(SuitabilityError("software implementation"))),
+        (false, true) => Err(anyhow!(SuitabilityError("hardware implementation"))),
+        _ => Ok(()),
+    }
+}
+
+/// 설치된 모든 ICD(Vulkan driver)가 노출하는 physical device를 log로 출력  
+/// 어떤 driver가 설치되어 있는지 알아야 `--gpu`나 `--software`로 원하는 device를 고를 수 있음
+pub unsafe fn log_physical_devices(instance: &Instance, physical_devices: &[vk::PhysicalDevice]) {
+    for (index, physical_device) in physical_devices.iter().enumerate() {
+        let properties = instance.get_physical_device_properties(*physical_device);
+        info!(
+            "Physical device {}: `{}` ({:?}, Vulkan {}, vendor {:#06x}, driver {:#x})",
+            index,
+            properties.device_name,
+            properties.device_type,
+            Version::from(properties.api_version),
+            properties.vendor_id,
+            properties.driver_version
+        );
+    }
+}
+
+/// physical device의 점수를 계산  
+/// 점수가 높을수록 tutorial을 실행하기에 더 적합한 device임
+pub unsafe fn rate_physical_device(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+) -> u32 {
+    let properties = instance.get_physical_device_properties(physical_device);
+    let features = instance.get_physical_device_features(physical_device);
+
+    // 외장 GPU가 내장 GPU보다 일반적으로 훨씬 성능이 좋음
+    let mut score = match properties.device_type {
+        vk::PhysicalDeviceType::DISCRETE_GPU => 10000,
+        vk::PhysicalDeviceType::INTEGRATED_GPU => 1000,
+        vk::PhysicalDeviceType::VIRTUAL_GPU => 100,
+        vk::PhysicalDeviceType::CPU => 10,
+        _ => 0,
+    };
+
+    // 더 큰 texture를 지원할수록 점수를 높게 줌
+    score += properties.limits.max_image_dimension_2d / 16;
+
+    if features.sampler_anisotropy == vk::TRUE {
+        score += 100;
+    }
+
+    score
+}
+
+/// surface에 그릴 수 있는 physical device 중에서 점수가 가장 높은 device를 찾아서 반환  
+/// `--gpu` flag나 `VK_TUTORIAL_GPU_INDEX` 환경 변수로 GPU를 직접 지정할 수 있음  
+/// 내장 GPU와 외장 GPU를 함께 가진 노트북에서 어떤 GPU를 사용할지 고를 때 유용함
+pub unsafe fn pick_physical_device(
+    instance: &Instance,
+    surface: vk::SurfaceKHR,
+    check_features: impl Fn(&Instance, vk::PhysicalDevice) -> Result<()>,
+) -> Result<vk::PhysicalDevice> {
+    let (physical_device, _) = pick_physical_device_with_features(
+        instance,
+        surface,
+        &RequestedFeatures::default(),
+        check_features,
+    )?;
+
+    Ok(physical_device)
+}
+
+/// `requested`의 required feature를 지원하는 physical device 중에서 점수가 가장 높은 device를 찾아서 반환  
+/// 선택한 device에서 활성화할 required, optional feature를 함께 반환함
+pub unsafe fn pick_physical_device_with_features(
+    instance: &Instance,
+    surface: vk::SurfaceKHR,
+    requested: &RequestedFeatures,
+    check_features: impl Fn(&Instance, vk::PhysicalDevice) -> Result<()>,
+) -> Result<(vk::PhysicalDevice, EnabledFeatures)> {
+    let physical_device = select_physical_device(instance, |physical_device| {
+        check_physical_device(
+            instance,
+            surface,
+            physical_device,
+            requested,
+            &check_features,
+        )
+        .map(|_| ())
+    })?;
+
+    let enabled = requested.negotiate(instance, physical_device)?;
+    for feature in requested
+        .optional
+        .iter()
+        .filter(|f| !enabled.is_enabled(**f))
+    {
+        info!("Optional {} is not supported.", feature.name());
+    }
+
+    Ok((physical_device, enabled))
+}
+
+/// `check`를 통과한 physical device 중에서 점수가 가장 높은 device를 찾아서 반환  
+/// surface 없이 device를 골라야 하는 경우에도 사용할 수 있도록 검사 방법을 인자로 받음  
+/// 적합한 hardware GPU가 없으면 CI 환경처럼 GPU가 없는 경우를 위해 CPU 구현(lavapipe, SwiftShader)을 대신 사용하고,  
+/// `--software`를 지정하면 처음부터 CPU 구현만 사용함
+pub unsafe fn select_physical_device(
+    instance: &Instance,
+    check: impl Fn(vk::PhysicalDevice) -> Result<()>,
+) -> Result<vk::PhysicalDevice> {
+    let physical_devices = instance.enumerate_physical_devices()?;
+    log_physical_devices(instance, &physical_devices);
+
+    let config = config::get()?;
+
+    if let Some(index) = config.gpu_index {
+        let physical_device = *physical_devices.get(index).ok_or_else(|| {
+            anyhow!(
+                "Requested GPU index {} is out of range ({} physical devices).",
+                index,
+                physical_devices.len()
+            )
+        })?;
+        let properties = instance.get_physical_device_properties(physical_device);
+
+        // 직접 지정한 경우에도 tutorial을 실행할 수 없는 device는 사용하지 않음
+        // index로 지정한 device가 우선하므로 device의 종류는 검사하지 않음
+        check(physical_device).map_err(|error| {
+            anyhow!(
+                "Requested physical device (`{}`) is not suitable: {}",
+                properties.device_name,
+                error
+            )
+        })?;
+
+        info!(
+            "Selected requested physical device (`{}`).",
+            properties.device_name
+        );
+        return Ok(physical_device);
+    }
+
+    // hardware GPU를 먼저 찾고, 없을 때만 CPU 구현을 찾음
+    let passes: &[bool] = if config.software {
+        info!("Software rendering requested, only CPU implementations will be used.");
+        &[true]
+    } else {
+        &[false, true]
+    };
+
+    for &software in passes {
+        if software && !config.software {
+            warn!("No suitable hardware device, falling back to software implementation.");
+        }
+
+        let best = select_best_physical_device(instance, &physical_devices, |physical_device| {
+            check_device_type(instance, physical_device, software)?;
+            check(physical_device)
+        });
+
+        if let Some(physical_device) = best {
+            let properties = instance.get_physical_device_properties(physical_device);
+            info!("Selected physical device (`{}`).", properties.device_name);
+            return Ok(physical_device);
+        }
+    }
+
+    if config.software {
+        Err(anyhow!(
+            "Failed to find suitable software implementation (lavapipe, SwiftShader)."
+        ))
+    } else {
+        Err(anyhow!("Failed to find suitable physical device."))
+    }
+}
+
+/// `check`를 통과한 physical device 중에서 점수가 가장 높은 device를 반환
+unsafe fn select_best_physical_device(
+    instance: &Instance,
+    physical_devices: &[vk::PhysicalDevice],
+    check: impl Fn(vk::PhysicalDevice) -> Result<()>,
+) -> Option<vk::PhysicalDevice> {
+    let mut best = None;
+    for (index, physical_device) in physical_devices.iter().copied().enumerate() {
+        let properties = instance.get_physical_device_properties(physical_device);
+
+        if let Err(error) = check(physical_device) {
+            warn!(
+                "Skipping physical device {} (`{}`): {}",
+                index, properties.device_name, error
+            );
+            continue;
+        }
+
+        let score = rate_physical_device(instance, physical_device);
+        info!(
+            "Found physical device {} (`{}`) with score {}.",
+            index, properties.device_name, score
+        );
+
+        // 점수가 같으면 먼저 나열된 device를 사용
+        match best {
+            Some((best_score, _)) if best_score >= score => {}
+            _ => best = Some((score, physical_device)),
+        }
+    }
+
+    best.map(|(_, physical_device)| physical_device)
+}
+
+/// `VK_KHR_portability_subset`을 지원하는 device(macOS의 MoltenVK 등)에서 사용할 수 없을 수도 있는 기능과 limit  
+/// extension을 지원하지 않는 device는 Vulkan의 모든 기능을 지원하므로 모든 값이 `true`인 `Default`와 같음  
+/// chapter는 이 값을 확인하고 지원하지 않는 기법은 건너뛰거나 다른 방법으로 대체해야 함
+#[derive(Copy, Clone, Debug)]
+pub struct PortabilitySubset {
+    // device가 portability subset device인지 여부
+    pub is_subset: bool,
+    pub triangle_fans: bool,
+    pub point_polygons: bool,
+    pub image_view_format_swizzle: bool,
+    pub sampler_mip_lod_bias: bool,
+    pub separate_stencil_mask_ref: bool,
+    pub constant_alpha_color_blend_factors: bool,
+    pub tessellation_isolines: bool,
+    pub tessellation_point_mode: bool,
+    pub events: bool,
+    // vertex input binding의 stride는 이 값의 배수여야 함
+    pub min_vertex_input_binding_stride_alignment: u32,
+}
+
+impl Default for PortabilitySubset {
+    fn default() -> Self {
+        Self {
+            is_subset: false,
+            triangle_fans: true,
+            point_polygons: true,
+            image_view_format_swizzle: true,
+            sampler_mip_lod_bias: true,
+            separate_stencil_mask_ref: true,
+            constant_alpha_color_blend_factors: true,
+            tessellation_isolines: true,
+            tessellation_point_mode: true,
+            events: true,
+            min_vertex_input_binding_stride_alignment: 1,
+        }
+    }
+}
+
+impl PortabilitySubset {
+    /// physical device가 portability subset device인 경우 지원하는 기능과 limit을 조회
+    pub unsafe fn get(
+        entry: &Entry,
+        instance: &Instance,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<Self> {
+        if !supports_device_extension(
+            instance,
+            physical_device,
+            vk::KHR_PORTABILITY_SUBSET_EXTENSION.name,
+        )? {
+            return Ok(Self::default());
+        }
+
+        let Some(features) = get_portability_subset_features(entry, instance, physical_device)?
+        else {
+            // 조회할 수 없으면 지원하지 않는다고 가정하는 것이 안전함
+            warn!("Portability subset features cannot be queried, assuming none are supported.");
+            return Ok(Self {
+                is_subset: true,
+                triangle_fans: false,
+                point_polygons: false,
+                image_view_format_swizzle: false,
+                sampler_mip_lod_bias: false,
+                separate_stencil_mask_ref: false,
+                constant_alpha_color_blend_factors: false,
+                tessellation_isolines: false,
+                tessellation_point_mode: false,
+                events: false,
+                min_vertex_input_binding_stride_alignment: 4,
+            });
+        };
+
+        let mut portability_properties =
+            vk::PhysicalDevicePortabilitySubsetPropertiesKHR::builder();
+        let mut properties =
+            vk::PhysicalDeviceProperties2::builder().push_next(&mut portability_properties);
+        instance.get_physical_device_properties2_khr(physical_device, &mut properties);
+
+        let subset = Self {
+            is_subset: true,
+            triangle_fans: features.triangle_fans == vk::TRUE,
+            point_polygons: features.point_polygons == vk::TRUE,
+            image_view_format_swizzle: features.image_view_format_swizzle == vk::TRUE,
+            sampler_mip_lod_bias: features.sampler_mip_lod_bias == vk::TRUE,
+            separate_stencil_mask_ref: features.separate_stencil_mask_ref == vk::TRUE,
+            constant_alpha_color_blend_factors: features.constant_alpha_color_blend_factors
+                == vk::TRUE,
+            tessellation_isolines: features.tessellation_isolines == vk::TRUE,
+            tessellation_point_mode: features.tessellation_point_mode == vk::TRUE,
+            events: features.events == vk::TRUE,
+            min_vertex_input_binding_stride_alignment: portability_properties
+                .min_vertex_input_binding_stride_alignment,
+        };
+        info!("Portability subset device: {:?}", subset);
+
+        Ok(subset)
+    }
+}
+
+/// `vk::PhysicalDevicePortabilitySubsetFeaturesKHR`를 조회  
+/// `VK_KHR_get_physical_device_properties2`는 macOS에서 `PORTABILITY_MACOS_VERSION` 이상의 loader를 사용할 때만 활성화되므로  
+/// 그보다 오래된 loader에서는 조회할 수 없어서 `None`을 반환함
+unsafe fn get_portability_subset_features(
+    entry: &Entry,
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+) -> Result<Option<vk::PhysicalDevicePortabilitySubsetFeaturesKHR>> {
+    if !cfg!(target_os = "macos") || entry.version()? < PORTABILITY_MACOS_VERSION {
+        return Ok(None);
+    }
+
+    let mut portability_features = vk::PhysicalDevicePortabilitySubsetFeaturesKHR::default();
+    let mut features = vk::PhysicalDeviceFeatures2::builder().push_next(&mut portability_features);
+    instance.get_physical_device_features2_khr(physical_device, &mut features);
+
+    Ok(Some(portability_features))
+}
+
+/// device가 `VK_KHR_portability_subset`을 지원하면 `extensions`에 추가하고 device를 만들 때 함께 전달할 feature를 반환  
+/// portability subset device에서는 이 extension을 반드시 활성화해야 하고  
+/// 지원하는 portability feature를 활성화해야 validation layer가 해당 기능의 사용을 허용함
+unsafe fn enable_portability_subset(
+    entry: &Entry,
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    extensions: &mut Vec<*const c_char>,
+) -> Result<Option<vk::PhysicalDevicePortabilitySubsetFeaturesKHR>> {
+    if !supports_device_extension(
+        instance,
+        physical_device,
+        vk::KHR_PORTABILITY_SUBSET_EXTENSION.name,
+    )? {
+        return Ok(None);
+    }
+
+    extensions.push(vk::KHR_PORTABILITY_SUBSET_EXTENSION.name.as_ptr());
+    get_portability_subset_features(entry, instance, physical_device)
+}
+
+/// logical device를 생성하고 graphics queue와 present queue를 함께 반환
+pub unsafe fn create_logical_device(
+    entry: &Entry,
+    instance: &Instance,
+    surface: vk::SurfaceKHR,
+    physical_device: vk::PhysicalDevice,
+    features: &vk::PhysicalDeviceFeatures,
+) -> Result<(Device, vk::Queue, vk::Queue)> {
+    let indices = QueueFamilyIndices::get(instance, surface, physical_device)?;
+
+    // queue family를 생성하기 위해 여러개의 DeviceQeuueCreateInfo가 필요하므로
+    // 세트를 생성해서 관리함
+    let mut unique_indices = HashSet::new();
+    unique_indices.insert(indices.graphics);
+    unique_indices.insert(indices.present);
+    // 사용하지 않는 chapter에서도 queue를 하나 더 만드는 것 외에는 비용이 없으므로 항상 생성함
+    if let Some(transfer) = indices.transfer {
+        unique_indices.insert(transfer);
+    }
+
+    let queue_priorities = &[1.0];
+    let queue_infos = unique_indices
+        .iter()
+        .map(|i| {
+            vk::DeviceQueueCreateInfo::builder()
+                .queue_family_index(*i)
+                .queue_priorities(queue_priorities)
+        })
+        .collect::<Vec<_>>();
+
+    let layers = if config::get()?.validation {
+        vec![VALIDATION_LAYER.as_ptr()]
+    } else {
+        vec![]
+    };
+
+    let mut extensions = DEVICE_EXTENSIONS
+        .iter()
+        .map(|n| n.as_ptr())
+        .collect::<Vec<_>>();
+
+    let mut portability_features =
+        enable_portability_subset(entry, instance, physical_device, &mut extensions)?;
+
+    // DeviceCreateInfo를 생성
+    let mut info = vk::DeviceCreateInfo::builder()
+        .queue_create_infos(&queue_infos)
+        .enabled_layer_names(&layers)
+        .enabled_extension_names(&extensions)
+        .enabled_features(features);
+    if let Some(portability_features) = portability_features.as_mut() {
+        info = info.push_next(portability_features);
+    }
+
+    let device = instance.create_device(physical_device, &info, None)?;
+
+    // queue family가 같은경우 index를 한번만 넘겨줘도 됨
+    let graphics_queue = device.get_device_queue(indices.graphics, 0);
+    let present_queue = device.get_device_queue(indices.present, 0);
+
+    Ok((device, graphics_queue, present_queue))
+}
+
+/// `features`의 pNext에 연결된 Vulkan 1.1 이후의 feature 구조체와 `extensions`를 함께 활성화한 logical device를 생성  
+/// `vk::PhysicalDeviceFeatures2`는 `enabled_features` 대신 pNext chain으로 전달해야 함  
+/// instance가 Vulkan 1.1 이상으로 생성되어 있어야 함
+pub unsafe fn create_logical_device_with_features(
+    entry: &Entry,
+    instance: &Instance,
+    surface: vk::SurfaceKHR,
+    physical_device: vk::PhysicalDevice,
+    features: &mut vk::PhysicalDeviceFeatures2,
+    extensions: &[vk::ExtensionName],
+) -> Result<(Device, vk::Queue, vk::Queue)> {
+    let indices = QueueFamilyIndices::get(instance, surface, physical_device)?;
+
+    let mut unique_indices = HashSet::new();
+    unique_indices.insert(indices.graphics);
+    unique_indices.insert(indices.present);
+    if let Some(transfer) = indices.transfer {
+        unique_indices.insert(transfer);
+    }
+
+    let queue_priorities = &[1.0];
+    let queue_infos = unique_indices
+        .iter()
+        .map(|i| {
+            vk::DeviceQueueCreateInfo::builder()
+                .queue_family_index(*i)
+                .queue_priorities(queue_priorities)
+        })
+        .collect::<Vec<_>>();
+
+    let layers = if config::get()?.validation {
+        vec![VALIDATION_LAYER.as_ptr()]
+    } else {
+        vec![]
+    };
+
+    let mut extensions = DEVICE_EXTENSIONS
+        .iter()
+        .chain(extensions)
+        .map(|n| n.as_ptr())
+        .collect::<Vec<_>>();
+
+    let mut portability_features =
+        enable_portability_subset(entry, instance, physical_device, &mut extensions)?;
+
+    let mut info = vk::DeviceCreateInfo::builder()
+        .queue_create_infos(&queue_infos)
+        .enabled_layer_names(&layers)
+        .enabled_extension_names(&extensions)
+        .push_next(features);
+    if let Some(portability_features) = portability_features.as_mut() {
+        info = info.push_next(portability_features);
+    }
+
+    let device = instance.create_device(physical_device, &info, None)?;
+
+    let graphics_queue = device.get_device_queue(indices.graphics, 0);
+    let present_queue = device.get_device_queue(indices.present, 0);
+
+    Ok((device, graphics_queue, present_queue))
+}
+
+/// Vulkan 1.3 baseline mode로 생성한 logical device에서 활성화된 Vulkan 1.1, 1.2, 1.3 feature  
+/// device가 지원하는 feature만 활성화하므로 다른 module은 기능을 사용하기 전에 이 구조체를 확인해야 함
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DeviceCapabilities {
+    // Vulkan 1.1
+    pub multiview: bool,
+    pub shader_draw_parameters: bool,
+    pub sampler_ycbcr_conversion: bool,
+    pub storage_buffer_16bit_access: bool,
+    // Vulkan 1.2
+    pub descriptor_indexing: bool,
+    pub runtime_descriptor_array: bool,
+    pub descriptor_binding_partially_bound: bool,
+    pub draw_indirect_count: bool,
+    pub scalar_block_layout: bool,
+    pub host_query_reset: bool,
+    pub timeline_semaphore: bool,
+    pub buffer_device_address: bool,
+    // Vulkan 1.3
+    pub dynamic_rendering: bool,
+    pub synchronization2: bool,
+    pub maintenance4: bool,
+    pub pipeline_creation_cache_control: bool,
+}
+
+impl DeviceCapabilities {
+    /// `vk::PhysicalDeviceFeatures2`의 pNext chain으로 physical device가 지원하는 feature를 조회
+    pub unsafe fn get(instance: &Instance, physical_device: vk::PhysicalDevice) -> Self {
+        let mut vulkan_11_features = vk::PhysicalDeviceVulkan11Features::builder();
+        let mut vulkan_12_features = vk::PhysicalDeviceVulkan12Features::builder();
+        let mut vulkan_13_features = vk::PhysicalDeviceVulkan13Features::builder();
+        let mut features = vk::PhysicalDeviceFeatures2::builder()
+            .push_next(&mut vulkan_11_features)
+            .push_next(&mut vulkan_12_features)
+            .push_next(&mut vulkan_13_features);
+        instance.get_physical_device_features2(physical_device, &mut features);
+
+        Self {
+            multiview: vulkan_11_features.multiview == vk::TRUE,
+            shader_draw_parameters: vulkan_11_features.shader_draw_parameters == vk::TRUE,
+            sampler_ycbcr_conversion: vulkan_11_features.sampler_ycbcr_conversion == vk::TRUE,
+            storage_buffer_16bit_access: vulkan_11_features.storage_buffer_16bit_access == vk::TRUE,
+            descriptor_indexing: vulkan_12_features.descriptor_indexing == vk::TRUE,
+            runtime_descriptor_array: vulkan_12_features.runtime_descriptor_array == vk::TRUE,
+            descriptor_binding_partially_bound: vulkan_12_features
+                .descriptor_binding_partially_bound
+                == vk::TRUE,
+            draw_indirect_count: vulkan_12_features.draw_indirect_count == vk::TRUE,
+            scalar_block_layout: vulkan_12_features.scalar_block_layout == vk::TRUE,
+            host_query_reset: vulkan_12_features.host_query_reset == vk::TRUE,
+            timeline_semaphore: vulkan_12_features.timeline_semaphore == vk::TRUE,
+            buffer_device_address: vulkan_12_features.buffer_device_address == vk::TRUE,
+            dynamic_rendering: vulkan_13_features.dynamic_rendering == vk::TRUE,
+            synchronization2: vulkan_13_features.synchronization2 == vk::TRUE,
+            maintenance4: vulkan_13_features.maintenance4 == vk::TRUE,
+            pipeline_creation_cache_control: vulkan_13_features.pipeline_creation_cache_control
+                == vk::TRUE,
+        }
+    }
+}
+
+/// physical device가 Vulkan 1.3을 지원하는지 확인  
+/// Vulkan 1.3 baseline mode를 사용하는 chapter의 `check_features`에서 함께 사용함
+pub unsafe fn check_vulkan_1_3_support(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+) -> Result<()> {
+    let properties = instance.get_physical_device_properties(physical_device);
+    if properties.api_version < u32::from(VULKAN_1_3_VERSION) {
+        return Err(anyhow!(SuitabilityError("Vulkan 1.3 support")));
+    }
+
+    Ok(())
+}
+
+/// Vulkan 1.3 baseline mode로 logical device를 생성  
+/// Vulkan 1.1, 1.2, 1.3 feature 구조체를 pNext chain으로 연결해서 device가 지원하는 feature를 모두 활성화하고  
+/// 활성화한 feature를 `DeviceCapabilities`로 함께 반환함  
+/// instance가 `VULKAN_1_3_VERSION` 이상으로 생성되어 있어야 함
+pub unsafe fn create_logical_device_vulkan_1_3(
+    entry: &Entry,
+    instance: &Instance,
+    surface: vk::SurfaceKHR,
+    physical_device: vk::PhysicalDevice,
+    features: &vk::PhysicalDeviceFeatures,
+) -> Result<(Device, vk::Queue, vk::Queue, DeviceCapabilities)> {
+    let capabilities = DeviceCapabilities::get(instance, physical_device);
+
+    let mut vulkan_11_features = vk::PhysicalDeviceVulkan11Features::builder()
+        .multiview(capabilities.multiview)
+        .shader_draw_parameters(capabilities.shader_draw_parameters)
+        .sampler_ycbcr_conversion(capabilities.sampler_ycbcr_conversion)
+        .storage_buffer_16bit_access(capabilities.storage_buffer_16bit_access);
+    let mut vulkan_12_features = vk::PhysicalDeviceVulkan12Features::builder()
+        .descriptor_indexing(capabilities.descriptor_indexing)
+        .runtime_descriptor_array(capabilities.runtime_descriptor_array)
+        .descriptor_binding_partially_bound(capabilities.descriptor_binding_partially_bound)
+        .draw_indirect_count(capabilities.draw_indirect_count)
+        .scalar_block_layout(capabilities.scalar_block_layout)
+        .host_query_reset(capabilities.host_query_reset)
+        .timeline_semaphore(capabilities.timeline_semaphore)
+        .buffer_device_address(capabilities.buffer_device_address);
+    let mut vulkan_13_features = vk::PhysicalDeviceVulkan13Features::builder()
+        .dynamic_rendering(capabilities.dynamic_rendering)
+        .synchronization2(capabilities.synchronization2)
+        .maintenance4(capabilities.maintenance4)
+        .pipeline_creation_cache_control(capabilities.pipeline_creation_cache_control);
+    let mut features = vk::PhysicalDeviceFeatures2::builder()
+        .features(*features)
+        .push_next(&mut vulkan_11_features)
+        .push_next(&mut vulkan_12_features)
+        .push_next(&mut vulkan_13_features);
+
+    let (device, graphics_queue, present_queue) = create_logical_device_with_features(
+        entry,
+        instance,
+        surface,
+        physical_device,
+        &mut features,
+        &[],
+    )?;
+    info!("Created Vulkan 1.3 device with {:?}.", capabilities);
+
+    Ok((device, graphics_queue, present_queue, capabilities))
+}
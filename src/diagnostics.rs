@@ -0,0 +1,270 @@
+//! `ERROR_DEVICE_LOST`가 발생했을 때 원인을 추적하기 위한 진단 정보  
+//! `VK_EXT_device_fault`는 driver가 기록한 fault의 설명과 문제가 된 주소를 알려주고,  
+//! `VK_NV_device_diagnostic_checkpoints`는 command buffer에 넣어둔 checkpoint 중에서 GPU가 마지막으로 지나간 위치를 알려줌  
+//! device가 lost 상태가 되면 대부분의 command가 실패하므로 이 두 가지 외에는 얻을 수 있는 정보가 거의 없음
+
+use anyhow::Result;
+use log::*;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::Version;
+
+use vulkanalia::vk::InstanceV1_1;
+
+use std::ffi::CStr;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use std::ptr;
+
+use crate::device::supports_device_extension;
+
+/// 진단 extension이 의존하는 `vkGetPhysicalDeviceFeatures2`가 core에 포함된 Vulkan 버전
+pub const DIAGNOSTICS_VERSION: Version = Version::new(1, 1, 0);
+
+/// device lost 보고서를 저장할 파일
+pub const DEVICE_LOST_REPORT_PATH: &str = "device_lost_report.txt";
+
+/// device가 지원해서 활성화할 진단 기능
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DeviceDiagnostics {
+    /// `VK_EXT_device_fault`의 `deviceFault` feature를 사용할 수 있는지 여부
+    pub device_fault: bool,
+    /// `VK_NV_device_diagnostic_checkpoints`를 사용할 수 있는지 여부
+    pub checkpoints: bool,
+}
+
+impl DeviceDiagnostics {
+    /// `physical_device`가 지원하는 진단 기능을 확인  
+    /// 두 extension 모두 Vulkan 1.1의 기능에 의존하므로 instance와 device가 1.1 미만이면 아무것도 사용하지 않음
+    pub unsafe fn query(
+        instance: &Instance,
+        physical_device: vk::PhysicalDevice,
+        instance_version: Version,
+    ) -> Result<Self> {
+        let properties = instance.get_physical_device_properties(physical_device);
+        if instance_version < DIAGNOSTICS_VERSION
+            || properties.api_version < u32::from(DIAGNOSTICS_VERSION)
+        {
+            info!("Device diagnostics require Vulkan 1.1.");
+            return Ok(Self::default());
+        }
+
+        // extension을 지원해도 feature가 꺼져있으면 fault 정보를 조회할 수 없음
+        let device_fault = supports_device_extension(
+            instance,
+            physical_device,
+            vk::EXT_DEVICE_FAULT_EXTENSION.name,
+        )? && {
+            let mut fault_features = vk::PhysicalDeviceFaultFeaturesEXT::default();
+            let mut features =
+                vk::PhysicalDeviceFeatures2::builder().push_next(&mut fault_features);
+            instance.get_physical_device_features2(physical_device, &mut features);
+            fault_features.device_fault == vk::TRUE
+        };
+
+        let checkpoints = supports_device_extension(
+            instance,
+            physical_device,
+            vk::NV_DEVICE_DIAGNOSTIC_CHECKPOINTS_EXTENSION.name,
+        )?;
+
+        let diagnostics = Self {
+            device_fault,
+            checkpoints,
+        };
+        info!("{:?}", diagnostics);
+
+        Ok(diagnostics)
+    }
+
+    /// 진단 기능을 하나라도 사용할 수 있는지 여부
+    pub fn is_enabled(&self) -> bool {
+        self.device_fault || self.checkpoints
+    }
+
+    /// logical device를 생성할 때 활성화해야 하는 extension
+    pub fn extensions(&self) -> Vec<vk::ExtensionName> {
+        let mut extensions = vec![];
+        if self.device_fault {
+            extensions.push(vk::EXT_DEVICE_FAULT_EXTENSION.name);
+        }
+        if self.checkpoints {
+            extensions.push(vk::NV_DEVICE_DIAGNOSTIC_CHECKPOINTS_EXTENSION.name);
+        }
+        extensions
+    }
+
+    /// logical device를 생성할 때 `vk::PhysicalDeviceFeatures2`의 pNext chain에 연결할 feature  
+    /// vendor binary는 크기가 크고 vendor의 도구로만 읽을 수 있으므로 요청하지 않음
+    pub fn fault_features(&self) -> vk::PhysicalDeviceFaultFeaturesEXT {
+        vk::PhysicalDeviceFaultFeaturesEXT {
+            device_fault: self.device_fault as vk::Bool32,
+            ..Default::default()
+        }
+    }
+
+    /// command buffer에 checkpoint를 기록  
+    /// `marker`는 NULL 문자로 끝나야 하고, device lost 이후에 포인터를 그대로 돌려받으므로 `'static`이어야 함  
+    /// checkpoint를 지원하지 않으면 아무것도 하지 않음
+    pub unsafe fn cmd_checkpoint(
+        &self,
+        device: &Device,
+        command_buffer: vk::CommandBuffer,
+        marker: &'static [u8],
+    ) {
+        debug_assert_eq!(marker.last(), Some(&0));
+
+        if self.checkpoints {
+            // marker는 driver가 해석하지 않는 불투명한 포인터이므로 wrapper를 거치지 않고 그대로 전달
+            (device.commands().cmd_set_checkpoint_nv)(command_buffer, marker.as_ptr().cast());
+        }
+    }
+
+    /// device lost의 원인을 추적하기 위한 보고서를 작성  
+    /// `queues`는 checkpoint를 조회할 queue와 보고서에 표시할 이름임
+    pub unsafe fn report(
+        &self,
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        queues: &[(&str, vk::Queue)],
+    ) -> String {
+        let properties = instance.get_physical_device_properties(physical_device);
+
+        let mut report = String::new();
+        let _ = writeln!(report, "Device lost report");
+        let _ = writeln!(
+            report,
+            "Device: {} (driver version {:#x})",
+            properties.device_name, properties.driver_version
+        );
+
+        let _ = writeln!(report, "\n[VK_EXT_device_fault]");
+        if self.device_fault {
+            report.push_str(&device_fault_info(device));
+        } else {
+            let _ = writeln!(report, "Not supported.");
+        }
+
+        let _ = writeln!(report, "\n[VK_NV_device_diagnostic_checkpoints]");
+        if self.checkpoints {
+            for (name, queue) in queues {
+                report.push_str(&checkpoint_info(device, name, *queue));
+            }
+        } else {
+            let _ = writeln!(report, "Not supported.");
+        }
+
+        report
+    }
+
+    /// 보고서를 작성해서 `DEVICE_LOST_REPORT_PATH`에 저장하고 log로도 출력
+    pub unsafe fn write_report(
+        &self,
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        queues: &[(&str, vk::Queue)],
+    ) -> Result<PathBuf> {
+        let report = self.report(instance, device, physical_device, queues);
+        error!("{}", report);
+
+        let path = PathBuf::from(DEVICE_LOST_REPORT_PATH);
+        fs::write(&path, report)?;
+        error!("Device lost report was written to `{}`.", path.display());
+
+        Ok(path)
+    }
+}
+
+/// `vkGetDeviceFaultInfoEXT`로 driver가 기록한 fault 정보를 조회해서 문자열로 변환  
+/// 개수를 먼저 조회하고 그만큼 배열을 할당한 뒤에 다시 호출함
+unsafe fn device_fault_info(device: &Device) -> String {
+    let get_device_fault_info = device.commands().get_device_fault_info_ext;
+    let mut text = String::new();
+
+    let mut counts = vk::DeviceFaultCountsEXT::default();
+    let result = get_device_fault_info(device.handle(), &mut counts, ptr::null_mut());
+    if result != vk::Result::SUCCESS {
+        let _ = writeln!(text, "Failed to query fault counts ({:?}).", result);
+        return text;
+    }
+
+    let mut address_infos =
+        vec![vk::DeviceFaultAddressInfoEXT::default(); counts.address_info_count as usize];
+    let mut vendor_infos =
+        vec![vk::DeviceFaultVendorInfoEXT::default(); counts.vendor_info_count as usize];
+    counts.vendor_binary_size = 0;
+
+    let mut info = vk::DeviceFaultInfoEXT {
+        address_infos: address_infos.as_mut_ptr(),
+        vendor_infos: vendor_infos.as_mut_ptr(),
+        vendor_binary_data: ptr::null_mut(),
+        ..Default::default()
+    };
+    let result = get_device_fault_info(device.handle(), &mut counts, &mut info);
+    // vendor binary를 받지 않았으므로 INCOMPLETE도 정상적인 결과임
+    if result != vk::Result::SUCCESS && result != vk::Result::INCOMPLETE {
+        let _ = writeln!(text, "Failed to query fault info ({:?}).", result);
+        return text;
+    }
+
+    let _ = writeln!(text, "Description: {}", info.description);
+
+    let address_infos = &address_infos[..counts.address_info_count as usize];
+    for address in address_infos {
+        let _ = writeln!(
+            text,
+            "Address: {:?} at {:#x} (precision {:#x})",
+            address.address_type, address.reported_address, address.address_precision
+        );
+    }
+
+    let vendor_infos = &vendor_infos[..counts.vendor_info_count as usize];
+    for vendor in vendor_infos {
+        let _ = writeln!(
+            text,
+            "Vendor: {} (code {:#x}, data {:#x})",
+            vendor.description, vendor.vendor_fault_code, vendor.vendor_fault_data
+        );
+    }
+
+    if address_infos.is_empty() && vendor_infos.is_empty() {
+        let _ = writeln!(
+            text,
+            "No fault addresses or vendor information were reported."
+        );
+    }
+
+    text
+}
+
+/// `queue`에서 GPU가 마지막으로 지나간 checkpoint를 조회해서 문자열로 변환  
+/// pipeline stage마다 해당 stage를 마지막으로 지나간 checkpoint가 하나씩 반환됨
+unsafe fn checkpoint_info(device: &Device, name: &str, queue: vk::Queue) -> String {
+    let get_checkpoint_data = device.commands().get_queue_checkpoint_data_nv;
+    let mut text = String::new();
+
+    let mut count = 0;
+    get_checkpoint_data(queue, &mut count, ptr::null_mut());
+    let mut checkpoints = vec![vk::CheckpointDataNV::default(); count as usize];
+    get_checkpoint_data(queue, &mut count, checkpoints.as_mut_ptr());
+    checkpoints.truncate(count as usize);
+
+    if checkpoints.is_empty() {
+        let _ = writeln!(text, "{} queue: no checkpoints were reached.", name);
+        return text;
+    }
+
+    let _ = writeln!(text, "{} queue:", name);
+    for checkpoint in checkpoints {
+        let marker = if checkpoint.checkpoint_marker.is_null() {
+            "(null)".into()
+        } else {
+            CStr::from_ptr(checkpoint.checkpoint_marker.cast()).to_string_lossy()
+        };
+        let _ = writeln!(text, "  {:?}: {}", checkpoint.stage, marker);
+    }
+
+    text
+}
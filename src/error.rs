@@ -0,0 +1,52 @@
+//! frame을 그리는 동안 발생할 수 있는 error
+
+use anyhow::anyhow;
+use thiserror::Error;
+use vulkanalia::prelude::v1_0::*;
+
+/// `render`가 반환하는 error  
+/// swapchain이나 surface를 다시 생성해서 복구할 수 있는 경우와 앱을 종료해야 하는 경우를 구분함
+#[derive(Debug, Error)]
+pub enum RenderError {
+    /// swapchain이 surface와 더 이상 호환되지 않아서 다시 생성해야 함
+    #[error("Swapchain is out of date.")]
+    OutOfDate,
+    /// swapchain을 계속 사용할 수는 있지만 surface와 정확히 일치하지 않음
+    #[error("Swapchain is suboptimal.")]
+    Suboptimal,
+    /// surface를 더 이상 사용할 수 없어서 surface부터 다시 생성해야 함
+    #[error("Surface was lost.")]
+    SurfaceLost,
+    /// 복구할 수 없는 error
+    #[error(transparent)]
+    Fatal(#[from] anyhow::Error),
+}
+
+impl RenderError {
+    /// swapchain이나 surface를 다시 생성해서 복구할 수 있는지 여부
+    pub fn is_recoverable(&self) -> bool {
+        !matches!(self, Self::Fatal(_))
+    }
+}
+
+impl From<vk::ErrorCode> for RenderError {
+    fn from(error: vk::ErrorCode) -> Self {
+        match error {
+            vk::ErrorCode::OUT_OF_DATE_KHR => Self::OutOfDate,
+            vk::ErrorCode::SURFACE_LOST_KHR => Self::SurfaceLost,
+            error => Self::Fatal(anyhow!(error)),
+        }
+    }
+}
+
+/// `error`가 `ERROR_DEVICE_LOST`로 발생한 error인지 확인  
+/// device lost는 `RenderError::Fatal`로 전달되므로 진단 정보를 남기려면 이 함수로 구분해야 함
+pub fn is_device_lost(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<vk::ErrorCode>() == Some(&vk::ErrorCode::DEVICE_LOST)
+}
+
+/// `error`가 `ERROR_SURFACE_LOST_KHR`로 발생한 error인지 확인  
+/// swapchain을 다시 생성하다가 surface가 lost 상태인 것을 알게 되면 `RenderError`가 아니라 `anyhow::Error`로 전달되므로 이 함수로 구분해야 함
+pub fn is_surface_lost(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<vk::ErrorCode>() == Some(&vk::ErrorCode::SURFACE_LOST_KHR)
+}
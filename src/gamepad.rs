@@ -0,0 +1,150 @@
+//! gilrs로 gamepad의 analog stick과 trigger를 읽어서 camera를 움직이는 기능  
+//! winit은 gamepad event를 전달하지 않으므로 event loop에서 매 frame 직접 상태를 읽어야 함  
+//! `gamepad` feature를 끄면 아무것도 하지 않으므로 chapter는 `cfg` 없이 사용할 수 있음
+
+#[cfg(feature = "gamepad")]
+use cgmath::Vector3;
+use log::*;
+
+use std::fmt;
+
+use crate::camera::CameraController;
+
+/// stick을 이 값보다 적게 기울이면 입력이 없는 것으로 취급함  
+/// 가만히 두어도 stick이 정확히 가운데로 돌아오지 않아서 camera가 조금씩 움직이는 것을 막음
+pub const DEAD_ZONE: f32 = 0.15;
+
+/// 연결된 gamepad를 관리하고 입력을 `CameraController`에 전달하는 구조체  
+/// 왼쪽 stick으로 앞뒤좌우, 오른쪽과 왼쪽 trigger로 위아래로 이동하고 오른쪽 stick으로 주위를 둘러봄
+pub struct GamepadInput {
+    #[cfg(feature = "gamepad")]
+    gilrs: Option<gilrs::Gilrs>,
+    // 여러 gamepad가 연결되어 있으면 마지막으로 입력이 있었던 gamepad를 사용함
+    #[cfg(feature = "gamepad")]
+    active: Option<gilrs::GamepadId>,
+}
+
+impl GamepadInput {
+    /// gamepad를 찾기 시작  
+    /// platform이 gamepad를 지원하지 않으면 log만 남기고 아무것도 하지 않음
+    pub fn new() -> Self {
+        #[cfg(feature = "gamepad")]
+        {
+            let gilrs = match gilrs::Gilrs::new() {
+                Ok(gilrs) => Some(gilrs),
+                Err(error) => {
+                    warn!("Failed to initialize gamepad input: {}", error);
+                    None
+                }
+            };
+
+            let active = gilrs.as_ref().and_then(|gilrs| {
+                gilrs
+                    .gamepads()
+                    .map(|(id, gamepad)| {
+                        info!("Found gamepad `{}`.", gamepad.name());
+                        id
+                    })
+                    .last()
+            });
+
+            Self { gilrs, active }
+        }
+
+        #[cfg(not(feature = "gamepad"))]
+        {
+            debug!("Gamepad input requires the `gamepad` feature.");
+            Self {}
+        }
+    }
+
+    /// gamepad를 사용할 수 있는지 여부
+    pub fn is_available(&self) -> bool {
+        #[cfg(feature = "gamepad")]
+        {
+            self.active.is_some()
+        }
+
+        #[cfg(not(feature = "gamepad"))]
+        {
+            false
+        }
+    }
+
+    /// 지난 호출 이후의 gamepad event를 처리하고 현재 stick과 trigger의 상태를 `controller`에 설정  
+    /// `CameraController::update`를 호출하기 전에 매 frame 호출해야 함
+    pub fn update(&mut self, controller: &mut CameraController) {
+        #[cfg(feature = "gamepad")]
+        {
+            use gilrs::{Axis, Button, EventType};
+
+            let Some(gilrs) = self.gilrs.as_mut() else {
+                return;
+            };
+
+            // event를 꺼내야 gilrs가 gamepad의 상태를 갱신함
+            while let Some(event) = gilrs.next_event() {
+                match event.event {
+                    EventType::Connected => {
+                        info!("Gamepad `{}` connected.", gilrs.gamepad(event.id).name());
+                        self.active = Some(event.id);
+                    }
+                    EventType::Disconnected => {
+                        info!("Gamepad `{}` disconnected.", gilrs.gamepad(event.id).name());
+                        if self.active == Some(event.id) {
+                            self.active = gilrs.gamepads().map(|(id, _)| id).last();
+                        }
+                    }
+                    _ => self.active = Some(event.id),
+                }
+            }
+
+            let Some(gamepad) = self.active.map(|id| gilrs.gamepad(id)) else {
+                controller.set_analog_input(Vector3::new(0.0, 0.0, 0.0), (0.0, 0.0));
+                return;
+            };
+
+            let trigger = |button| gamepad.button_data(button).map_or(0.0, |data| data.value());
+
+            let movement = Vector3::new(
+                apply_dead_zone(gamepad.value(Axis::LeftStickX)),
+                apply_dead_zone(gamepad.value(Axis::LeftStickY)),
+                apply_dead_zone(trigger(Button::RightTrigger2) - trigger(Button::LeftTrigger2)),
+            );
+            let look = (
+                apply_dead_zone(gamepad.value(Axis::RightStickX)),
+                apply_dead_zone(gamepad.value(Axis::RightStickY)),
+            );
+
+            controller.set_analog_input(movement, look);
+        }
+
+        #[cfg(not(feature = "gamepad"))]
+        let _ = controller;
+    }
+}
+
+impl Default for GamepadInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for GamepadInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GamepadInput")
+            .field("available", &self.is_available())
+            .finish()
+    }
+}
+
+/// `DEAD_ZONE`보다 작은 값은 0으로 만들고, 나머지 범위를 다시 0.0 ~ 1.0으로 늘림  
+/// 늘리지 않으면 dead zone을 막 벗어나는 순간 속도가 0에서 `DEAD_ZONE`으로 갑자기 바뀜
+#[cfg(feature = "gamepad")]
+fn apply_dead_zone(value: f32) -> f32 {
+    if value.abs() < DEAD_ZONE {
+        0.0
+    } else {
+        value.signum() * (value.abs() - DEAD_ZONE) / (1.0 - DEAD_ZONE)
+    }
+}
@@ -0,0 +1,754 @@
+//! glTF 파일에서 mesh와 metallic-roughness PBR material을 읽는 loader  
+//! node의 transform을 적용한 모든 primitive를 하나의 mesh로 합치며, material은 첫 번째 primitive의 것만 사용함  
+//! skin이나 morph target이 있으면 animation이 움직이는 node hierarchy와 animation도 함께 읽음
+
+use anyhow::{anyhow, Result};
+use cgmath::{
+    InnerSpace, Matrix, Matrix3, Matrix4, Quaternion, SquareMatrix, Vector2, Vector3, Vector4, Zero,
+};
+use gltf::animation::util::ReadOutputs;
+use gltf::image::Format;
+use log::*;
+
+use std::path::Path;
+
+use crate::animation::{Animation, Channel, Interpolation, Keyframes, Node, Skeleton, Transform};
+
+/// material에 texture가 없을 때 대신 사용하는 texel  
+/// texture의 값에 factor를 곱해서 사용하므로 factor만 적용됨
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+/// normal map이 없을 때 대신 사용하는 texel  
+/// tangent space에서 표면에 수직인 (0, 0, 1)을 저장한 값
+const FLAT_NORMAL: [u8; 4] = [128, 128, 255, 255];
+
+/// RGBA8 형식으로 변환한 material texture
+#[derive(Clone, Debug)]
+pub struct MaterialImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl MaterialImage {
+    /// 하나의 texel만 가진 image
+    fn solid(texel: [u8; 4]) -> Self {
+        Self {
+            width: 1,
+            height: 1,
+            pixels: texel.to_vec(),
+        }
+    }
+}
+
+/// metallic-roughness model의 PBR material  
+/// shader에서는 각 texture의 값에 factor를 곱해서 사용함
+#[derive(Clone, Debug)]
+pub struct Material {
+    pub base_color_factor: [f32; 4],
+    pub metallic_factor: f32,
+    pub roughness_factor: f32,
+    /// normal map에서 읽은 x, y에 곱하는 값
+    pub normal_scale: f32,
+    /// ambient occlusion을 적용하는 정도
+    pub occlusion_strength: f32,
+    /// sRGB로 저장된 base color
+    pub albedo: MaterialImage,
+    /// tangent space의 normal
+    pub normal: MaterialImage,
+    /// g channel에 roughness, b channel에 metallic이 저장됨
+    pub metallic_roughness: MaterialImage,
+    /// r channel에 ambient occlusion이 저장됨
+    pub occlusion: MaterialImage,
+}
+
+/// node의 transform을 적용한 mesh
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// xyz는 texture의 u가 증가하는 방향, w는 bitangent의 방향을 나타내는 부호  
+    /// bitangent는 `cross(normal, tangent.xyz) * tangent.w`로 구함
+    pub tangents: Vec<[f32; 4]>,
+    pub tex_coords: Vec<[f32; 2]>,
+    /// vertex에 영향을 주는 joint 4개의 `Skeleton::joints`에서의 index  
+    /// model에 skin이 없으면 비어있음
+    pub joints: Vec<[u16; 4]>,
+    /// `joints`의 각 joint가 영향을 주는 정도  
+    /// skin이 적용되지 않는 primitive의 vertex는 모두 0임
+    pub weights: Vec<[f32; 4]>,
+    /// morph target마다 vertex의 position과 normal에 더할 값  
+    /// model에 morph target이 없으면 비어있음
+    pub morph_targets: Vec<MorphTarget>,
+    pub indices: Vec<u32>,
+}
+
+/// 기본 모양에서 vertex를 옮기는 morph target (blend shape)  
+/// 각 배열은 `Mesh::positions`와 길이가 같고, morph target이 적용되지 않는 primitive의 vertex는 0임
+#[derive(Clone, Debug, Default)]
+pub struct MorphTarget {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+}
+
+/// glTF 파일에서 읽은 mesh와 material
+#[derive(Clone, Debug)]
+pub struct Model {
+    pub mesh: Mesh,
+    pub material: Material,
+    /// skin의 joint와 node hierarchy  
+    /// mesh에 skin이 없으면 `None`
+    pub skeleton: Option<Skeleton>,
+    /// animation이 없을 때 morph target에 적용하는 weight  
+    /// `Mesh::morph_targets`와 길이가 같음
+    pub morph_weights: Vec<f32>,
+    /// `skeleton`의 node와 morph target의 weight를 움직이는 animation
+    pub animations: Vec<Animation>,
+}
+
+/// glTF(.gltf, .glb) 파일의 기본 scene을 읽어서 하나의 model로 변환  
+/// 삼각형이 아닌 primitive는 무시하고, material이나 skin, morph target이 있는 mesh가 여러개면 첫 번째 것만 사용함  
+/// skin과 morph target이 모두 없으면 node의 transform을 vertex에 미리 적용하므로 animation은 읽지 않음
+pub fn load_gltf(path: impl AsRef<Path>) -> Result<Model> {
+    let path = path.as_ref();
+    let (document, buffers, images) = gltf::import(path)
+        .map_err(|e| anyhow!("Failed to import glTF file `{}`: {}", path.display(), e))?;
+
+    let scene = document
+        .default_scene()
+        .or_else(|| document.scenes().next())
+        .ok_or_else(|| anyhow!("Missing scene in `{}`.", path.display()))?;
+
+    let mut mesh = Mesh::default();
+    let mut material = None;
+    let mut skin = None;
+    let mut morph = None;
+    for node in scene.nodes() {
+        load_node(
+            &node,
+            Matrix4::identity(),
+            &buffers,
+            &mut mesh,
+            &mut material,
+            &mut skin,
+            &mut morph,
+        )?;
+    }
+
+    let material =
+        material.ok_or_else(|| anyhow!("Missing triangle mesh in `{}`.", path.display()))?;
+
+    // node에 weight가 없으면 mesh의 기본 weight를, 둘 다 없으면 0을 사용함
+    let morph_weights = match &morph {
+        Some(node) => node
+            .weights()
+            .or_else(|| node.mesh().and_then(|m| m.weights()))
+            .map_or_else(|| vec![0.0; mesh.morph_targets.len()], <[f32]>::to_vec),
+        None => vec![],
+    };
+    if morph_weights.len() != mesh.morph_targets.len() {
+        return Err(anyhow!(
+            "Morph weight count does not match morph target count in `{}`.",
+            path.display()
+        ));
+    }
+
+    // skin이 없으면 joint와 weight를 사용하지 않음
+    if skin.is_none() {
+        mesh.joints.clear();
+        mesh.weights.clear();
+    }
+
+    let (skeleton, animations) = if skin.is_some() || morph.is_some() {
+        let (nodes, indices) = load_scene_nodes(&scene, document.nodes().len());
+        let animations = document
+            .animations()
+            .map(|a| {
+                load_animation(
+                    &a,
+                    &indices,
+                    morph.as_ref().map(|n| n.index()),
+                    morph_weights.len(),
+                    &buffers,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let skeleton = skin
+            .as_ref()
+            .map(|skin| load_skeleton(skin, nodes, &indices, &buffers))
+            .transpose()?;
+        (skeleton, animations)
+    } else {
+        (None, vec![])
+    };
+
+    Ok(Model {
+        mesh,
+        material: load_material(&material, &images)?,
+        skeleton,
+        morph_weights,
+        animations,
+    })
+}
+
+/// node와 자식 node의 mesh를 `mesh`에 추가  
+/// 자식 node의 transform은 부모 node의 transform을 기준으로 함
+fn load_node<'a>(
+    node: &gltf::Node<'a>,
+    parent: Matrix4<f32>,
+    buffers: &[gltf::buffer::Data],
+    mesh: &mut Mesh,
+    material: &mut Option<gltf::Material<'a>>,
+    skin: &mut Option<gltf::Skin<'a>>,
+    morph: &mut Option<gltf::Node<'a>>,
+) -> Result<()> {
+    let transform = parent * Matrix4::from(node.transform().matrix());
+
+    // skin이 있는 mesh는 node의 transform을 무시하고 joint의 transform으로만 배치됨
+    let skinned = match (node.skin(), node.mesh()) {
+        (Some(node_skin), Some(_)) => match skin.as_ref().map(|s| s.index()) {
+            None => {
+                *skin = Some(node_skin);
+                true
+            }
+            Some(index) if index == node_skin.index() => true,
+            Some(_) => {
+                warn!(
+                    "Only the first skin is used, ignoring skin {}.",
+                    node_skin.index()
+                );
+                false
+            }
+        },
+        _ => false,
+    };
+    let mesh_transform = if skinned {
+        Matrix4::identity()
+    } else {
+        transform
+    };
+
+    // morph target의 weight는 node마다 다르므로 morph target이 있는 첫 번째 node만 사용함
+    let has_targets = node
+        .mesh()
+        .iter()
+        .flat_map(|m| m.primitives())
+        .any(|p| p.morph_targets().next().is_some());
+    let morphed = match (has_targets, morph.is_none()) {
+        (true, true) => {
+            *morph = Some(node.clone());
+            true
+        }
+        (true, false) => {
+            warn!(
+                "Only the first morph target mesh is used, ignoring node {}.",
+                node.index()
+            );
+            false
+        }
+        _ => false,
+    };
+
+    for primitive in node.mesh().iter().flat_map(|m| m.primitives()) {
+        if primitive.mode() != gltf::mesh::Mode::Triangles {
+            warn!("Skipping non-triangle primitive {:?}.", primitive.mode());
+            continue;
+        }
+
+        match material {
+            None => *material = Some(primitive.material()),
+            Some(m) if m.index() != primitive.material().index() => {
+                warn!(
+                    "Only the first material is used, ignoring material {:?}.",
+                    primitive.material().index()
+                );
+            }
+            _ => {}
+        }
+
+        load_primitive(&primitive, mesh_transform, skinned, morphed, buffers, mesh)?;
+    }
+
+    for child in node.children() {
+        load_node(&child, transform, buffers, mesh, material, skin, morph)?;
+    }
+
+    Ok(())
+}
+
+/// primitive의 vertex와 index를 `transform`을 적용해서 `mesh`에 추가  
+/// `skinned`이면 joint index와 weight를, `morphed`이면 morph target도 함께 읽음
+fn load_primitive(
+    primitive: &gltf::Primitive,
+    transform: Matrix4<f32>,
+    skinned: bool,
+    morphed: bool,
+    buffers: &[gltf::buffer::Data],
+    mesh: &mut Mesh,
+) -> Result<()> {
+    let reader = primitive.reader(|b| Some(&buffers[b.index()]));
+
+    let positions = reader
+        .read_positions()
+        .ok_or_else(|| anyhow!("Missing positions in primitive {}.", primitive.index()))?;
+    let normals = reader
+        .read_normals()
+        .ok_or_else(|| anyhow!("Missing normals in primitive {}.", primitive.index()))?;
+
+    // normal은 크기가 균일하지 않은 scale에서도 표면에 수직이 되도록 inverse transpose로 변환함
+    let normal_matrix = Matrix3::from_cols(
+        transform.x.truncate(),
+        transform.y.truncate(),
+        transform.z.truncate(),
+    )
+    .invert()
+    .ok_or_else(|| anyhow!("Node transform is not invertible."))?
+    .transpose();
+
+    // index는 이미 추가된 vertex 뒤를 가리키도록 옮겨야 함
+    let base = mesh.positions.len();
+    let index_base = mesh.indices.len();
+
+    mesh.positions.extend(positions.map(|p| {
+        let p = transform * Vector4::new(p[0], p[1], p[2], 1.0);
+        [p.x, p.y, p.z]
+    }));
+    mesh.normals.extend(
+        normals.map(|n| -> [f32; 3] { (normal_matrix * Vector3::from(n)).normalize().into() }),
+    );
+
+    let count = mesh.positions.len() - base;
+    if mesh.normals.len() != mesh.positions.len() {
+        return Err(anyhow!(
+            "Normal count does not match position count in primitive {}.",
+            primitive.index()
+        ));
+    }
+
+    // texture 좌표가 없으면 모든 vertex가 texture의 같은 texel을 사용함
+    match reader.read_tex_coords(0) {
+        Some(tex_coords) => mesh.tex_coords.extend(tex_coords.into_f32()),
+        None => mesh.tex_coords.extend((0..count).map(|_| [0.0, 0.0])),
+    }
+
+    // index가 없으면 vertex 3개씩 순서대로 삼각형을 이룸
+    match reader.read_indices() {
+        Some(indices) => mesh
+            .indices
+            .extend(indices.into_u32().map(|i| base as u32 + i)),
+        None => mesh.indices.extend((base..base + count).map(|i| i as u32)),
+    }
+
+    // tangent는 표면을 따르는 방향이므로 position과 같은 matrix로 변환함
+    // 파일에 tangent가 없으면 삼각형의 position과 texture 좌표로 계산함
+    let tangent_matrix = Matrix3::from_cols(
+        transform.x.truncate(),
+        transform.y.truncate(),
+        transform.z.truncate(),
+    );
+    match reader.read_tangents() {
+        Some(tangents) => mesh.tangents.extend(tangents.map(|t| {
+            let xyz = (tangent_matrix * Vector3::new(t[0], t[1], t[2])).normalize();
+            [xyz.x, xyz.y, xyz.z, t[3]]
+        })),
+        None => generate_tangents(mesh, base, index_base),
+    }
+
+    if mesh.tangents.len() != mesh.positions.len() {
+        return Err(anyhow!(
+            "Tangent count does not match position count in primitive {}.",
+            primitive.index()
+        ));
+    }
+
+    // skin이 적용되지 않는 primitive는 weight를 0으로 채워서 joint의 영향을 받지 않도록 함
+    match (reader.read_joints(0), reader.read_weights(0)) {
+        (Some(joints), Some(weights)) if skinned => {
+            mesh.joints.extend(joints.into_u16());
+            mesh.weights.extend(weights.into_f32());
+        }
+        _ => {
+            if skinned {
+                warn!(
+                    "Missing joints or weights in skinned primitive {}.",
+                    primitive.index()
+                );
+            }
+            mesh.joints.extend((0..count).map(|_| [0; 4]));
+            mesh.weights.extend((0..count).map(|_| [0.0; 4]));
+        }
+    }
+
+    if mesh.joints.len() != mesh.positions.len() || mesh.weights.len() != mesh.positions.len() {
+        return Err(anyhow!(
+            "Joint or weight count does not match position count in primitive {}.",
+            primitive.index()
+        ));
+    }
+
+    let targets = if morphed {
+        reader.read_morph_targets().collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    // 앞에서 추가된 vertex는 morph target의 영향을 받지 않도록 0으로 채움
+    if mesh.morph_targets.is_empty() && !targets.is_empty() {
+        mesh.morph_targets = (0..targets.len())
+            .map(|_| MorphTarget {
+                positions: vec![[0.0; 3]; base],
+                normals: vec![[0.0; 3]; base],
+            })
+            .collect();
+    }
+
+    if !targets.is_empty() && targets.len() != mesh.morph_targets.len() {
+        return Err(anyhow!(
+            "Morph target count does not match other primitives in primitive {}.",
+            primitive.index()
+        ));
+    }
+
+    // delta는 방향이므로 translation 없이 position과 normal과 같은 matrix로 변환함
+    let len = mesh.positions.len();
+    let mut targets = targets.into_iter();
+    for target in &mut mesh.morph_targets {
+        if let Some((positions, normals, _)) = targets.next() {
+            if let Some(positions) = positions {
+                target.positions.extend(
+                    positions.map(|d| -> [f32; 3] { (tangent_matrix * Vector3::from(d)).into() }),
+                );
+            }
+            if let Some(normals) = normals {
+                target.normals.extend(
+                    normals.map(|d| -> [f32; 3] { (normal_matrix * Vector3::from(d)).into() }),
+                );
+            }
+        }
+
+        if target.positions.len() > len || target.normals.len() > len {
+            return Err(anyhow!(
+                "Morph target count does not match position count in primitive {}.",
+                primitive.index()
+            ));
+        }
+
+        // morph target이 적용되지 않거나 값이 없는 vertex는 움직이지 않음
+        target.positions.resize(len, [0.0; 3]);
+        target.normals.resize(len, [0.0; 3]);
+    }
+
+    Ok(())
+}
+
+/// scene의 모든 node를 부모가 자식보다 앞에 오도록 나열  
+/// glTF의 node index마다 반환한 배열에서의 index를 함께 반환하며, scene에 없는 node는 `None`임
+fn load_scene_nodes(scene: &gltf::Scene, node_count: usize) -> (Vec<Node>, Vec<Option<usize>>) {
+    let mut nodes = vec![];
+    let mut indices = vec![None; node_count];
+    for node in scene.nodes() {
+        add_scene_node(&node, None, &mut nodes, &mut indices);
+    }
+
+    (nodes, indices)
+}
+
+/// node와 자식 node를 깊이 우선으로 `nodes`에 추가
+fn add_scene_node(
+    node: &gltf::Node,
+    parent: Option<usize>,
+    nodes: &mut Vec<Node>,
+    indices: &mut [Option<usize>],
+) {
+    // glTF의 quaternion은 (x, y, z, w) 순서로 저장됨
+    let (translation, [x, y, z, w], scale) = node.transform().decomposed();
+
+    let index = nodes.len();
+    indices[node.index()] = Some(index);
+    nodes.push(Node {
+        name: node.name().map(String::from),
+        parent,
+        transform: Transform {
+            translation: translation.into(),
+            rotation: Quaternion::new(w, x, y, z),
+            scale: scale.into(),
+        },
+    });
+
+    for child in node.children() {
+        add_scene_node(&child, Some(index), nodes, indices);
+    }
+}
+
+/// skin의 joint와 inverse bind matrix를 읽음  
+/// `indices`는 `load_scene_nodes`가 반환한 glTF node index와 `nodes`의 index의 대응
+fn load_skeleton(
+    skin: &gltf::Skin,
+    nodes: Vec<Node>,
+    indices: &[Option<usize>],
+    buffers: &[gltf::buffer::Data],
+) -> Result<Skeleton> {
+    let joints = skin
+        .joints()
+        .map(|joint| {
+            indices[joint.index()].ok_or_else(|| {
+                anyhow!(
+                    "Joint node {} of skin {} is not in the scene.",
+                    joint.index(),
+                    skin.index()
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // inverse bind matrix가 없으면 모두 단위 행렬로 취급함
+    let reader = skin.reader(|b| Some(&buffers[b.index()]));
+    let inverse_bind_matrices = match reader.read_inverse_bind_matrices() {
+        Some(matrices) => matrices.map(Matrix4::from).collect::<Vec<_>>(),
+        None => vec![Matrix4::identity(); joints.len()],
+    };
+
+    if inverse_bind_matrices.len() != joints.len() {
+        return Err(anyhow!(
+            "Inverse bind matrix count does not match joint count in skin {}.",
+            skin.index()
+        ));
+    }
+
+    Ok(Skeleton {
+        nodes,
+        joints,
+        inverse_bind_matrices,
+    })
+}
+
+/// animation의 channel과 keyframe을 읽음  
+/// `morph_node`는 morph target을 사용하는 glTF node의 index이고 `morph_target_count`는 morph target의 수임  
+/// scene에 없는 node를 움직이는 channel과 다른 node의 morph target weight를 바꾸는 channel은 무시함
+fn load_animation(
+    animation: &gltf::Animation,
+    indices: &[Option<usize>],
+    morph_node: Option<usize>,
+    morph_target_count: usize,
+    buffers: &[gltf::buffer::Data],
+) -> Result<Animation> {
+    let mut channels = vec![];
+    for channel in animation.channels() {
+        let target = channel.target().node();
+        let Some(node) = indices[target.index()] else {
+            warn!(
+                "Skipping animation channel for node {} outside the scene.",
+                target.index()
+            );
+            continue;
+        };
+
+        let reader = channel.reader(|b| Some(&buffers[b.index()]));
+        let times = reader
+            .read_inputs()
+            .ok_or_else(|| anyhow!("Missing keyframe times in animation {}.", animation.index()))?
+            .collect::<Vec<_>>();
+        let outputs = reader.read_outputs().ok_or_else(|| {
+            anyhow!(
+                "Missing keyframe values in animation {}.",
+                animation.index()
+            )
+        })?;
+
+        let keyframes = match outputs {
+            ReadOutputs::Translations(values) => {
+                Keyframes::Translation(values.map(Vector3::from).collect())
+            }
+            ReadOutputs::Rotations(values) => Keyframes::Rotation(
+                values
+                    .into_f32()
+                    .map(|[x, y, z, w]| Quaternion::new(w, x, y, z))
+                    .collect(),
+            ),
+            ReadOutputs::Scales(values) => Keyframes::Scale(values.map(Vector3::from).collect()),
+            ReadOutputs::MorphTargetWeights(values) if morph_node == Some(target.index()) => {
+                Keyframes::Weights(values.into_f32().collect())
+            }
+            ReadOutputs::MorphTargetWeights(_) => {
+                warn!(
+                    "Skipping morph target animation channel for node {}.",
+                    target.index()
+                );
+                continue;
+            }
+        };
+
+        let interpolation = match channel.sampler().interpolation() {
+            gltf::animation::Interpolation::Step => Interpolation::Step,
+            gltf::animation::Interpolation::Linear => Interpolation::Linear,
+            gltf::animation::Interpolation::CubicSpline => Interpolation::CubicSpline,
+        };
+
+        // cubic spline은 keyframe마다 in-tangent, 값, out-tangent 3개의 값이 있음
+        let values_per_keyframe = match interpolation {
+            Interpolation::CubicSpline => 3,
+            _ => 1,
+        };
+        // weight는 keyframe마다 morph target의 수만큼 값이 있음
+        let (value_count, values_per_time) = match &keyframes {
+            Keyframes::Translation(values) | Keyframes::Scale(values) => {
+                (values.len(), values_per_keyframe)
+            }
+            Keyframes::Rotation(values) => (values.len(), values_per_keyframe),
+            Keyframes::Weights(values) => (values.len(), values_per_keyframe * morph_target_count),
+        };
+        if times.is_empty() || value_count != times.len() * values_per_time {
+            return Err(anyhow!(
+                "Keyframe value count does not match time count in animation {}.",
+                animation.index()
+            ));
+        }
+
+        channels.push(Channel {
+            node,
+            interpolation,
+            times,
+            keyframes,
+        });
+    }
+
+    let duration = channels
+        .iter()
+        .map(|c| c.times[c.times.len() - 1])
+        .fold(0.0, f32::max);
+
+    Ok(Animation {
+        name: animation.name().map(String::from),
+        duration,
+        channels,
+    })
+}
+
+/// `base`번 vertex부터 추가된 vertex의 tangent를 `index_base`번 index부터의 삼각형으로 계산  
+/// 삼각형마다 texture 좌표의 u, v가 증가하는 방향을 구해서 vertex에 누적한 뒤 normal에 수직이 되도록 보정함
+fn generate_tangents(mesh: &mut Mesh, base: usize, index_base: usize) {
+    let count = mesh.positions.len() - base;
+    let mut tangents = vec![Vector3::<f32>::zero(); count];
+    let mut bitangents = vec![Vector3::<f32>::zero(); count];
+
+    for triangle in mesh.indices[index_base..].chunks_exact(3) {
+        let [i0, i1, i2] = [0, 1, 2].map(|i| triangle[i] as usize);
+        let position = |i: usize| Vector3::from(mesh.positions[i]);
+        let tex_coord = |i: usize| Vector2::from(mesh.tex_coords[i]);
+
+        let edge1 = position(i1) - position(i0);
+        let edge2 = position(i2) - position(i0);
+        let delta1 = tex_coord(i1) - tex_coord(i0);
+        let delta2 = tex_coord(i2) - tex_coord(i0);
+
+        // texture 좌표가 한 점이나 선으로 모인 삼각형은 방향을 정할 수 없음
+        let determinant = delta1.x * delta2.y - delta2.x * delta1.y;
+        if determinant.abs() < f32::EPSILON {
+            continue;
+        }
+
+        let tangent = (edge1 * delta2.y - edge2 * delta1.y) / determinant;
+        let bitangent = (edge2 * delta1.x - edge1 * delta2.x) / determinant;
+        for i in [i0, i1, i2] {
+            tangents[i - base] += tangent;
+            bitangents[i - base] += bitangent;
+        }
+    }
+
+    mesh.tangents.extend((0..count).map(|i| {
+        let normal = Vector3::from(mesh.normals[base + i]);
+
+        // Gram-Schmidt로 normal 성분을 제거해서 tangent가 표면에 수직이 되도록 함
+        let mut tangent = tangents[i] - normal * normal.dot(tangents[i]);
+        if tangent.magnitude2() < f32::EPSILON {
+            // 방향을 정할 수 없으면 normal에 수직인 임의의 방향을 사용함
+            let axis = if normal.x.abs() < 0.9 {
+                Vector3::unit_x()
+            } else {
+                Vector3::unit_y()
+            };
+            tangent = normal.cross(axis).cross(normal);
+        }
+        let tangent = tangent.normalize();
+
+        // glTF의 v축은 image의 아래를 향하지만 normal map의 +Y는 위를 향하므로 v의 반대 방향과 비교함
+        let w = if normal.cross(tangent).dot(-bitangents[i]) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+
+        [tangent.x, tangent.y, tangent.z, w]
+    }));
+}
+
+/// glTF material의 factor와 texture를 읽음  
+/// 없는 texture는 factor만 적용되도록 기본값으로 채운 1x1 image를 사용함
+fn load_material(material: &gltf::Material, images: &[gltf::image::Data]) -> Result<Material> {
+    let pbr = material.pbr_metallic_roughness();
+    let normal = material.normal_texture();
+    let occlusion = material.occlusion_texture();
+
+    Ok(Material {
+        base_color_factor: pbr.base_color_factor(),
+        metallic_factor: pbr.metallic_factor(),
+        roughness_factor: pbr.roughness_factor(),
+        normal_scale: normal.as_ref().map_or(1.0, |n| n.scale()),
+        occlusion_strength: occlusion.as_ref().map_or(1.0, |o| o.strength()),
+        albedo: load_image(pbr.base_color_texture().map(|i| i.texture()), images, WHITE)?,
+        normal: load_image(normal.map(|n| n.texture()), images, FLAT_NORMAL)?,
+        metallic_roughness: load_image(
+            pbr.metallic_roughness_texture().map(|i| i.texture()),
+            images,
+            WHITE,
+        )?,
+        occlusion: load_image(occlusion.map(|o| o.texture()), images, WHITE)?,
+    })
+}
+
+/// `texture`가 사용하는 image를 RGBA8 형식으로 변환  
+/// `texture`가 없으면 `default`로 채운 1x1 image를 반환함
+fn load_image(
+    texture: Option<gltf::Texture>,
+    images: &[gltf::image::Data],
+    default: [u8; 4],
+) -> Result<MaterialImage> {
+    let Some(texture) = texture else {
+        return Ok(MaterialImage::solid(default));
+    };
+
+    let image = &images[texture.source().index()];
+
+    // GPU가 3 channel format을 지원하지 않는 경우가 많으므로 모두 4 channel로 맞춤
+    let pixels = match image.format {
+        Format::R8G8B8A8 => image.pixels.clone(),
+        Format::R8G8B8 => image
+            .pixels
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        Format::R8G8 => image
+            .pixels
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[1], 0, 255])
+            .collect(),
+        Format::R8 => image
+            .pixels
+            .iter()
+            .flat_map(|p| [*p, *p, *p, 255])
+            .collect(),
+        format => {
+            return Err(anyhow!(
+                "Unsupported glTF image format {:?} in image {}.",
+                format,
+                texture.source().index()
+            ))
+        }
+    };
+
+    Ok(MaterialImage {
+        width: image.width,
+        height: image.height,
+        pixels,
+    })
+}
@@ -0,0 +1,340 @@
+//! headless chapter나 38_screenshot이 그린 결과를 저장소에 commit된 기준 image(golden image)와 비교하는 기능  
+//! 공유 library를 수정했을 때 chapter의 출력이 바뀌었는지 `tests/golden.rs`에서 확인함  
+//! driver마다 rasterization 결과가 조금씩 다르므로 pixel이 정확히 같은지가 아니라 눈에 띄는 차이가 있는지를 비교함
+
+use anyhow::{anyhow, Result};
+use log::*;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+/// 기준 image를 저장하는 directory
+pub const GOLDEN_DIR: &str = "tests/golden";
+
+/// 설정하면 비교하지 않고 현재 결과로 기준 image를 갱신하는 환경 변수
+pub const UPDATE_GOLDEN_ENV: &str = "VK_TUTORIAL_UPDATE_GOLDEN";
+
+/// headless chapter가 결과를 저장할 경로를 지정하는 환경 변수  
+/// window가 있는 38_screenshot도 이 값이 설정되면 시간을 멈추고 결과를 저장한 뒤 종료함
+pub const HEADLESS_OUTPUT_ENV: &str = "VK_TUTORIAL_HEADLESS_OUTPUT";
+
+/// headless chapter가 결과를 저장하기 전에 그릴 frame 수를 지정하는 환경 변수
+pub const HEADLESS_FRAMES_ENV: &str = "VK_TUTORIAL_HEADLESS_FRAMES";
+
+/// YIQ 색 공간에서 두 색의 차이가 가질 수 있는 최댓값  
+/// 검은색과 흰색의 차이로, `Tolerance::threshold`를 이 값에 대한 비율로 사용함
+const MAX_YIQ_DELTA: f32 = 35215.0;
+
+/// RGBA 8bit image  
+/// `pixels`는 위쪽 행부터 순서대로 `width * height * 4`개의 byte를 담고 있음
+#[derive(Clone, Debug)]
+pub struct GoldenImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl GoldenImage {
+    /// RGBA PNG 파일을 읽음
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        let mut reader = decoder.read_info()?;
+
+        if reader.info().color_type != png::ColorType::Rgba
+            || reader.info().bit_depth != png::BitDepth::Eight
+        {
+            return Err(anyhow!("Image `{}` must be 8bit RGBA.", path.display()));
+        }
+
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels)?;
+        pixels.truncate(info.buffer_size());
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    /// RGBA PNG 파일로 저장하고, 상위 directory가 없으면 함께 생성함
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+
+        Ok(())
+    }
+}
+
+/// 두 image가 같다고 판단하는 기준
+#[derive(Copy, Clone, Debug)]
+pub struct Tolerance {
+    /// pixel 하나의 색 차이가 이 비율(0.0 ~ 1.0)을 넘으면 다른 pixel로 셈
+    pub threshold: f32,
+    /// 전체 pixel 중에서 다른 pixel이 이 비율 이하이면 같은 image로 판단함
+    pub max_mismatch: f32,
+}
+
+impl Default for Tolerance {
+    /// 삼각형 가장자리의 anti-aliasing 차이 정도는 허용하는 기준
+    fn default() -> Self {
+        Self {
+            threshold: 0.1,
+            max_mismatch: 0.001,
+        }
+    }
+}
+
+/// 두 image를 비교한 결과
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    /// 색 차이가 기준을 넘은 pixel 수
+    pub mismatched: usize,
+    /// 전체 pixel 수
+    pub total: usize,
+    /// 다른 pixel은 빨간색, 같은 pixel은 흐린 회색으로 표시한 image
+    pub diff: GoldenImage,
+}
+
+impl Comparison {
+    /// 다른 pixel의 비율
+    pub fn mismatch_ratio(&self) -> f32 {
+        self.mismatched as f32 / self.total.max(1) as f32
+    }
+
+    /// `tolerance`의 기준으로 두 image가 같은지 여부
+    pub fn passed(&self, tolerance: &Tolerance) -> bool {
+        self.mismatch_ratio() <= tolerance.max_mismatch
+    }
+}
+
+/// `actual`과 `expected`를 pixel마다 비교  
+/// 사람이 느끼는 밝기 차이를 반영하도록 RGB가 아니라 YIQ 색 공간에서의 거리를 사용함
+pub fn compare(
+    actual: &GoldenImage,
+    expected: &GoldenImage,
+    tolerance: &Tolerance,
+) -> Result<Comparison> {
+    if actual.width != expected.width || actual.height != expected.height {
+        return Err(anyhow!(
+            "Image size mismatch ({}x{} != {}x{}).",
+            actual.width,
+            actual.height,
+            expected.width,
+            expected.height
+        ));
+    }
+
+    let max_delta = MAX_YIQ_DELTA * tolerance.threshold * tolerance.threshold;
+    let mut mismatched = 0;
+    let mut diff = Vec::with_capacity(actual.pixels.len());
+
+    for (a, e) in actual.pixels.chunks(4).zip(expected.pixels.chunks(4)) {
+        if yiq_delta(a, e) > max_delta {
+            mismatched += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            // 어떤 부분이 다른지 알아보기 쉽도록 기준 image를 흐리게 깔아둠
+            let gray = 192 + (luminance(e) / 4.0) as u8;
+            diff.extend_from_slice(&[gray, gray, gray, 255]);
+        }
+    }
+
+    Ok(Comparison {
+        mismatched,
+        total: (actual.width * actual.height) as usize,
+        diff: GoldenImage {
+            width: actual.width,
+            height: actual.height,
+            pixels: diff,
+        },
+    })
+}
+
+/// `actual`을 `name`의 기준 image와 비교  
+/// 기준 image가 없거나 다르면 error를 반환하고, 다른 경우에는 결과와 차이를 `output_dir`에 저장함  
+/// `UPDATE_GOLDEN_ENV`가 설정되어 있으면 비교하지 않고 기준 image를 갱신함
+pub fn check(
+    name: &str,
+    actual: &GoldenImage,
+    tolerance: &Tolerance,
+    output_dir: impl AsRef<Path>,
+) -> Result<()> {
+    let reference = golden_path(name);
+
+    if env::var_os(UPDATE_GOLDEN_ENV).is_some() {
+        actual.save(&reference)?;
+        info!("Updated golden image `{}`.", reference.display());
+        return Ok(());
+    }
+
+    if !reference.exists() {
+        return Err(anyhow!(
+            "Golden image `{}` does not exist, run with `{}=1` to create it.",
+            reference.display(),
+            UPDATE_GOLDEN_ENV
+        ));
+    }
+
+    let expected = GoldenImage::load(&reference)?;
+    let comparison = compare(actual, &expected, tolerance)?;
+    if comparison.passed(tolerance) {
+        return Ok(());
+    }
+
+    let output_dir = output_dir.as_ref();
+    let actual_path = output_dir.join(format!("{}.actual.png", name));
+    let diff_path = output_dir.join(format!("{}.diff.png", name));
+    actual.save(&actual_path)?;
+    comparison.diff.save(&diff_path)?;
+
+    Err(anyhow!(
+        "`{}` differs from golden image ({} of {} pixels, {:.3}%), see `{}` and `{}`.",
+        name,
+        comparison.mismatched,
+        comparison.total,
+        comparison.mismatch_ratio() * 100.0,
+        actual_path.display(),
+        diff_path.display()
+    ))
+}
+
+/// `name`의 기준 image 경로  
+/// test와 chapter를 어느 directory에서 실행하든 같은 파일을 가리키도록 crate root를 기준으로 함
+pub fn golden_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(GOLDEN_DIR)
+        .join(format!("{}.png", name))
+}
+
+/// headless chapter가 결과를 저장할 경로  
+/// `HEADLESS_OUTPUT_ENV`가 없으면 `default`를 사용함
+pub fn headless_output(default: &str) -> PathBuf {
+    env::var_os(HEADLESS_OUTPUT_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(default))
+}
+
+/// headless chapter가 결과를 저장하기 전에 그릴 frame 수  
+/// `HEADLESS_FRAMES_ENV`가 없거나 올바르지 않으면 1
+pub fn headless_frames() -> u32 {
+    match env::var(HEADLESS_FRAMES_ENV) {
+        Ok(value) => match value.parse::<u32>() {
+            Ok(frames) if frames > 0 => frames,
+            _ => {
+                warn!("Invalid {} (`{}`), using 1.", HEADLESS_FRAMES_ENV, value);
+                1
+            }
+        },
+        Err(_) => 1,
+    }
+}
+
+/// 두 pixel의 YIQ 색 공간에서의 거리의 제곱  
+/// Y(밝기)의 차이에 가장 큰 가중치를 두고, alpha는 비교하지 않음
+fn yiq_delta(a: &[u8], b: &[u8]) -> f32 {
+    let (r1, g1, b1) = (a[0] as f32, a[1] as f32, a[2] as f32);
+    let (r2, g2, b2) = (b[0] as f32, b[1] as f32, b[2] as f32);
+
+    let y = luminance(a) - luminance(b);
+    let i = (0.596 * r1 - 0.274 * g1 - 0.322 * b1) - (0.596 * r2 - 0.274 * g2 - 0.322 * b2);
+    let q = (0.211 * r1 - 0.523 * g1 + 0.312 * b1) - (0.211 * r2 - 0.523 * g2 + 0.312 * b2);
+
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+/// pixel의 밝기 (YIQ의 Y)
+fn luminance(pixel: &[u8]) -> f32 {
+    0.299 * pixel[0] as f32 + 0.587 * pixel[1] as f32 + 0.114 * pixel[2] as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 모든 pixel이 `value`인 회색 image
+    fn gray(width: u32, height: u32, value: u8) -> GoldenImage {
+        GoldenImage {
+            width,
+            height,
+            pixels: [value, value, value, 255].repeat((width * height) as usize),
+        }
+    }
+
+    /// `index`번째 pixel만 `value`로 바꾼 image
+    fn with_pixel(image: &GoldenImage, index: usize, value: u8) -> GoldenImage {
+        let mut image = image.clone();
+        image.pixels[index * 4..index * 4 + 3].fill(value);
+        image
+    }
+
+    #[test]
+    fn identical_images_match() {
+        let image = gray(4, 4, 128);
+        let comparison = compare(&image, &image, &Tolerance::default()).unwrap();
+
+        assert_eq!(comparison.mismatched, 0);
+        assert_eq!(comparison.total, 16);
+        assert!(comparison.passed(&Tolerance::default()));
+    }
+
+    #[test]
+    fn small_difference_is_ignored() {
+        // 회색끼리는 밝기만 다르므로 기본 threshold에서는 약 26 이하의 차이를 무시함
+        let expected = gray(4, 4, 128);
+        let actual = with_pixel(&expected, 5, 148);
+        let comparison = compare(&actual, &expected, &Tolerance::default()).unwrap();
+
+        assert_eq!(comparison.mismatched, 0);
+    }
+
+    #[test]
+    fn large_difference_is_reported() {
+        let expected = gray(4, 4, 128);
+        let actual = with_pixel(&expected, 5, 168);
+        let tolerance = Tolerance::default();
+        let comparison = compare(&actual, &expected, &tolerance).unwrap();
+
+        assert_eq!(comparison.mismatched, 1);
+        assert!(!comparison.passed(&tolerance));
+        assert_eq!(&comparison.diff.pixels[5 * 4..6 * 4], &[255, 0, 0, 255]);
+        assert_ne!(&comparison.diff.pixels[..4], &[255, 0, 0, 255]);
+
+        // 다른 pixel의 비율이 max_mismatch 이하이면 같은 image로 판단함
+        let tolerance = Tolerance {
+            max_mismatch: 1.0 / 16.0,
+            ..tolerance
+        };
+        assert!(comparison.passed(&tolerance));
+    }
+
+    #[test]
+    fn size_mismatch_is_error() {
+        let result = compare(&gray(4, 4, 0), &gray(4, 2, 0), &Tolerance::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn yiq_delta_ignores_alpha() {
+        assert_eq!(yiq_delta(&[10, 20, 30, 255], &[10, 20, 30, 0]), 0.0);
+        assert_eq!(
+            yiq_delta(&[0, 0, 0, 255], &[255, 128, 0, 255]),
+            yiq_delta(&[255, 128, 0, 255], &[0, 0, 0, 255])
+        );
+        // 밝기가 같아도 색이 다르면 차이가 있음
+        assert!(yiq_delta(&[255, 0, 0, 255], &[0, 0, 255, 255]) > 0.0);
+    }
+}
@@ -0,0 +1,56 @@
+//! Radiance HDR(.hdr) 파일을 읽는 loader  
+//! 1.0보다 밝은 값을 그대로 보존해야 하므로 8bit로 변환하지 않고 32bit float으로 읽음
+
+use anyhow::{anyhow, Result};
+use image::codecs::hdr::HdrDecoder;
+use image::ImageDecoder;
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// RGBA 32bit float 형식으로 변환한 HDR image  
+/// `pixels`는 위쪽 행부터 순서대로 `width * height`개의 texel을 담고 있음
+#[derive(Clone, Debug)]
+pub struct HdrImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[f32; 4]>,
+}
+
+impl HdrImage {
+    /// GPU에 upload할 때 필요한 byte 크기
+    pub fn size(&self) -> usize {
+        self.pixels.len() * std::mem::size_of::<[f32; 4]>()
+    }
+}
+
+/// HDR 파일을 읽어서 RGBA 32bit float image로 변환  
+/// GPU가 3 channel float format을 지원하지 않는 경우가 많으므로 alpha를 1.0으로 채워서 4 channel로 맞춤
+pub fn load_hdr(path: impl AsRef<Path>) -> Result<HdrImage> {
+    let path = path.as_ref();
+    let file = BufReader::new(File::open(path)?);
+    let decoder = HdrDecoder::new(file)
+        .map_err(|e| anyhow!("Invalid HDR file `{}`: {}", path.display(), e))?;
+
+    let (width, height) = decoder.dimensions();
+    let mut bytes = vec![0; decoder.total_bytes() as usize];
+    decoder
+        .read_image(&mut bytes)
+        .map_err(|e| anyhow!("Failed to decode HDR file `{}`: {}", path.display(), e))?;
+
+    // decoder는 texel마다 RGB 3개의 f32를 native endian으로 기록함
+    let pixels = bytes
+        .chunks_exact(12)
+        .map(|t| {
+            let channel = |i: usize| f32::from_ne_bytes(t[i * 4..i * 4 + 4].try_into().unwrap());
+            [channel(0), channel(1), channel(2), 1.0]
+        })
+        .collect();
+
+    Ok(HdrImage {
+        width,
+        height,
+        pixels,
+    })
+}
@@ -0,0 +1,409 @@
+//! Vulkan instance와 debug messenger
+
+use anyhow::{anyhow, Result};
+use log::*;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::window as vk_window;
+use vulkanalia::Version;
+use winit::window::Window;
+
+use vulkanalia::vk::ExtDebugUtilsExtension;
+
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::config::{self, Config};
+
+/// macOS에서 Vulkan을 사용할 때 필요한 버전  
+pub const PORTABILITY_MACOS_VERSION: Version = Version::new(1, 3, 216);
+
+/// validation layer와 debug messenger를 사용할지 결정하는 기본값  
+/// debug 빌드에서만 활성화하도록 설정함  
+/// 설정의 `validation`으로 바꿀 수 있으므로 messenger가 생성되었는지는 반환된 핸들이 null인지로 확인해야 함
+pub const VALIDATION_ENABLED: bool = cfg!(debug_assertions);
+
+/// GPU-assisted validation을 켜기 위한 환경 변수 (`--gpu-assisted-validation`과 같음)
+pub const GPU_ASSISTED_VALIDATION_ENV: &str = "VK_TUTORIAL_GPU_ASSISTED_VALIDATION";
+
+/// best practices 검사를 켜기 위한 환경 변수 (`--best-practices`와 같음)
+pub const BEST_PRACTICES_ENV: &str = "VK_TUTORIAL_BEST_PRACTICES";
+
+/// synchronization validation을 켜기 위한 환경 변수 (`--sync-validation`과 같음)
+pub const SYNC_VALIDATION_ENV: &str = "VK_TUTORIAL_SYNC_VALIDATION";
+
+/// 설정과 관계없이 항상 출력하지 않는 debug 메세지의 id  
+/// window 크기가 바뀌는 도중에 swapchain을 다시 만들면 surface의 extent와 window 크기가 잠깐 어긋나는 경우처럼  
+/// tutorial의 코드가 잘못되지 않았는데도 자주 출력되는 메세지임
+pub const MUTED_MESSAGES: &[&str] = &[
+    "VUID-VkSwapchainCreateInfoKHR-imageExtent-01274",
+    "UNASSIGNED-BestPractices-vkCreateInstance-specialuse-extension-debugging",
+];
+
+/// 같은 id의 메세지를 지금까지 받은 횟수  
+/// 반복되는 메세지는 횟수가 2의 거듭제곱일 때만 출력함
+static MESSAGE_COUNTS: Mutex<BTreeMap<String, u32>> = Mutex::new(BTreeMap::new());
+
+/// 지금까지 받은 ERROR 심각도의 validation 메세지의 수
+static VALIDATION_ERRORS: AtomicUsize = AtomicUsize::new(0);
+
+/// standard validation layer를 사용함  
+/// validation layer는 Vulkan function call을 후킹해서 추가적인 연산을 적용함
+pub const VALIDATION_LAYER: vk::ExtensionName =
+    vk::ExtensionName::from_bytes(b"VK_LAYER_KHRONOS_validation");
+
+/// window에 그리기 위한 instance 생성  
+/// validation layer가 활성화된 경우 debug messenger도 함께 생성해서 반환하고, 아니면 null 핸들을 반환함
+pub unsafe fn create_instance(
+    window: &Window,
+    entry: &Entry,
+) -> Result<(Instance, vk::DebugUtilsMessengerEXT)> {
+    create_instance_with_version(window, entry, Version::new(1, 0, 0))
+}
+
+/// `api_version`의 Vulkan을 사용하는 window용 instance 생성  
+/// Vulkan 1.1 이후에 추가된 기능을 사용하는 chapter에서 사용함  
+/// instance의 버전은 loader가 지원하는 버전만 확인하므로 device의 지원 여부는 따로 확인해야 함
+pub unsafe fn create_instance_with_version(
+    window: &Window,
+    entry: &Entry,
+    api_version: Version,
+) -> Result<(Instance, vk::DebugUtilsMessengerEXT)> {
+    // 필수 instance extension들을 가져옴
+    let extensions = vk_window::get_required_instance_extensions(window)
+        .iter()
+        // 이름들을 전부 const * const c_char로 변환
+        .map(|e| e.as_ptr())
+        .collect::<Vec<_>>();
+
+    create_instance_with_extensions(entry, extensions, api_version)
+}
+
+/// HDR surface format을 사용할 수 있는 window용 instance 생성  
+/// `VK_EXT_swapchain_colorspace`를 지원하면 활성화하고, 활성화했는지 여부를 함께 반환함  
+/// extension이 없으면 surface가 SRGB_NONLINEAR 외의 color space를 반환하지 않으므로 SDR로만 출력할 수 있음
+pub unsafe fn create_hdr_instance(
+    window: &Window,
+    entry: &Entry,
+) -> Result<(Instance, vk::DebugUtilsMessengerEXT, bool)> {
+    let mut extensions = vk_window::get_required_instance_extensions(window)
+        .iter()
+        .map(|e| e.as_ptr())
+        .collect::<Vec<_>>();
+
+    let colorspace_supported = entry
+        .enumerate_instance_extension_properties(None)?
+        .iter()
+        .any(|e| e.extension_name == vk::EXT_SWAPCHAIN_COLORSPACE_EXTENSION.name);
+    if colorspace_supported {
+        extensions.push(vk::EXT_SWAPCHAIN_COLORSPACE_EXTENSION.name.as_ptr());
+    } else {
+        info!("VK_EXT_swapchain_colorspace is not supported, HDR output is disabled.");
+    }
+
+    let (instance, messenger) =
+        create_instance_with_extensions(entry, extensions, Version::new(1, 0, 0))?;
+
+    Ok((instance, messenger, colorspace_supported))
+}
+
+/// exclusive fullscreen을 사용할 수 있는 window용 instance 생성  
+/// `VK_EXT_full_screen_exclusive`가 의존하는 `VK_KHR_get_surface_capabilities2`를 지원하면 활성화하고, 활성화했는지 여부를 함께 반환함  
+/// device extension은 Windows에서만 제공되므로 다른 플랫폼에서는 항상 Vulkan 1.0 instance를 생성함
+pub unsafe fn create_full_screen_instance(
+    window: &Window,
+    entry: &Entry,
+) -> Result<(Instance, vk::DebugUtilsMessengerEXT, bool)> {
+    let mut extensions = vk_window::get_required_instance_extensions(window)
+        .iter()
+        .map(|e| e.as_ptr())
+        .collect::<Vec<_>>();
+
+    // `VK_KHR_get_physical_device_properties2`에도 의존하므로 해당 기능이 core인 Vulkan 1.1 instance가 필요함
+    let full_screen_version = Version::new(1, 1, 0);
+    let surface_capabilities2_supported = cfg!(target_os = "windows")
+        && entry.version()? >= full_screen_version
+        && entry
+            .enumerate_instance_extension_properties(None)?
+            .iter()
+            .any(|e| e.extension_name == vk::KHR_GET_SURFACE_CAPABILITIES2_EXTENSION.name);
+
+    let api_version = if surface_capabilities2_supported {
+        extensions.push(vk::KHR_GET_SURFACE_CAPABILITIES2_EXTENSION.name.as_ptr());
+        full_screen_version
+    } else {
+        info!("VK_KHR_get_surface_capabilities2 is not available, exclusive fullscreen control is disabled.");
+        Version::new(1, 0, 0)
+    };
+
+    let (instance, messenger) = create_instance_with_extensions(entry, extensions, api_version)?;
+
+    Ok((instance, messenger, surface_capabilities2_supported))
+}
+
+/// window와 surface 없이 offscreen으로만 그리기 위한 instance 생성  
+/// surface extension을 요구하지 않으므로 display server가 없는 환경에서도 사용할 수 있음
+pub unsafe fn create_headless_instance(
+    entry: &Entry,
+) -> Result<(Instance, vk::DebugUtilsMessengerEXT)> {
+    create_instance_with_extensions(entry, vec![], Version::new(1, 0, 0))
+}
+
+/// `extensions`와 validation, portability에 필요한 extension을 활성화한 instance 생성
+unsafe fn create_instance_with_extensions(
+    entry: &Entry,
+    mut extensions: Vec<*const c_char>,
+    api_version: Version,
+) -> Result<(Instance, vk::DebugUtilsMessengerEXT)> {
+    // Vulkan 1.0만 지원하는 loader는 1.0보다 높은 api_version으로 instance를 만들 수 없음
+    let loader_version = entry.version()?;
+    if loader_version < api_version {
+        return Err(anyhow!(
+            "Vulkan {} is not supported by the loader (version {}).",
+            api_version,
+            loader_version
+        ));
+    }
+
+    // 애플리케이션 정보를 설정
+    // 보통 optional이지만, 애플리케이션을 최적화하는데 유용한 정보를 드라이버에 제공할 수 있음
+    // Vulkan은 UTF-8 문자열을 사용하므로 문자열 끝에 NULL 문자를 추가해야 함
+    let application_info = vk::ApplicationInfo::builder()
+        .application_name(b"Vulkan Tutorial\0")
+        .application_version(vk::make_version(1, 0, 0))
+        .engine_name(b"No Engine\0")
+        .engine_version(vk::make_version(1, 0, 0))
+        .api_version(vk::make_version(
+            api_version.major,
+            api_version.minor,
+            api_version.patch,
+        ));
+
+    // 사용 가능한 레이어를 가져옴
+    let available_layers = entry
+        // 모든 레이어를 가져옴
+        .enumerate_instance_layer_properties()?
+        .iter()
+        // 레이어의 이름을 HashSet에 모음
+        .map(|l| l.layer_name)
+        .collect::<HashSet<_>>();
+
+    // 설정으로 validation layer를 켜면 빌드 종류와 관계없이 messenger를 만들어서 filter와 abort 설정을 적용함
+    let config = config::get()?;
+    let validation = config.validation;
+
+    // validation layer가 요청되었지만 사용 가능한 레이어에 없다면 에러를 반환
+    if validation && !available_layers.contains(&VALIDATION_LAYER) {
+        return Err(anyhow!("Validation layer requested but not supported."));
+    }
+
+    // validation layer의 활성 여부에 따라 레이어 목록을 설정
+    let layers = if validation {
+        vec![VALIDATION_LAYER.as_ptr()]
+    } else {
+        Vec::new()
+    };
+
+    if validation {
+        // validation layer가 활성화된 경우에만 디버그 유틸 확장 추가
+        // 디버그 메세지를 핸들링하기 위해 필요함
+        // 메세지를 debug_callback함수로 전달해서 핸들링 할 것임
+        extensions.push(vk::EXT_DEBUG_UTILS_EXTENSION.name.as_ptr());
+    }
+
+    // validation layer가 제공하는 `VK_EXT_validation_features`로 기본 검사 외의 추가 검사를 켬
+    let validation_features = if validation {
+        enabled_validation_features(config)
+    } else {
+        if config.gpu_assisted_validation || config.best_practices || config.sync_validation {
+            warn!("Validation features are ignored because the validation layer is disabled.");
+        }
+        vec![]
+    };
+    if !validation_features.is_empty() {
+        extensions.push(vk::EXT_VALIDATION_FEATURES_EXTENSION.name.as_ptr());
+    }
+
+    // Required by Vulkan SDK on macOS since 1.3.216.
+    let flags = if cfg!(target_os = "macos") && entry.version()? >= PORTABILITY_MACOS_VERSION {
+        info!("Enabling extensions for macOS portability.");
+        extensions.push(
+            vk::KHR_GET_PHYSICAL_DEVICE_PROPERTIES2_EXTENSION
+                .name
+                .as_ptr(),
+        );
+        extensions.push(vk::KHR_PORTABILITY_ENUMERATION_EXTENSION.name.as_ptr());
+        vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR
+    } else {
+        vk::InstanceCreateFlags::empty()
+    };
+
+    // Vulkan 인스턴스 생성하기 위한 정보를 설정
+    let mut info = vk::InstanceCreateInfo::builder()
+        .application_info(&application_info)
+        // 사용할 레이어 목록을 설정
+        .enabled_layer_names(&layers)
+        // 사용할 확장 목록을 설정
+        .enabled_extension_names(&extensions)
+        .flags(flags);
+
+    // 디버그 정보를 설정
+    let mut debug_info = vk::DebugUtilsMessengerCreateInfoEXT::builder()
+        // 알림을 받을 심각도를 설정
+        // 사용할수 없을수도 있는 모든 flags를 사용하지만, 사용하지 않는 경우 문제가 없음
+        // 그런 플래그를 사용하면 validation error를 발생시킴
+        .message_severity(vk::DebugUtilsMessageSeverityFlagsEXT::all())
+        // 알림을 받을 메세지 타입을 설정
+        .message_type(
+            vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+                | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
+                | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
+        )
+        // 디버그 콜백 설정
+        .user_callback(Some(debug_callback));
+
+    if validation {
+        info = info.push_next(&mut debug_info);
+    }
+
+    let mut features_info =
+        vk::ValidationFeaturesEXT::builder().enabled_validation_features(&validation_features);
+
+    if !validation_features.is_empty() {
+        info = info.push_next(&mut features_info);
+    }
+
+    let instance = entry.create_instance(&info, None)?;
+
+    // debug info를 instance에 등록
+    // 이것도 instance가 파괴되기 전에 해제해야 함
+    let messenger = if validation {
+        instance.create_debug_utils_messenger_ext(&debug_info, None)?
+    } else {
+        vk::DebugUtilsMessengerEXT::null()
+    };
+
+    Ok((instance, messenger))
+}
+
+/// 설정으로 켠 validation layer의 추가 검사를 반환  
+/// 검사마다 비용이 크게 다르므로 켠 검사와 그 overhead를 log로 출력함
+fn enabled_validation_features(config: &Config) -> Vec<vk::ValidationFeatureEnableEXT> {
+    let mut enables = vec![];
+
+    if config.gpu_assisted_validation {
+        info!("GPU-assisted validation enabled: shaders are instrumented to catch out-of-bounds descriptor and buffer access (high CPU and GPU overhead, reserves the last descriptor set slot).");
+        enables.push(vk::ValidationFeatureEnableEXT::GPU_ASSISTED);
+        // 계측 코드가 사용할 descriptor set slot을 layer가 예약하도록 함
+        enables.push(vk::ValidationFeatureEnableEXT::GPU_ASSISTED_RESERVE_BINDING_SLOT);
+    }
+
+    if config.best_practices {
+        info!("Best practices validation enabled: warns about valid but inefficient API usage (low CPU overhead).");
+        enables.push(vk::ValidationFeatureEnableEXT::BEST_PRACTICES);
+    }
+
+    if config.sync_validation {
+        info!("Synchronization validation enabled: tracks every resource access to find missing barriers (high CPU overhead, grows with the number of commands).");
+        enables.push(vk::ValidationFeatureEnableEXT::SYNCHRONIZATION_VALIDATION);
+    }
+
+    enables
+}
+
+/// Vulkan에서 발생하는 디버그 메세지를 처리하기 위한 콜백 함수  
+/// Vulkan이 Rust함수를 호출하도록 허용하기 위해서 `extern "system"`을 사용함
+extern "system" fn debug_callback(
+    // 메세지의 심각도
+    severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+    // 메세지의 타입
+    // 일반, 검증, 성능등의 타입이 있음
+    type_: vk::DebugUtilsMessageTypeFlagsEXT,
+    // 메세지의 데이터
+    data: *const vk::DebugUtilsMessengerCallbackDataEXT,
+    _: *mut c_void,
+) -> vk::Bool32 {
+    let data = unsafe { *data };
+    let message = unsafe { CStr::from_ptr(data.message) }.to_string_lossy();
+
+    // 이름이 없는 메세지도 있으므로 번호를 함께 사용함
+    let id_name = if data.message_id_name.is_null() {
+        None
+    } else {
+        Some(unsafe { CStr::from_ptr(data.message_id_name) }.to_string_lossy())
+    };
+    let id_number = format!("{:#x}", data.message_id_number as u32);
+    let matches = |id: &str| id_name.as_deref() == Some(id) || id.eq_ignore_ascii_case(&id_number);
+
+    // instance를 만들 때 이미 읽었으므로 실패하지 않지만, callback에서 에러를 반환할 수 없으므로 실패하면 무시함
+    let config = config::get().ok();
+
+    let is_validation_error = severity >= vk::DebugUtilsMessageSeverityFlagsEXT::ERROR
+        && type_.contains(vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION);
+    if is_validation_error {
+        VALIDATION_ERRORS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let muted = MUTED_MESSAGES.iter().any(|id| matches(id))
+        || config.is_some_and(|c| {
+            c.mute_messages.iter().any(|id| matches(id))
+                || (!c.message_filter.is_empty() && !c.message_filter.iter().any(|id| matches(id)))
+        });
+
+    if !muted {
+        let key = id_name.as_deref().unwrap_or(&id_number).to_string();
+        let count = {
+            let mut counts = MESSAGE_COUNTS.lock().unwrap_or_else(|e| e.into_inner());
+            let count = counts.entry(key).or_insert(0);
+            *count += 1;
+            *count
+        };
+
+        // 매 frame 반복되는 메세지가 log를 덮지 않도록 1, 2, 4, 8, ...번째만 출력함
+        if count.is_power_of_two() {
+            let repeated = if count > 1 {
+                format!(" (repeated {} times)", count)
+            } else {
+                String::new()
+            };
+
+            if severity >= vk::DebugUtilsMessageSeverityFlagsEXT::ERROR {
+                error!("({:?}) {}{}", type_, message, repeated);
+            } else if severity >= vk::DebugUtilsMessageSeverityFlagsEXT::WARNING {
+                warn!("({:?}) {}{}", type_, message, repeated);
+            } else if severity >= vk::DebugUtilsMessageSeverityFlagsEXT::INFO {
+                debug!("({:?}) {}{}", type_, message, repeated);
+            } else {
+                trace!("({:?}) {}{}", type_, message, repeated);
+            }
+        }
+    }
+
+    // Vulkan을 거쳐서 호출되는 callback에서는 unwind할 수 없으므로 panic 대신 abort함
+    // mute한 메세지라도 에러이므로 중단함
+    if is_validation_error && config.is_some_and(|c| c.abort_on_validation_error) {
+        error!(
+            "Aborting on validation error ({}).",
+            id_name.as_deref().unwrap_or(&id_number)
+        );
+        std::process::abort();
+    }
+
+    vk::FALSE
+}
+
+/// 지금까지 받은 ERROR 심각도의 validation 메세지의 수  
+/// 중단하지 않고 끝까지 실행한 뒤 에러가 있었는지 확인할 때 사용함
+pub fn validation_error_count() -> usize {
+    VALIDATION_ERRORS.load(Ordering::Relaxed)
+}
+
+/// 2번 이상 받은 debug 메세지와 받은 횟수를 log로 출력
+pub fn log_repeated_messages() {
+    let counts = MESSAGE_COUNTS.lock().unwrap_or_else(|e| e.into_inner());
+    for (id, count) in counts.iter().filter(|(_, c)| **c > 1) {
+        info!("Debug message `{}` was received {} times.", id, count);
+    }
+}
@@ -0,0 +1,37 @@
+//! 여러 chapter에서 공통으로 사용하는 Vulkan setup 코드  
+//! 05 ~ 20 chapter에서 하나씩 작성한 코드를 모아둔 것으로, 이후의 chapter들은 이 crate를 가져다 사용함
+
+#![allow(clippy::missing_safety_doc)]
+
+pub mod allocator;
+pub mod animation;
+pub mod assets;
+pub mod camera;
+pub mod capture;
+pub mod config;
+pub mod debug;
+pub mod deletion;
+pub mod descriptors;
+pub mod device;
+pub mod diagnostics;
+pub mod error;
+pub mod gamepad;
+pub mod gltf_model;
+pub mod golden;
+pub mod hdr;
+pub mod instance;
+pub mod memory;
+pub mod owned;
+pub mod pacing;
+pub mod pipeline;
+pub mod profiler;
+pub mod scene;
+pub mod staging;
+pub mod swapchain;
+pub mod terrain;
+pub mod text;
+pub mod texture;
+pub mod timer;
+pub mod trace;
+pub mod ui;
+pub mod uniform;
@@ -0,0 +1,200 @@
+//! `VK_EXT_memory_budget`으로 memory heap마다 사용량과 budget을 조회하는 통계  
+//! budget은 다른 프로세스의 사용량까지 고려해서 이 프로세스가 할당해도 되는 크기를 driver가 추정한 값이므로  
+//! heap 크기보다 작을 수 있고, usage가 budget을 넘으면 할당이 실패하거나 성능이 떨어질 수 있음
+
+use anyhow::Result;
+use log::*;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::Version;
+
+use vulkanalia::vk::InstanceV1_1;
+
+use std::time::{Duration, Instant};
+
+use crate::device::supports_device_extension;
+
+/// `vkGetPhysicalDeviceMemoryProperties2`가 core에 포함된 Vulkan 버전
+pub const MEMORY_BUDGET_VERSION: Version = Version::new(1, 1, 0);
+
+/// physical device가 `VK_EXT_memory_budget`을 사용할 수 있는지 확인  
+/// 조회에 사용하는 `vkGetPhysicalDeviceMemoryProperties2`가 필요하므로 instance와 device 모두 Vulkan 1.1 이상이어야 함
+pub unsafe fn supports_memory_budget(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    instance_version: Version,
+) -> Result<bool> {
+    let properties = instance.get_physical_device_properties(physical_device);
+
+    Ok(instance_version >= MEMORY_BUDGET_VERSION
+        && properties.api_version >= u32::from(MEMORY_BUDGET_VERSION)
+        && supports_device_extension(
+            instance,
+            physical_device,
+            vk::EXT_MEMORY_BUDGET_EXTENSION.name,
+        )?)
+}
+
+/// memory heap 하나의 크기와 사용량
+#[derive(Copy, Clone, Debug, Default)]
+pub struct HeapStats {
+    /// heap 전체의 크기
+    pub size: vk::DeviceSize,
+    /// 이 프로세스가 사용할 수 있다고 driver가 추정한 크기  
+    /// extension을 사용할 수 없으면 heap의 크기와 같음
+    pub budget: vk::DeviceSize,
+    /// 이 프로세스가 현재 사용하는 크기  
+    /// extension을 사용할 수 없으면 알 수 없으므로 0임
+    pub usage: vk::DeviceSize,
+    /// 첫 조회에서 측정한 사용량  
+    /// swapchain image처럼 driver가 먼저 할당한 memory가 포함되므로 `usage`에서 빼면 tutorial이 할당한 양이 됨
+    pub baseline: vk::DeviceSize,
+    /// DEVICE_LOCAL heap(VRAM)인지 여부
+    pub device_local: bool,
+}
+
+impl HeapStats {
+    /// 첫 조회 이후로 늘어난 사용량
+    pub fn application_usage(&self) -> vk::DeviceSize {
+        self.usage.saturating_sub(self.baseline)
+    }
+}
+
+/// 일정한 간격으로 heap의 사용량과 budget을 조회하는 통계  
+/// 조회할 때마다 driver가 값을 다시 계산하므로 매 frame 호출하지 않고 `interval`마다 조회함
+#[derive(Clone, Debug)]
+pub struct MemoryStats {
+    /// `VK_EXT_memory_budget`을 활성화했는지 여부
+    pub budget_supported: bool,
+    interval: Duration,
+    last_query: Option<Instant>,
+    heaps: Vec<HeapStats>,
+}
+
+impl MemoryStats {
+    /// `interval`마다 조회하는 통계를 생성  
+    /// `budget_supported`가 false면 heap의 크기만 조회함
+    pub fn new(budget_supported: bool, interval: Duration) -> Self {
+        Self {
+            budget_supported,
+            interval,
+            last_query: None,
+            heaps: vec![],
+        }
+    }
+
+    /// 마지막 조회 이후 `interval`이 지났으면 다시 조회하고 조회했는지 여부를 반환  
+    /// 처음 호출할 때 측정한 사용량을 baseline으로 사용하므로 resource를 만들기 전에 한 번 호출해야 함
+    pub unsafe fn update(
+        &mut self,
+        instance: &Instance,
+        physical_device: vk::PhysicalDevice,
+    ) -> bool {
+        if self
+            .last_query
+            .is_some_and(|last| last.elapsed() < self.interval)
+        {
+            return false;
+        }
+
+        self.query(instance, physical_device);
+        true
+    }
+
+    /// 간격과 관계없이 바로 조회
+    pub unsafe fn query(&mut self, instance: &Instance, physical_device: vk::PhysicalDevice) {
+        let (memory, budget) = if self.budget_supported {
+            let mut budget = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+            let mut properties =
+                vk::PhysicalDeviceMemoryProperties2::builder().push_next(&mut budget);
+            instance.get_physical_device_memory_properties2(physical_device, &mut properties);
+            (properties.memory_properties, Some(budget))
+        } else {
+            (
+                instance.get_physical_device_memory_properties(physical_device),
+                None,
+            )
+        };
+
+        let first = self.heaps.is_empty();
+        self.heaps = (0..memory.memory_heap_count as usize)
+            .map(|i| {
+                let heap = memory.memory_heaps[i];
+                let (budget, usage) = match &budget {
+                    Some(b) => (b.heap_budget[i], b.heap_usage[i]),
+                    None => (heap.size, 0),
+                };
+                let baseline = if first {
+                    usage
+                } else {
+                    self.heaps.get(i).map_or(usage, |h| h.baseline)
+                };
+
+                HeapStats {
+                    size: heap.size,
+                    budget,
+                    usage,
+                    baseline,
+                    device_local: heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL),
+                }
+            })
+            .collect();
+
+        self.last_query = Some(Instant::now());
+    }
+
+    /// 마지막으로 조회한 heap별 통계
+    pub fn heaps(&self) -> &[HeapStats] {
+        &self.heaps
+    }
+
+    /// DEVICE_LOCAL heap에서 첫 조회 이후로 늘어난 사용량의 합
+    pub fn device_local_usage(&self) -> vk::DeviceSize {
+        self.heaps
+            .iter()
+            .filter(|h| h.device_local)
+            .map(|h| h.application_usage())
+            .sum()
+    }
+
+    /// heap별 통계를 log로 출력
+    pub fn log(&self) {
+        for (i, heap) in self.heaps.iter().enumerate() {
+            let kind = if heap.device_local {
+                " (device local)"
+            } else {
+                ""
+            };
+            if self.budget_supported {
+                info!(
+                    "Heap {}{}: usage {} / budget {} (+{} since startup), size {}.",
+                    i,
+                    kind,
+                    format_bytes(heap.usage),
+                    format_bytes(heap.budget),
+                    format_bytes(heap.application_usage()),
+                    format_bytes(heap.size),
+                );
+            } else {
+                info!("Heap {}{}: size {}.", i, kind, format_bytes(heap.size));
+            }
+        }
+    }
+}
+
+/// byte 단위의 크기를 읽기 쉬운 단위로 변환
+pub fn format_bytes(bytes: vk::DeviceSize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -0,0 +1,115 @@
+//! drop될 때 Vulkan 오브젝트를 파괴하는 wrapper 타입들  
+//! 오브젝트를 만든 device를 `Arc`로 함께 들고 있으므로 device보다 먼저 drop되기만 하면 됨
+
+use vulkanalia::prelude::v1_0::*;
+
+use vulkanalia::vk::KhrSwapchainExtension;
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::swapchain::Swapchain;
+
+/// `$handle`을 소유하고 drop될 때 `$destroy`로 파괴하는 wrapper 타입을 정의  
+/// 기본값은 null 핸들이며 아무것도 파괴하지 않음
+macro_rules! owned_handle {
+    ($(#[$meta:meta])* $name:ident, $handle:ty, $destroy:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Default)]
+        pub struct $name {
+            device: Option<Arc<Device>>,
+            handle: $handle,
+        }
+
+        impl $name {
+            pub fn new(device: &Arc<Device>, handle: $handle) -> Self {
+                Self {
+                    device: Some(device.clone()),
+                    handle,
+                }
+            }
+        }
+
+        impl Deref for $name {
+            type Target = $handle;
+
+            fn deref(&self) -> &Self::Target {
+                &self.handle
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                if let Some(device) = &self.device {
+                    unsafe { device.$destroy(self.handle, None) };
+                }
+            }
+        }
+    };
+}
+
+owned_handle!(OwnedImage, vk::Image, destroy_image);
+owned_handle!(OwnedImageView, vk::ImageView, destroy_image_view);
+owned_handle!(OwnedSampler, vk::Sampler, destroy_sampler);
+owned_handle!(OwnedBuffer, vk::Buffer, destroy_buffer);
+owned_handle!(OwnedDeviceMemory, vk::DeviceMemory, free_memory);
+owned_handle!(OwnedRenderPass, vk::RenderPass, destroy_render_pass);
+owned_handle!(
+    OwnedPipelineLayout,
+    vk::PipelineLayout,
+    destroy_pipeline_layout
+);
+owned_handle!(OwnedPipeline, vk::Pipeline, destroy_pipeline);
+owned_handle!(OwnedFramebuffer, vk::Framebuffer, destroy_framebuffer);
+owned_handle!(
+    /// pool에서 할당된 command buffer는 pool이 파괴될 때 함께 해제됨
+    OwnedCommandPool,
+    vk::CommandPool,
+    destroy_command_pool
+);
+owned_handle!(
+    OwnedDescriptorSetLayout,
+    vk::DescriptorSetLayout,
+    destroy_descriptor_set_layout
+);
+owned_handle!(
+    /// pool에서 할당된 descriptor set은 pool이 파괴될 때 함께 해제됨
+    OwnedDescriptorPool,
+    vk::DescriptorPool,
+    destroy_descriptor_pool
+);
+owned_handle!(OwnedSemaphore, vk::Semaphore, destroy_semaphore);
+owned_handle!(OwnedFence, vk::Fence, destroy_fence);
+
+/// swapchain과 swapchain image의 속성을 소유하는 wrapper  
+/// swapchain image는 swapchain이 파괴될 때 함께 파괴됨
+#[derive(Debug, Default)]
+pub struct OwnedSwapchain {
+    device: Option<Arc<Device>>,
+    swapchain: Swapchain,
+}
+
+impl OwnedSwapchain {
+    pub fn new(device: &Arc<Device>, swapchain: Swapchain) -> Self {
+        Self {
+            device: Some(device.clone()),
+            swapchain,
+        }
+    }
+}
+
+impl Deref for OwnedSwapchain {
+    type Target = Swapchain;
+
+    fn deref(&self) -> &Self::Target {
+        &self.swapchain
+    }
+}
+
+impl Drop for OwnedSwapchain {
+    fn drop(&mut self) {
+        if let Some(device) = &self.device {
+            unsafe { device.destroy_swapchain_khr(self.swapchain.handle, None) };
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! frame rate를 제한하는 frame limiter와 acquire부터 present까지의 latency를 측정하는 tracker  
+//! MAILBOX와 FIFO처럼 present mode에 따라 달라지는 latency를 비교하기 위해 사용함
+
+use anyhow::Result;
+use vulkanalia::prelude::v1_0::*;
+
+use vulkanalia::vk::GoogleDisplayTimingExtension;
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::timer::{average, push};
+
+/// frame rate를 제한하는 방법
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LimitMethod {
+    /// 다음 frame을 시작하기 전에 CPU에서 sleep
+    Sleep,
+    /// `VK_GOOGLE_display_timing`으로 image를 표시할 시간을 presentation engine에 지정  
+    /// CPU는 기다리지 않으므로 MAILBOX에서는 표시되지 않고 버려지는 frame이 생길 수 있음
+    DisplayTiming,
+}
+
+/// 목표 frame rate에 맞춰 frame을 시작하도록 대기하는 frame limiter
+#[derive(Clone, Debug)]
+pub struct FrameLimiter {
+    /// frame rate를 제한하는 방법
+    pub method: LimitMethod,
+    target_fps: Option<f32>,
+    next_frame: Instant,
+}
+
+impl Default for FrameLimiter {
+    fn default() -> Self {
+        Self {
+            method: LimitMethod::Sleep,
+            target_fps: None,
+            next_frame: Instant::now(),
+        }
+    }
+}
+
+impl FrameLimiter {
+    /// 목표 frame rate  
+    /// `None`이면 frame rate를 제한하지 않음
+    pub fn target_fps(&self) -> Option<f32> {
+        self.target_fps
+    }
+
+    /// 0보다 작거나 같은 값은 `None`과 같이 frame rate를 제한하지 않음
+    pub fn set_target_fps(&mut self, target_fps: Option<f32>) {
+        self.target_fps = target_fps.filter(|fps| *fps > 0.0);
+        self.next_frame = Instant::now();
+    }
+
+    /// 목표 frame rate에 해당하는 frame time
+    pub fn frame_time(&self) -> Option<Duration> {
+        self.target_fps
+            .map(|fps| Duration::from_secs_f32(1.0 / fps))
+    }
+
+    /// `LimitMethod::Sleep`이면 목표 frame time이 지날 때까지 현재 thread를 재우고 sleep한 시간을 반환  
+    /// frame이 목표보다 오래 걸려서 늦어진 경우에는 밀린 frame을 따라잡지 않고 현재 시간부터 다시 간격을 맞춤
+    pub fn wait(&mut self) -> Duration {
+        let frame_time = match (self.method, self.frame_time()) {
+            (LimitMethod::Sleep, Some(frame_time)) => frame_time,
+            _ => return Duration::ZERO,
+        };
+
+        let now = Instant::now();
+        let sleep = self.next_frame.saturating_duration_since(now);
+        if !sleep.is_zero() {
+            thread::sleep(sleep);
+        }
+
+        self.next_frame = (self.next_frame + frame_time).max(Instant::now());
+        sleep
+    }
+}
+
+/// `VK_GOOGLE_display_timing`으로 image를 표시할 시간을 지정하고 실제로 표시된 시간을 읽어옴  
+/// swapchain마다 present id와 refresh 주기가 다르므로 swapchain을 다시 생성하면 새로 만들어야 함
+#[derive(Clone, Debug, Default)]
+pub struct DisplayTiming {
+    /// display의 refresh 주기
+    pub refresh_duration: Duration,
+    next_present_id: u32,
+    // 가장 최근에 표시된 image의 present id와 표시된 시간(ns)
+    last_presented: Option<(u32, u64)>,
+}
+
+impl DisplayTiming {
+    /// `swapchain`이 표시되는 display의 refresh 주기를 읽어옴
+    pub unsafe fn new(device: &Device, swapchain: vk::SwapchainKHR) -> Result<Self> {
+        let refresh_cycle = device.get_refresh_cycle_duration_google(swapchain)?;
+
+        Ok(Self {
+            refresh_duration: Duration::from_nanos(refresh_cycle.refresh_duration),
+            ..Default::default()
+        })
+    }
+
+    /// 다음 present에 함께 전달할 present time  
+    /// `frame_time`이 있으면 마지막으로 표시된 image부터 refresh 주기의 배수로 올림한 간격마다 표시되도록 지정함  
+    /// 아직 표시된 image가 없거나 `frame_time`이 없으면 0을 지정해서 presentation engine이 가능한 빨리 표시하도록 함
+    pub fn next_present_time(&mut self, frame_time: Option<Duration>) -> vk::PresentTimeGOOGLE {
+        let present_id = self.next_present_id;
+        self.next_present_id = self.next_present_id.wrapping_add(1);
+
+        let refresh = self.refresh_duration.as_nanos() as u64;
+        let desired_present_time = match (frame_time, self.last_presented) {
+            (Some(frame_time), Some((last_id, last_time))) if refresh > 0 => {
+                // refresh 주기 중간에는 표시할 수 없으므로 목표보다 빠르게 표시되지 않도록 올림함
+                let interval = (frame_time.as_nanos() as u64).div_ceil(refresh).max(1) * refresh;
+                last_time + present_id.wrapping_sub(last_id) as u64 * interval
+            }
+            _ => 0,
+        };
+
+        vk::PresentTimeGOOGLE {
+            present_id,
+            desired_present_time,
+        }
+    }
+
+    /// 마지막으로 호출한 뒤 표시된 image들의 timing을 읽어와서 `tracker`에 기록
+    pub unsafe fn update(
+        &mut self,
+        device: &Device,
+        swapchain: vk::SwapchainKHR,
+        tracker: &mut LatencyTracker,
+    ) -> Result<()> {
+        for timing in device.get_past_presentation_timing_google(swapchain)? {
+            tracker.record_present_margin(Duration::from_nanos(timing.present_margin));
+            if let Some((_, last_time)) = self.last_presented {
+                if timing.actual_present_time > last_time {
+                    tracker.record_display_interval(Duration::from_nanos(
+                        timing.actual_present_time - last_time,
+                    ));
+                }
+            }
+            self.last_presented = Some((timing.present_id, timing.actual_present_time));
+        }
+
+        Ok(())
+    }
+}
+
+/// 최근 frame들의 평균 latency 통계
+#[derive(Copy, Clone, Debug)]
+pub struct LatencyStats {
+    /// frame limiter가 sleep한 평균 시간
+    pub limiter_sleep: Duration,
+    /// 이전 frame의 fence를 기다린 평균 시간
+    pub fence_wait: Duration,
+    /// acquire에서 swapchain image를 기다린 평균 시간
+    pub acquire_wait: Duration,
+    /// acquire를 시작한 뒤 present 요청이 끝날 때까지 걸린 평균 시간
+    pub acquire_to_present: Duration,
+    /// 실제로 표시된 image 사이의 평균 간격  
+    /// `VK_GOOGLE_display_timing`을 사용하지 않으면 `None`
+    pub display_interval: Option<Duration>,
+    /// image가 표시되기 전에 남아있던 평균 여유 시간  
+    /// `VK_GOOGLE_display_timing`을 사용하지 않으면 `None`
+    pub present_margin: Option<Duration>,
+}
+
+impl fmt::Display for LatencyStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        write!(
+            f,
+            "{:.2} ms acquire to present (sleep {:.2} ms, fence {:.2} ms, acquire {:.2} ms",
+            ms(self.acquire_to_present),
+            ms(self.limiter_sleep),
+            ms(self.fence_wait),
+            ms(self.acquire_wait)
+        )?;
+        if let Some(display_interval) = self.display_interval {
+            write!(f, ", display {:.2} ms", ms(display_interval))?;
+        }
+        if let Some(present_margin) = self.present_margin {
+            write!(f, ", margin {:.2} ms", ms(present_margin))?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// render loop에서 frame마다 대기한 시간과 acquire부터 present까지 걸린 시간을 기록하는 tracker
+#[derive(Clone, Debug, Default)]
+pub struct LatencyTracker {
+    limiter_sleeps: VecDeque<Duration>,
+    fence_waits: VecDeque<Duration>,
+    acquire_waits: VecDeque<Duration>,
+    acquire_to_presents: VecDeque<Duration>,
+    display_intervals: VecDeque<Duration>,
+    present_margins: VecDeque<Duration>,
+}
+
+impl LatencyTracker {
+    /// CPU에서 측정한 한 frame의 대기 시간과 latency를 기록
+    pub fn record_frame(
+        &mut self,
+        limiter_sleep: Duration,
+        fence_wait: Duration,
+        acquire_wait: Duration,
+        acquire_to_present: Duration,
+    ) {
+        push(&mut self.limiter_sleeps, limiter_sleep);
+        push(&mut self.fence_waits, fence_wait);
+        push(&mut self.acquire_waits, acquire_wait);
+        push(&mut self.acquire_to_presents, acquire_to_present);
+    }
+
+    /// 연속해서 표시된 두 image 사이의 간격을 기록
+    pub fn record_display_interval(&mut self, interval: Duration) {
+        push(&mut self.display_intervals, interval);
+    }
+
+    /// image가 표시되기 전에 남아있던 여유 시간을 기록
+    pub fn record_present_margin(&mut self, margin: Duration) {
+        push(&mut self.present_margins, margin);
+    }
+
+    /// 기록된 통계를 모두 버림  
+    /// present mode나 frame limiter를 바꾼 뒤 이전 설정의 값이 평균에 섞이지 않도록 사용함
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// 최근 frame들의 평균 통계를 반환  
+    /// 아직 기록된 frame이 없으면 `None`
+    pub fn stats(&self) -> Option<LatencyStats> {
+        Some(LatencyStats {
+            limiter_sleep: average(&self.limiter_sleeps)?,
+            fence_wait: average(&self.fence_waits)?,
+            acquire_wait: average(&self.acquire_waits)?,
+            acquire_to_present: average(&self.acquire_to_presents)?,
+            display_interval: average(&self.display_intervals),
+            present_margin: average(&self.present_margins),
+        })
+    }
+}
@@ -0,0 +1,108 @@
+//! graphics pipeline을 만들 때 사용하는 helper
+
+use anyhow::Result;
+use log::*;
+use vulkanalia::bytecode::Bytecode;
+use vulkanalia::prelude::v1_0::*;
+
+use std::fs;
+use std::path::PathBuf;
+
+/// shader bytecode를 vk::ShaderModule로 래핑하는 helper function
+pub unsafe fn create_shader_module(device: &Device, bytecode: &[u8]) -> Result<vk::ShaderModule> {
+    let bytecode = Bytecode::new(bytecode).unwrap();
+
+    let info = vk::ShaderModuleCreateInfo::builder()
+        .code_size(bytecode.code_size())
+        .code(bytecode.code());
+
+    Ok(device.create_shader_module(&info, None)?)
+}
+
+/// pipeline cache data 앞에 붙는 header의 크기  
+/// header length, header version, vendor ID, device ID(각 4 bytes)와 cache UUID(16 bytes)로 구성됨
+const PIPELINE_CACHE_HEADER_SIZE: usize = 32;
+
+/// 파일에 저장되는 `vk::PipelineCache`  
+/// 이전 실행에서 저장한 cache를 불러와서 사용하면 driver가 shader를 다시 compile하지 않아도 됨
+#[derive(Clone, Debug, Default)]
+pub struct PipelineCache {
+    pub handle: vk::PipelineCache,
+    path: PathBuf,
+}
+
+impl PipelineCache {
+    /// physical device에 해당하는 cache 파일을 읽어서 pipeline cache를 생성  
+    /// 파일이 없거나 다른 device, driver에서 저장된 경우 빈 cache로 시작함
+    pub unsafe fn new(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<Self> {
+        let properties = instance.get_physical_device_properties(physical_device);
+
+        // 같은 device라도 driver가 바뀌면 UUID가 달라지므로 UUID로 파일을 구분함
+        let uuid = properties
+            .pipeline_cache_uuid
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        let path = PathBuf::from(format!("pipeline_cache_{}.bin", uuid));
+
+        let data = match fs::read(&path) {
+            Ok(data) if is_compatible(&data, &properties) => {
+                info!("Loaded pipeline cache from `{}`.", path.display());
+                data
+            }
+            Ok(_) => {
+                warn!("Ignoring incompatible pipeline cache `{}`.", path.display());
+                vec![]
+            }
+            Err(_) => vec![],
+        };
+
+        let info = vk::PipelineCacheCreateInfo::builder().initial_data(&data);
+        let handle = device.create_pipeline_cache(&info, None)?;
+
+        Ok(Self { handle, path })
+    }
+
+    /// pipeline cache의 내용을 파일에 저장  
+    /// 다음 실행에서 `new`가 이 파일을 읽어옴
+    pub unsafe fn save(&self, device: &Device) -> Result<()> {
+        let data = device.get_pipeline_cache_data(self.handle)?;
+        fs::write(&self.path, data)?;
+
+        info!("Saved pipeline cache to `{}`.", self.path.display());
+
+        Ok(())
+    }
+
+    pub unsafe fn destroy(&self, device: &Device) {
+        device.destroy_pipeline_cache(self.handle, None);
+    }
+}
+
+/// cache data의 header가 현재 physical device와 일치하는지 확인  
+/// driver는 일치하지 않는 data를 무시해야 하지만 잘못된 data로 문제가 생기는 driver도 있으므로 직접 확인함
+fn is_compatible(data: &[u8], properties: &vk::PhysicalDeviceProperties) -> bool {
+    if data.len() < PIPELINE_CACHE_HEADER_SIZE {
+        return false;
+    }
+
+    // header의 정수는 little endian으로 저장됨
+    let read_u32 = |offset: usize| {
+        u32::from_le_bytes([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ])
+    };
+
+    read_u32(0) as usize >= PIPELINE_CACHE_HEADER_SIZE
+        && read_u32(4) == vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+        && read_u32(8) == properties.vendor_id
+        && read_u32(12) == properties.device_id
+        && data[16..32] == properties.pipeline_cache_uuid[..]
+}
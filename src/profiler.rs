@@ -0,0 +1,297 @@
+//! timestamp query로 GPU에서 각 구간이 실행되는 데 걸린 시간을 측정하는 profiler
+
+use anyhow::Result;
+use log::*;
+use vulkanalia::prelude::v1_0::*;
+
+use std::mem::size_of;
+use std::time::Duration;
+
+/// frame 안에서 시작과 끝 timestamp로 측정하는 하나의 구간  
+/// 끝 timestamp는 `begin` 바로 다음 query에 기록됨
+#[derive(Clone, Debug)]
+struct Scope {
+    name: String,
+    begin: u32,
+    ended: bool,
+}
+
+/// frame마다 기록한 구간들
+#[derive(Clone, Debug, Default)]
+struct FrameQueries {
+    scopes: Vec<Scope>,
+    // 아직 끝나지 않은 구간의 index, query가 부족해서 기록하지 못한 구간은 None
+    open: Vec<Option<usize>>,
+    next_query: u32,
+}
+
+/// frame in flight마다 query 영역을 나누어 사용하는 GPU profiler  
+/// fence로 frame이 끝난 것을 확인한 뒤에 `read`로 이전에 기록한 결과를 가져옴  
+/// timestamp를 지원하지 않는 device에서는 모든 함수가 아무것도 하지 않음
+#[derive(Clone, Debug, Default)]
+pub struct GpuProfiler {
+    query_pool: vk::QueryPool,
+    // timestamp 한 tick이 몇 ns인지
+    timestamp_period: f32,
+    // timestamp에서 유효한 하위 bit들, 나머지 bit는 값이 정의되지 않음
+    timestamp_mask: u64,
+    queries_per_frame: u32,
+    frames: Vec<FrameQueries>,
+    current: usize,
+}
+
+impl GpuProfiler {
+    /// `frames`개의 frame에서 frame마다 최대 `max_scopes`개의 구간을 측정할 수 있는 profiler를 생성  
+    /// `queue_family`에서 timestamp를 기록할 수 없으면 비활성화된 profiler를 반환함
+    pub unsafe fn new(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        queue_family: u32,
+        frames: usize,
+        max_scopes: u32,
+    ) -> Result<Self> {
+        let properties = instance.get_physical_device_properties(physical_device);
+        let queue_family = instance.get_physical_device_queue_family_properties(physical_device)
+            [queue_family as usize];
+
+        // timestamp_valid_bits가 0이면 이 queue family에서 timestamp를 기록할 수 없음
+        if queue_family.timestamp_valid_bits == 0 {
+            warn!("Queue family does not support timestamps, GPU time will not be measured.");
+            return Ok(Self::default());
+        }
+
+        // 구간마다 시작과 끝 두 개의 query가 필요함
+        let queries_per_frame = max_scopes * 2;
+        let info = vk::QueryPoolCreateInfo::builder()
+            .query_type(vk::QueryType::TIMESTAMP)
+            .query_count(queries_per_frame * frames as u32);
+
+        Ok(Self {
+            query_pool: device.create_query_pool(&info, None)?,
+            timestamp_period: properties.limits.timestamp_period,
+            // timestamp_valid_bits는 1 이상 64 이하
+            timestamp_mask: u64::MAX >> (64 - queue_family.timestamp_valid_bits),
+            queries_per_frame,
+            frames: vec![FrameQueries::default(); frames],
+            current: 0,
+        })
+    }
+
+    /// profiler가 사용하는 query pool  
+    /// timestamp를 지원하지 않으면 null
+    pub fn query_pool(&self) -> vk::QueryPool {
+        self.query_pool
+    }
+
+    /// timestamp 한 tick이 몇 ns인지  
+    /// timestamp를 지원하지 않으면 0
+    pub fn timestamp_period(&self) -> f32 {
+        self.timestamp_period
+    }
+
+    /// `frame`에서 이전에 기록한 구간들의 이름과 GPU time을 기록한 순서대로 반환  
+    /// command buffer의 실행이 끝난 뒤에 호출해야 하며, 아직 기록된 구간이 없으면 빈 Vec을 반환함
+    pub unsafe fn read(&self, device: &Device, frame: usize) -> Result<Vec<(String, Duration)>> {
+        let results = self
+            .read_timestamps(device, frame)?
+            .into_iter()
+            .map(|(name, begin, end)| {
+                // timestamp는 tick 단위이고 timestamp_period는 한 tick이 몇 ns인지를 나타냄
+                // 유효한 bit 범위에서 값이 한 바퀴 돌았어도 mask를 적용하면 차이를 구할 수 있음
+                let ticks = end.wrapping_sub(begin) & self.timestamp_mask;
+                let nanos = ticks as f64 * self.timestamp_period as f64;
+                (name, Duration::from_nanos(nanos as u64))
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    /// `frame`에서 이전에 기록한 구간들의 이름과 시작, 끝 timestamp를 tick 단위 그대로 반환  
+    /// 외부 profiler에 GPU timeline을 전달할 때 사용하며, 호출 조건은 `read`와 같음
+    pub unsafe fn read_timestamps(
+        &self,
+        device: &Device,
+        frame: usize,
+    ) -> Result<Vec<(String, u64, u64)>> {
+        if self.query_pool.is_null() || self.frames[frame].next_query == 0 {
+            return Ok(vec![]);
+        }
+
+        let first_query = self.first_query(frame);
+        let mut results = vec![];
+        for scope in &self.frames[frame].scopes {
+            // 끝나지 않은 구간의 query는 기록되지 않으므로 WAIT으로 기다리면 안 됨
+            if !scope.ended {
+                continue;
+            }
+
+            let mut timestamps = [0u64; 2];
+            device.get_query_pool_results(
+                self.query_pool,
+                first_query + scope.begin,
+                2,
+                std::slice::from_raw_parts_mut(
+                    timestamps.as_mut_ptr() as *mut u8,
+                    size_of::<[u64; 2]>(),
+                ),
+                size_of::<u64>() as vk::DeviceSize,
+                vk::QueryResultFlags::_64 | vk::QueryResultFlags::WAIT,
+            )?;
+
+            results.push((scope.name.clone(), timestamps[0], timestamps[1]));
+        }
+
+        Ok(results)
+    }
+
+    /// `queue`에서 지금 시점의 GPU timestamp를 기록해서 반환  
+    /// CPU와 GPU의 timeline을 맞추는 기준으로 사용하며, queue가 idle 상태가 될 때까지 기다림  
+    /// 첫 frame의 query를 사용하므로 frame을 기록하기 전에 호출해야 하고, timestamp를 지원하지 않으면 None
+    pub unsafe fn calibrate(
+        &self,
+        device: &Device,
+        queue: vk::Queue,
+        command_pool: vk::CommandPool,
+    ) -> Result<Option<u64>> {
+        if self.query_pool.is_null() {
+            return Ok(None);
+        }
+
+        let info = vk::CommandBufferAllocateInfo::builder()
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_pool(command_pool)
+            .command_buffer_count(1);
+        let command_buffer = device.allocate_command_buffers(&info)?[0];
+
+        let info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        device.begin_command_buffer(command_buffer, &info)?;
+        device.cmd_reset_query_pool(command_buffer, self.query_pool, 0, 1);
+        device.cmd_write_timestamp(
+            command_buffer,
+            vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+            self.query_pool,
+            0,
+        );
+        device.end_command_buffer(command_buffer)?;
+
+        let command_buffers = &[command_buffer];
+        let info = vk::SubmitInfo::builder().command_buffers(command_buffers);
+        device.queue_submit(queue, &[info], vk::Fence::null())?;
+        device.queue_wait_idle(queue)?;
+
+        let mut timestamp = 0u64;
+        device.get_query_pool_results(
+            self.query_pool,
+            0,
+            1,
+            std::slice::from_raw_parts_mut(&mut timestamp as *mut u64 as *mut u8, size_of::<u64>()),
+            size_of::<u64>() as vk::DeviceSize,
+            vk::QueryResultFlags::_64 | vk::QueryResultFlags::WAIT,
+        )?;
+
+        device.free_command_buffers(command_pool, command_buffers);
+
+        Ok(Some(timestamp))
+    }
+
+    /// `frame`의 query들을 초기화하고 새로운 구간들을 기록할 준비를 함  
+    /// render pass 밖에서 command buffer를 기록하기 시작할 때 호출해야 하며 이전 결과는 버려짐
+    pub unsafe fn begin_frame(
+        &mut self,
+        device: &Device,
+        command_buffer: vk::CommandBuffer,
+        frame: usize,
+    ) {
+        if self.query_pool.is_null() {
+            return;
+        }
+
+        self.current = frame;
+        self.frames[frame] = FrameQueries::default();
+
+        device.cmd_reset_query_pool(
+            command_buffer,
+            self.query_pool,
+            self.first_query(frame),
+            self.queries_per_frame,
+        );
+    }
+
+    /// `name`이라는 구간을 시작하고 시작 timestamp를 기록  
+    /// `end_scope`와 짝을 이루어야 하며 구간은 중첩될 수 있음
+    pub unsafe fn begin_scope(
+        &mut self,
+        device: &Device,
+        command_buffer: vk::CommandBuffer,
+        name: &str,
+    ) {
+        if self.query_pool.is_null() {
+            return;
+        }
+
+        let first_query = self.first_query(self.current);
+        let queries = &mut self.frames[self.current];
+
+        // 시작과 끝 timestamp를 기록할 query를 함께 할당함
+        if queries.next_query + 2 > self.queries_per_frame {
+            warn!("Not enough timestamp queries for GPU scope (`{}`).", name);
+            queries.open.push(None);
+            return;
+        }
+
+        let begin = queries.next_query;
+        queries.next_query += 2;
+        queries.open.push(Some(queries.scopes.len()));
+        queries.scopes.push(Scope {
+            name: name.to_string(),
+            begin,
+            ended: false,
+        });
+
+        device.cmd_write_timestamp(
+            command_buffer,
+            vk::PipelineStageFlags::TOP_OF_PIPE,
+            self.query_pool,
+            first_query + begin,
+        );
+    }
+
+    /// 가장 최근에 시작한 구간을 끝내고 끝 timestamp를 기록
+    pub unsafe fn end_scope(&mut self, device: &Device, command_buffer: vk::CommandBuffer) {
+        if self.query_pool.is_null() {
+            return;
+        }
+
+        let first_query = self.first_query(self.current);
+        let queries = &mut self.frames[self.current];
+
+        let Some(Some(index)) = queries.open.pop() else {
+            return;
+        };
+
+        let scope = &mut queries.scopes[index];
+        scope.ended = true;
+        let end = scope.begin + 1;
+
+        // 구간 안의 모든 command가 끝난 뒤에 기록되도록 BOTTOM_OF_PIPE를 사용
+        device.cmd_write_timestamp(
+            command_buffer,
+            vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+            self.query_pool,
+            first_query + end,
+        );
+    }
+
+    /// query pool을 파괴
+    pub unsafe fn destroy(&mut self, device: &Device) {
+        device.destroy_query_pool(self.query_pool, None);
+        *self = Self::default();
+    }
+
+    fn first_query(&self, frame: usize) -> u32 {
+        frame as u32 * self.queries_per_frame
+    }
+}
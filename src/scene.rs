@@ -0,0 +1,325 @@
+//! transform과 mesh를 가지는 node의 계층 구조와 view frustum culling  
+//! 부모 node의 transform은 모든 자식 node에 적용되며, node마다 자신과 자식들을 감싸는 AABB를 유지함  
+//! frame마다 camera의 view frustum과 AABB를 비교해서 화면에 보이는 mesh만 draw list에 담음
+
+use cgmath::prelude::*;
+use cgmath::{point3, vec4, Matrix4, Point3, Vector4};
+
+/// 축에 정렬된 bounding box(axis-aligned bounding box)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Point3<f32>,
+    pub max: Point3<f32>,
+}
+
+impl Aabb {
+    /// 아무것도 감싸지 않는 AABB  
+    /// 어떤 AABB와 합쳐도 그 AABB가 그대로 남음
+    pub const EMPTY: Self = Self {
+        min: Point3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY),
+        max: Point3::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY),
+    };
+
+    /// `points`를 모두 감싸는 가장 작은 AABB  
+    /// `points`가 비어있으면 `Aabb::EMPTY`를 반환함
+    pub fn from_points(points: impl IntoIterator<Item = Point3<f32>>) -> Self {
+        points.into_iter().fold(Self::EMPTY, |aabb, p| Self {
+            min: point3(
+                aabb.min.x.min(p.x),
+                aabb.min.y.min(p.y),
+                aabb.min.z.min(p.z),
+            ),
+            max: point3(
+                aabb.max.x.max(p.x),
+                aabb.max.y.max(p.y),
+                aabb.max.z.max(p.z),
+            ),
+        })
+    }
+
+    /// 아무것도 감싸지 않는 AABB인지 확인
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    /// 두 AABB를 모두 감싸는 AABB
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_points([self.min, self.max, other.min, other.max])
+    }
+
+    /// AABB의 8개 꼭짓점
+    pub fn corners(&self) -> [Point3<f32>; 8] {
+        let (min, max) = (self.min, self.max);
+        [
+            point3(min.x, min.y, min.z),
+            point3(max.x, min.y, min.z),
+            point3(min.x, max.y, min.z),
+            point3(max.x, max.y, min.z),
+            point3(min.x, min.y, max.z),
+            point3(max.x, min.y, max.z),
+            point3(min.x, max.y, max.z),
+            point3(max.x, max.y, max.z),
+        ]
+    }
+
+    /// `transform`으로 변환한 box를 감싸는 AABB  
+    /// 회전하면 box가 축에 정렬되지 않으므로 변환된 꼭짓점을 모두 감싸도록 다시 계산함
+    pub fn transform(&self, transform: &Matrix4<f32>) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+
+        Self::from_points(self.corners().map(|c| transform.transform_point(c)))
+    }
+}
+
+/// camera에 보이는 공간을 감싸는 평면 6개
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Frustum {
+    /// left, right, bottom, top, near, far 순서의 평면  
+    /// 각 평면의 xyz는 frustum 안쪽을 향하는 단위 normal이고 w는 원점까지의 거리임
+    pub planes: [Vector4<f32>; 6],
+}
+
+impl Frustum {
+    /// view projection matrix에서 world space의 view frustum 평면을 추출  
+    /// Vulkan의 clip space는 depth가 0.0 ~ 1.0이므로 near 평면은 세 번째 row만으로 구함
+    pub fn from_matrix(view_proj: Matrix4<f32>) -> Self {
+        // cgmath는 column-major이므로 row를 직접 모아야 함
+        let row = |i: usize| {
+            vec4(
+                view_proj.x[i],
+                view_proj.y[i],
+                view_proj.z[i],
+                view_proj.w[i],
+            )
+        };
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+
+        Self {
+            planes: [r3 + r0, r3 - r0, r3 + r1, r3 - r1, r2, r3 - r2]
+                .map(|p| p / p.truncate().magnitude()),
+        }
+    }
+
+    /// `aabb`의 일부라도 frustum 안에 있는지 확인  
+    /// 평면마다 normal 방향으로 가장 멀리 있는 꼭짓점만 검사하며, 그 꼭짓점이 평면 밖이면 box 전체가 밖에 있음  
+    /// frustum의 모서리 근처에서는 밖에 있는 box도 보인다고 판단할 수 있지만 그려도 결과는 같음
+    pub fn intersects(&self, aabb: &Aabb) -> bool {
+        if aabb.is_empty() {
+            return false;
+        }
+
+        // normal의 부호에 따라 축마다 min과 max 중 하나를 고름
+        let pick = |n: f32, min: f32, max: f32| if n >= 0.0 { max } else { min };
+        self.planes.iter().all(|plane| {
+            let farthest = point3(
+                pick(plane.x, aabb.min.x, aabb.max.x),
+                pick(plane.y, aabb.min.y, aabb.max.y),
+                pick(plane.z, aabb.min.z, aabb.max.z),
+            );
+            plane.truncate().dot(farthest.to_vec()) + plane.w >= 0.0
+        })
+    }
+}
+
+/// `Scene::add_mesh`로 등록한 mesh를 가리키는 handle  
+/// 실제 vertex, index 데이터는 chapter에서 관리하며 scene은 bounding box만 알고 있음
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MeshHandle(pub usize);
+
+/// `Scene::add_node`로 추가한 node를 가리키는 handle
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(pub usize);
+
+/// scene 계층 구조의 node 하나
+#[derive(Clone, Debug)]
+pub struct Node {
+    /// debug용 이름
+    pub name: String,
+    /// 부모 node의 좌표계를 기준으로 한 transform
+    pub transform: Matrix4<f32>,
+    /// 이 node에서 그릴 mesh  
+    /// 자식 node들을 묶기 위한 group node는 mesh가 없음
+    pub mesh: Option<MeshHandle>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    // 부모 node들의 transform을 모두 적용한 world transform
+    world_transform: Matrix4<f32>,
+    // world space에서 이 node의 mesh와 모든 자식 node의 mesh를 감싸는 AABB
+    bounds: Aabb,
+}
+
+impl Node {
+    /// 부모 node  
+    /// root node는 `None`
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// 자식 node들
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+
+    /// 마지막 `Scene::update`에서 계산된 world transform
+    pub fn world_transform(&self) -> Matrix4<f32> {
+        self.world_transform
+    }
+
+    /// 마지막 `Scene::update`에서 계산된 world space의 AABB
+    pub fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+}
+
+/// draw list에 담기는 draw 하나
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DrawItem {
+    pub node: NodeId,
+    pub mesh: MeshHandle,
+    /// mesh를 그릴 때 사용할 model matrix
+    pub transform: Matrix4<f32>,
+}
+
+/// culling 결과의 통계
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CullStats {
+    /// frustum과 비교한 node의 수  
+    /// 부모 node가 frustum 밖에 있으면 자식 node는 비교하지 않고 함께 제외됨
+    pub visited: usize,
+    /// mesh를 가지는 전체 node의 수
+    pub total: usize,
+    /// draw list에 담긴 node의 수
+    pub visible: usize,
+}
+
+/// node의 계층 구조와 mesh의 bounding box를 관리하는 scene
+#[derive(Clone, Debug, Default)]
+pub struct Scene {
+    nodes: Vec<Node>,
+    roots: Vec<NodeId>,
+    // local space에서 mesh를 감싸는 AABB
+    mesh_bounds: Vec<Aabb>,
+}
+
+impl Scene {
+    /// local space에서 `bounds`를 차지하는 mesh를 등록
+    pub fn add_mesh(&mut self, bounds: Aabb) -> MeshHandle {
+        self.mesh_bounds.push(bounds);
+        MeshHandle(self.mesh_bounds.len() - 1)
+    }
+
+    /// `parent`의 자식으로 node를 추가  
+    /// `parent`가 `None`이면 root node가 됨
+    pub fn add_node(
+        &mut self,
+        parent: Option<NodeId>,
+        name: impl Into<String>,
+        transform: Matrix4<f32>,
+        mesh: Option<MeshHandle>,
+    ) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            name: name.into(),
+            transform,
+            mesh,
+            parent,
+            children: vec![],
+            world_transform: Matrix4::identity(),
+            bounds: Aabb::EMPTY,
+        });
+
+        match parent {
+            Some(parent) => self.nodes[parent.0].children.push(id),
+            None => self.roots.push(id),
+        }
+
+        id
+    }
+
+    /// root node들
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
+
+    /// node의 transform을 바꾼 뒤에는 `update`를 호출해야 world transform과 AABB에 반영됨
+    pub fn node_mut(&mut self, id: NodeId) -> &mut Node {
+        &mut self.nodes[id.0]
+    }
+
+    /// 모든 node의 world transform과 AABB를 다시 계산  
+    /// transform을 바꾼 뒤 culling하기 전에 한 번 호출해야 함
+    pub fn update(&mut self) {
+        for root in self.roots.clone() {
+            self.update_node(root, Matrix4::identity());
+        }
+    }
+
+    /// `id`와 자식 node들의 world transform을 계산하고 자식 node의 AABB를 합쳐서 반환
+    fn update_node(&mut self, id: NodeId, parent_transform: Matrix4<f32>) -> Aabb {
+        let node = &mut self.nodes[id.0];
+        node.world_transform = parent_transform * node.transform;
+        let world_transform = node.world_transform;
+
+        let mut bounds = match node.mesh {
+            Some(mesh) => self.mesh_bounds[mesh.0].transform(&world_transform),
+            None => Aabb::EMPTY,
+        };
+
+        for child in self.nodes[id.0].children.clone() {
+            bounds = bounds.union(&self.update_node(child, world_transform));
+        }
+
+        self.nodes[id.0].bounds = bounds;
+        bounds
+    }
+
+    /// `frustum`에 보이는 mesh들의 draw list를 생성  
+    /// node의 AABB가 자식 node까지 감싸므로 frustum 밖에 있는 node는 자식 node를 검사하지 않고 건너뜀  
+    /// `frustum`이 `None`이면 culling하지 않고 모든 mesh를 담음
+    pub fn draw_list(&self, frustum: Option<&Frustum>) -> (Vec<DrawItem>, CullStats) {
+        let mut draws = vec![];
+        let mut stats = CullStats {
+            total: self.nodes.iter().filter(|n| n.mesh.is_some()).count(),
+            ..Default::default()
+        };
+
+        let mut stack = self.roots.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id.0];
+            stats.visited += 1;
+
+            if let Some(frustum) = frustum {
+                if !frustum.intersects(&node.bounds) {
+                    continue;
+                }
+            }
+
+            if let Some(mesh) = node.mesh {
+                // 자식 node만 보이는 경우에도 부모 node의 AABB는 통과하므로 mesh 자신의 AABB로 다시 확인
+                let visible = match frustum {
+                    Some(frustum) => frustum
+                        .intersects(&self.mesh_bounds[mesh.0].transform(&node.world_transform)),
+                    None => true,
+                };
+                if visible {
+                    draws.push(DrawItem {
+                        node: id,
+                        mesh,
+                        transform: node.world_transform,
+                    });
+                }
+            }
+
+            stack.extend(node.children.iter().rev());
+        }
+
+        stats.visible = draws.len();
+        (draws, stats)
+    }
+}
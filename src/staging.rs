@@ -0,0 +1,374 @@
+//! 계속 map된 상태로 유지하는 staging buffer를 frame마다 나누어 사용하는 staging ring  
+//! 매번 staging buffer를 만들고 map/unmap한 뒤 파괴하는 대신 한 번 할당한 buffer에 데이터를 쓰고  
+//! frame의 transfer command buffer에 복사 command를 기록해서 graphics command buffer보다 먼저 제출함  
+//! 영역에 들어가지 않는 큰 데이터는 임시 buffer로 복사하고, 임시 buffer는 deletion queue로 frame이 끝난 뒤에 파괴함
+
+use anyhow::{anyhow, Result};
+use vulkanalia::prelude::v1_0::*;
+
+use std::mem::size_of_val;
+use std::ptr::copy_nonoverlapping as memcpy;
+use std::ptr::NonNull;
+
+use crate::allocator::get_memory_type;
+use crate::deletion::DeletionQueue;
+
+/// staging buffer 안에서 복사를 시작하는 위치의 최소 alignment  
+/// image로 복사할 때는 texel 크기의 배수여야 하므로 16 byte texel까지 지원하도록 여유있게 맞춤
+const MIN_ALIGNMENT: vk::DeviceSize = 16;
+
+/// frame 하나가 사용하는 staging 영역과 transfer command buffer
+#[derive(Clone, Debug, Default)]
+struct Region {
+    command_buffer: vk::CommandBuffer,
+    // 이 영역을 사용한 transfer command가 끝났음을 알리는 frame의 fence
+    fence: vk::Fence,
+}
+
+/// frame마다 영역을 나누어 사용하는 persistently mapped staging buffer  
+/// `begin_frame`은 영역을 마지막으로 사용한 frame의 fence를 기다린 뒤에 영역을 처음부터 다시 사용함  
+/// `end_frame`이 반환한 command buffer는 같은 queue에서 데이터를 사용하는 command buffer보다 먼저 제출해야 함  
+/// 영역이 가득 차면 실패하지 않고 데이터만 담는 임시 buffer를 만들어서 복사함
+#[derive(Clone, Debug, Default)]
+pub struct StagingRing {
+    /// 모든 frame의 영역을 담는 buffer
+    pub buffer: vk::Buffer,
+    /// buffer에 bind된 HOST_VISIBLE memory
+    pub memory: vk::DeviceMemory,
+    /// frame 하나가 사용할 수 있는 영역의 크기
+    pub region_size: vk::DeviceSize,
+    command_pool: vk::CommandPool,
+    regions: Vec<Region>,
+    alignment: vk::DeviceSize,
+    // 임시 buffer의 memory type을 찾기 위한 physical device의 memory 속성
+    memory_properties: vk::PhysicalDeviceMemoryProperties,
+    // 영역이 부족할 때 만든 임시 buffer를 사용한 frame이 끝난 뒤에 파괴하기 위한 queue
+    deletion: DeletionQueue,
+    // 생성할 때 한 번만 map해두고 파괴할 때까지 유지하는 주소
+    mapped: Option<NonNull<u8>>,
+    // 현재 기록중인 frame의 index
+    frame: usize,
+    // 현재 frame의 영역에서 다음에 사용할 위치(영역의 시작 기준)
+    offset: vk::DeviceSize,
+}
+
+impl StagingRing {
+    /// frame마다 `region_size`만큼 사용할 수 있는 staging ring을 `frames`개의 frame만큼 생성  
+    /// transfer command buffer는 `queue_family_index`의 queue에 제출해야 함
+    pub unsafe fn new(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        queue_family_index: u32,
+        region_size: vk::DeviceSize,
+        frames: usize,
+    ) -> Result<Self> {
+        let limits = instance
+            .get_physical_device_properties(physical_device)
+            .limits;
+        let alignment = limits
+            .optimal_buffer_copy_offset_alignment
+            .max(MIN_ALIGNMENT);
+
+        let region_size = region_size.div_ceil(alignment) * alignment;
+        let size = region_size * frames as vk::DeviceSize;
+
+        let memory_properties = instance.get_physical_device_memory_properties(physical_device);
+        let (buffer, memory) = create_staging_buffer(device, &memory_properties, size)?;
+
+        let mapped = device.map_memory(memory, 0, size, vk::MemoryMapFlags::empty())?;
+
+        // frame마다 command buffer를 따로 reset할 수 있도록 RESET_COMMAND_BUFFER를 지정
+        let pool_info = vk::CommandPoolCreateInfo::builder()
+            .flags(
+                vk::CommandPoolCreateFlags::TRANSIENT
+                    | vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER,
+            )
+            .queue_family_index(queue_family_index);
+
+        let command_pool = device.create_command_pool(&pool_info, None)?;
+
+        let allocate_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(command_pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(frames as u32);
+
+        let regions = device
+            .allocate_command_buffers(&allocate_info)?
+            .into_iter()
+            .map(|command_buffer| Region {
+                command_buffer,
+                fence: vk::Fence::null(),
+            })
+            .collect();
+
+        Ok(Self {
+            buffer,
+            memory,
+            region_size,
+            command_pool,
+            regions,
+            alignment,
+            memory_properties,
+            deletion: DeletionQueue::new(frames),
+            mapped: NonNull::new(mapped.cast()),
+            frame: 0,
+            offset: 0,
+        })
+    }
+
+    /// `frame`의 영역을 처음부터 다시 사용하고 transfer command buffer의 기록을 시작  
+    /// `fence`는 이번 frame의 command buffer를 제출할 때 사용하는 fence로, 다음에 같은 영역을 사용할 때 기다림  
+    /// 같은 fence를 다시 사용하므로 fence를 reset하기 전에 호출해야 함
+    pub unsafe fn begin_frame(
+        &mut self,
+        device: &Device,
+        frame: usize,
+        fence: vk::Fence,
+    ) -> Result<()> {
+        self.frame = frame % self.regions.len();
+        self.offset = 0;
+
+        // 이 영역에서 복사하던 이전 transfer가 끝날 때까지 기다림
+        let region = &mut self.regions[self.frame];
+        if !region.fence.is_null() {
+            device.wait_for_fences(&[region.fence], true, u64::MAX)?;
+        }
+        region.fence = fence;
+
+        // 이 영역을 사용한 frame이 끝났으므로 그 frame에서 만든 임시 buffer도 파괴할 수 있음
+        self.deletion.flush(device, self.frame);
+
+        device.reset_command_buffer(region.command_buffer, vk::CommandBufferResetFlags::empty())?;
+
+        let info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        device.begin_command_buffer(region.command_buffer, &info)?;
+
+        Ok(())
+    }
+
+    /// 현재 frame의 transfer command buffer의 기록을 끝내고 반환
+    pub unsafe fn end_frame(&mut self, device: &Device) -> Result<vk::CommandBuffer> {
+        let command_buffer = self.regions[self.frame].command_buffer;
+        device.end_command_buffer(command_buffer)?;
+
+        Ok(command_buffer)
+    }
+
+    /// `data`를 `dst`의 `dst_offset` 위치로 복사하는 command를 기록  
+    /// 복사가 끝난 뒤에 `dst_stage`의 `dst_access`가 데이터를 읽을 수 있도록 barrier를 함께 기록함
+    pub unsafe fn upload_buffer<T: Copy>(
+        &mut self,
+        device: &Device,
+        data: &[T],
+        dst: vk::Buffer,
+        dst_offset: vk::DeviceSize,
+        dst_stage: vk::PipelineStageFlags,
+        dst_access: vk::AccessFlags,
+    ) -> Result<()> {
+        let size = size_of_val(data) as vk::DeviceSize;
+        let (src, src_offset) = self.write(device, data)?;
+        let command_buffer = self.regions[self.frame].command_buffer;
+
+        let region = vk::BufferCopy::builder()
+            .src_offset(src_offset)
+            .dst_offset(dst_offset)
+            .size(size);
+        device.cmd_copy_buffer(command_buffer, src, dst, &[region]);
+
+        let barrier = vk::BufferMemoryBarrier::builder()
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(dst_access)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .buffer(dst)
+            .offset(dst_offset)
+            .size(size);
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            dst_stage,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[barrier],
+            &[] as &[vk::ImageMemoryBarrier],
+        );
+
+        Ok(())
+    }
+
+    /// `pixels`를 mip level이 하나인 color image의 전체 영역으로 복사하는 command를 기록  
+    /// image의 이전 내용은 버리고, 복사가 끝나면 fragment shader에서 읽을 수 있도록 SHADER_READ_ONLY_OPTIMAL로 바꿈  
+    /// 이전 frame의 fragment shader가 image를 다 읽을 때까지 복사를 시작하지 않음
+    pub unsafe fn upload_image(
+        &mut self,
+        device: &Device,
+        pixels: &[u8],
+        image: vk::Image,
+        width: u32,
+        height: u32,
+    ) -> Result<()> {
+        let (src, src_offset) = self.write(device, pixels)?;
+        let command_buffer = self.regions[self.frame].command_buffer;
+
+        let subresource = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(0)
+            .level_count(1)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        // write-after-read이므로 memory dependency 없이 execution dependency만 있으면 됨
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::UNDEFINED)
+            .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(subresource)
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+
+        let subresource_layers = vk::ImageSubresourceLayers::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .mip_level(0)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        // row_length와 image_height가 0이면 pixel이 빈틈없이 채워져 있다는 의미
+        let region = vk::BufferImageCopy::builder()
+            .buffer_offset(src_offset)
+            .buffer_row_length(0)
+            .buffer_image_height(0)
+            .image_subresource(subresource_layers)
+            .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
+            .image_extent(vk::Extent3D {
+                width,
+                height,
+                depth: 1,
+            });
+
+        device.cmd_copy_buffer_to_image(
+            command_buffer,
+            src,
+            image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[region],
+        );
+
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(subresource)
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::SHADER_READ);
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+
+        Ok(())
+    }
+
+    /// command pool과 buffer를 파괴하고 memory를 해제  
+    /// 모든 transfer command가 끝난 뒤에 호출해야 함
+    pub unsafe fn destroy(&mut self, device: &Device) {
+        // 아직 파괴하지 않은 임시 buffer를 파괴
+        self.deletion.flush_all(device);
+        // command pool이 파괴되면 할당된 command buffer도 함께 해제됨
+        device.destroy_command_pool(self.command_pool, None);
+        device.destroy_buffer(self.buffer, None);
+        // free_memory가 암묵적으로 unmap함
+        device.free_memory(self.memory, None);
+        self.regions.clear();
+        self.mapped = None;
+    }
+
+    /// 현재 frame의 영역에 `data`를 쓰고 복사할 때 사용할 buffer와 그 안에서의 위치를 반환  
+    /// 영역에 남은 공간이 부족하면 `data`만 담는 임시 buffer를 만들고 현재 frame이 끝난 뒤에 파괴되도록 등록함
+    unsafe fn write<T: Copy>(
+        &mut self,
+        device: &Device,
+        data: &[T],
+    ) -> Result<(vk::Buffer, vk::DeviceSize)> {
+        let size = size_of_val(data) as vk::DeviceSize;
+        if self.offset + size > self.region_size {
+            let (buffer, memory) = create_staging_buffer(device, &self.memory_properties, size)?;
+
+            let mapped = device.map_memory(memory, 0, size, vk::MemoryMapFlags::empty())?;
+            memcpy(data.as_ptr(), mapped.cast(), data.len());
+            device.unmap_memory(memory);
+
+            self.deletion.push(self.frame, buffer);
+            self.deletion.push(self.frame, memory);
+
+            return Ok((buffer, 0));
+        }
+
+        let mapped = self
+            .mapped
+            .ok_or_else(|| anyhow!("Staging ring is not mapped."))?;
+
+        let offset = self.region_size * self.frame as vk::DeviceSize + self.offset;
+        memcpy(
+            data.as_ptr(),
+            mapped.as_ptr().add(offset as usize).cast(),
+            data.len(),
+        );
+
+        // 다음 복사가 alignment에 맞는 위치에서 시작하도록 올림
+        self.offset = (self.offset + size).div_ceil(self.alignment) * self.alignment;
+
+        Ok((self.buffer, offset))
+    }
+}
+
+/// transfer의 source로 사용하는 `size` 크기의 HOST_VISIBLE buffer를 생성
+unsafe fn create_staging_buffer(
+    device: &Device,
+    memory_properties: &vk::PhysicalDeviceMemoryProperties,
+    size: vk::DeviceSize,
+) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+    let buffer_info = vk::BufferCreateInfo::builder()
+        .size(size)
+        .usage(vk::BufferUsageFlags::TRANSFER_SRC)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+    let buffer = device.create_buffer(&buffer_info, None)?;
+
+    // host에서 쓴 데이터를 flush 없이 transfer에서 읽을 수 있도록 HOST_COHERENT memory를 사용
+    let requirements = device.get_buffer_memory_requirements(buffer);
+    let memory_info = vk::MemoryAllocateInfo::builder()
+        .allocation_size(requirements.size)
+        .memory_type_index(get_memory_type(
+            memory_properties,
+            vk::MemoryPropertyFlags::HOST_COHERENT | vk::MemoryPropertyFlags::HOST_VISIBLE,
+            requirements,
+        )?);
+
+    let memory = device.allocate_memory(&memory_info, None)?;
+    device.bind_buffer_memory(buffer, memory, 0)?;
+
+    Ok((buffer, memory))
+}
@@ -0,0 +1,479 @@
+//! swapchain과 swapchain image view
+
+use anyhow::Result;
+use log::*;
+use vulkanalia::prelude::v1_0::*;
+use winit::window::Window;
+
+use vulkanalia::vk::KhrSwapchainExtension;
+
+use crate::config;
+use crate::device::{QueueFamilyIndices, SwapchainSupport};
+
+/// 사용할 present mode를 직접 지정하기 위한 command line flag  
+/// `--present-mode <mode>` 또는 `--present-mode=<mode>`이며, mode는 `fifo`, `fifo_relaxed`, `mailbox`, `immediate` 중 하나  
+/// 설정 파일에서는 `present-mode`로 지정함
+pub const PRESENT_MODE_FLAG: &str = "--present-mode";
+
+/// 선택할 수 있는 present mode와 command line에서 사용하는 이름
+pub const PRESENT_MODES: &[(vk::PresentModeKHR, &str)] = &[
+    (vk::PresentModeKHR::FIFO, "fifo"),
+    (vk::PresentModeKHR::FIFO_RELAXED, "fifo_relaxed"),
+    (vk::PresentModeKHR::MAILBOX, "mailbox"),
+    (vk::PresentModeKHR::IMMEDIATE, "immediate"),
+];
+
+/// graphics queue family와 present queue family가 다를 때 swapchain image의 sharing mode를 직접 지정하기 위한 command line flag  
+/// `--sharing-mode <mode>` 또는 `--sharing-mode=<mode>`이며, mode는 `concurrent`, `exclusive` 중 하나  
+/// 설정 파일에서는 `sharing-mode`로 지정함
+pub const SHARING_MODE_FLAG: &str = "--sharing-mode";
+
+/// 선택할 수 있는 sharing mode와 command line에서 사용하는 이름
+pub const SHARING_MODES: &[(vk::SharingMode, &str)] = &[
+    (vk::SharingMode::CONCURRENT, "concurrent"),
+    (vk::SharingMode::EXCLUSIVE, "exclusive"),
+];
+
+/// swapchain 핸들과 swapchain image의 속성을 묶은 구조체
+#[derive(Clone, Debug, Default)]
+pub struct Swapchain {
+    // swapchain 핸들
+    pub handle: vk::SwapchainKHR,
+    // swapchain image를 위한 format
+    pub format: vk::Format,
+    // presentation engine이 image의 값을 해석하는 color space
+    pub color_space: vk::ColorSpaceKHR,
+    // swapchain image를 위한 extent
+    pub extent: vk::Extent2D,
+    // swapchain image를 생성할 때 지정한 usage
+    pub usage: vk::ImageUsageFlags,
+    // 실제로 사용하는 present mode
+    pub present_mode: vk::PresentModeKHR,
+    // presentation engine이 image를 표시할 때 적용하는 transform
+    pub transform: vk::SurfaceTransformFlagsKHR,
+    // presentation engine이 image의 alpha를 다른 window와 합성할 때 사용하는 방법
+    pub composite_alpha: vk::CompositeAlphaFlagsKHR,
+    // swapchain image를 여러 queue family에서 사용하는 방법
+    // EXCLUSIVE이고 graphics와 present queue family가 다르면 queue family ownership transfer가 필요함
+    pub sharing_mode: vk::SharingMode,
+    // 생성할 때 요청한 full-screen exclusive mode
+    // 다시 생성할 때 같은 값을 전달하기 위해 저장함
+    pub full_screen_exclusive: Option<vk::FullScreenExclusiveEXT>,
+    // swapchain의 이미지
+    pub images: Vec<vk::Image>,
+}
+
+impl Swapchain {
+    /// presentation engine이 image를 HDR(scRGB)로 해석하는지 여부  
+    /// 이 경우 image의 값은 tone mapping 없이 linear로 저장해야 함
+    pub fn is_hdr(&self) -> bool {
+        self.color_space == HDR_SURFACE_FORMAT.color_space
+    }
+
+    /// `transform`에 포함된 회전 각도(degree)  
+    /// presentation engine이 회전하지 않으므로 앱이 clip space를 이 각도만큼 회전해서 그려야 함
+    pub fn pre_rotation(&self) -> f32 {
+        if self
+            .transform
+            .contains(vk::SurfaceTransformFlagsKHR::ROTATE_90)
+        {
+            90.0
+        } else if self
+            .transform
+            .contains(vk::SurfaceTransformFlagsKHR::ROTATE_180)
+        {
+            180.0
+        } else if self
+            .transform
+            .contains(vk::SurfaceTransformFlagsKHR::ROTATE_270)
+        {
+            270.0
+        } else {
+            0.0
+        }
+    }
+
+    /// 화면에 보이는 방향을 기준으로 한 extent  
+    /// 90도나 270도 회전한 경우 swapchain image와 width, height가 반대이므로 projection의 aspect는 이 값으로 계산해야 함
+    pub fn display_extent(&self) -> vk::Extent2D {
+        if is_rotated(self.transform) {
+            vk::Extent2D {
+                width: self.extent.height,
+                height: self.extent.width,
+            }
+        } else {
+            self.extent
+        }
+    }
+}
+
+/// `transform`이 width와 height를 바꾸는 90도나 270도 회전인지 확인
+fn is_rotated(transform: vk::SurfaceTransformFlagsKHR) -> bool {
+    transform.intersects(
+        vk::SurfaceTransformFlagsKHR::ROTATE_90 | vk::SurfaceTransformFlagsKHR::ROTATE_270,
+    )
+}
+
+/// 따로 지정하지 않은 경우 사용하는 surface format  
+/// shader가 linear 값을 출력하면 hardware가 sRGB로 encode해줌
+pub const DEFAULT_SURFACE_FORMAT: vk::SurfaceFormatKHR = vk::SurfaceFormatKHR {
+    format: vk::Format::B8G8R8A8_SRGB,
+    color_space: vk::ColorSpaceKHR::SRGB_NONLINEAR,
+};
+
+/// HDR display에 출력하기 위한 surface format  
+/// scRGB(extended sRGB linear)에서는 1.0이 80 nit이며 1.0보다 큰 값과 음수로 sRGB보다 넓은 범위를 표현함  
+/// instance에서 `VK_EXT_swapchain_colorspace`를 활성화해야 surface가 이 color space를 반환함
+pub const HDR_SURFACE_FORMAT: vk::SurfaceFormatKHR = vk::SurfaceFormatKHR {
+    format: vk::Format::R16G16B16A16_SFLOAT,
+    color_space: vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT,
+};
+
+/// surface가 `preferred`를 지원하면 그대로 사용하고, 아니면 `DEFAULT_SURFACE_FORMAT`을 사용  
+/// 둘 다 지원하지 않으면 surface가 지원하는 첫 번째 format을 사용함
+pub fn get_swapchain_surface_format(
+    formats: &[vk::SurfaceFormatKHR],
+    preferred: vk::SurfaceFormatKHR,
+) -> vk::SurfaceFormatKHR {
+    let find = |preferred: vk::SurfaceFormatKHR| {
+        formats
+            .iter()
+            .cloned()
+            .find(|f| f.format == preferred.format && f.color_space == preferred.color_space)
+    };
+
+    find(preferred).unwrap_or_else(|| {
+        if preferred != DEFAULT_SURFACE_FORMAT {
+            info!(
+                "Surface format {:?} ({:?}) is not supported, falling back to the default.",
+                preferred.format, preferred.color_space
+            );
+        }
+        find(DEFAULT_SURFACE_FORMAT).unwrap_or_else(|| formats[0])
+    })
+}
+
+/// surface가 `preferred`를 지원하면 그대로 사용하고, 아니면 항상 지원되는 FIFO를 사용
+pub fn get_swapchain_present_mode(
+    present_modes: &[vk::PresentModeKHR],
+    preferred: vk::PresentModeKHR,
+) -> vk::PresentModeKHR {
+    if present_modes.contains(&preferred) {
+        preferred
+    } else {
+        info!(
+            "Present mode {:?} is not supported, falling back to FIFO.",
+            preferred
+        );
+        vk::PresentModeKHR::FIFO
+    }
+}
+
+/// command line flag나 설정 파일로 지정된 present mode를 반환  
+/// `--vsync`만 지정한 경우에도 그에 맞는 present mode를 반환함
+pub fn requested_present_mode() -> Result<Option<vk::PresentModeKHR>> {
+    Ok(config::get()?.present_mode)
+}
+
+/// command line flag나 설정 파일로 지정된 sharing mode를 반환
+pub fn requested_sharing_mode() -> Result<Option<vk::SharingMode>> {
+    Ok(config::get()?.sharing_mode)
+}
+
+/// 최적의 Swap extent 찾기
+pub fn get_swapchain_extent(
+    window: &Window,
+    capabilities: vk::SurfaceCapabilitiesKHR,
+) -> vk::Extent2D {
+    if capabilities.current_extent.width != u32::MAX {
+        capabilities.current_extent
+    } else {
+        vk::Extent2D::builder()
+            .width(window.inner_size().width.clamp(
+                capabilities.min_image_extent.width,
+                capabilities.max_image_extent.width,
+            ))
+            .height(window.inner_size().height.clamp(
+                capabilities.min_image_extent.height,
+                capabilities.max_image_extent.height,
+            ))
+            .build()
+    }
+}
+
+/// Swapchain을 생성할 때 지정하는 설정  
+/// surface가 지원하지 않는 값은 지원하는 값으로 대체되므로 실제로 사용된 값은 `Swapchain`의 field로 확인해야 함
+#[derive(Clone, Debug)]
+pub struct SwapchainOptions {
+    /// 사용할 present mode  
+    /// surface가 지원하지 않으면 FIFO를 사용함
+    pub present_mode: vk::PresentModeKHR,
+    /// 사용할 surface format  
+    /// surface가 지원하지 않으면 `DEFAULT_SURFACE_FORMAT`이나 surface가 지원하는 첫 번째 format을 사용함
+    pub surface_format: vk::SurfaceFormatKHR,
+    /// `VK_EXT_full_screen_exclusive`로 presentation engine에 알릴 full-screen exclusive mode  
+    /// `None`이면 extension 구조체를 전달하지 않으므로 extension을 활성화하지 않은 device에서도 사용할 수 있음  
+    /// `APPLICATION_CONTROLLED`는 Win32 surface에서 monitor handle을 추가로 요구하므로 `ALLOWED`나 `DISALLOWED`를 사용해야 함
+    pub full_screen_exclusive: Option<vk::FullScreenExclusiveEXT>,
+    /// 선호하는 순서대로 나열한 composite alpha  
+    /// 투명한 window를 만들려면 PRE_MULTIPLIED나 POST_MULTIPLIED를 선호하도록 지정해야 함
+    pub composite_alpha: Vec<vk::CompositeAlphaFlagsKHR>,
+    /// graphics queue family와 present queue family가 다를 때 swapchain image를 공유하는 방법  
+    /// CONCURRENT는 두 queue family에서 그대로 사용할 수 있지만 driver가 최적화를 덜 할 수 있고  
+    /// EXCLUSIVE는 present하기 전에 graphics queue에서 present queue로 ownership을 직접 넘겨야 함  
+    /// 두 queue family가 같으면 이 값과 관계없이 항상 EXCLUSIVE를 사용함
+    pub sharing_mode: vk::SharingMode,
+    /// 새 swapchain이 대체할 이전 swapchain  
+    /// presentation engine이 이전 swapchain의 resource를 재사용할 수 있고, 이전 swapchain은 retire되어 더 이상 image를 acquire할 수 없음  
+    /// retire된 swapchain에서 이미 acquire한 image는 계속 present할 수 있으므로 호출한 쪽에서 사용이 끝난 뒤에 파괴해야 함
+    pub old_swapchain: vk::SwapchainKHR,
+}
+
+impl Default for SwapchainOptions {
+    fn default() -> Self {
+        Self {
+            present_mode: vk::PresentModeKHR::MAILBOX,
+            surface_format: DEFAULT_SURFACE_FORMAT,
+            full_screen_exclusive: None,
+            composite_alpha: vec![vk::CompositeAlphaFlagsKHR::OPAQUE],
+            sharing_mode: vk::SharingMode::CONCURRENT,
+            old_swapchain: vk::SwapchainKHR::null(),
+        }
+    }
+}
+
+impl SwapchainOptions {
+    /// 설정으로 지정한 present mode를 사용하는 기본 설정  
+    /// 지정한 present mode가 없으면 MAILBOX를 사용함
+    pub fn from_config() -> Result<Self> {
+        Ok(Self {
+            present_mode: requested_present_mode()?.unwrap_or(vk::PresentModeKHR::MAILBOX),
+            ..Default::default()
+        })
+    }
+}
+
+/// 선호하는 composite alpha를 지원하지 않을 때 순서대로 시도하는 composite alpha  
+/// surface는 적어도 하나의 composite alpha를 지원해야 하지만 OPAQUE라는 보장은 없음
+pub const COMPOSITE_ALPHA_FALLBACKS: &[vk::CompositeAlphaFlagsKHR] = &[
+    vk::CompositeAlphaFlagsKHR::OPAQUE,
+    vk::CompositeAlphaFlagsKHR::INHERIT,
+    vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED,
+    vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED,
+];
+
+/// `supported`에 포함된 composite alpha 중에서 `preferred`에 먼저 나온 것을 선택  
+/// `preferred`를 하나도 지원하지 않으면 `COMPOSITE_ALPHA_FALLBACKS`의 순서로 선택함
+pub fn get_swapchain_composite_alpha(
+    supported: vk::CompositeAlphaFlagsKHR,
+    preferred: &[vk::CompositeAlphaFlagsKHR],
+) -> vk::CompositeAlphaFlagsKHR {
+    preferred
+        .iter()
+        .chain(COMPOSITE_ALPHA_FALLBACKS)
+        .cloned()
+        .find(|a| supported.contains(*a))
+        .unwrap_or(vk::CompositeAlphaFlagsKHR::OPAQUE)
+}
+
+/// `options`를 사용하는 Swapchain 생성
+#[tracing::instrument(
+    skip_all,
+    fields(present_mode = ?options.present_mode, sharing_mode = ?options.sharing_mode)
+)]
+pub unsafe fn create_swapchain(
+    window: &Window,
+    instance: &Instance,
+    device: &Device,
+    surface: vk::SurfaceKHR,
+    physical_device: vk::PhysicalDevice,
+    options: &SwapchainOptions,
+) -> Result<Swapchain> {
+    let indices = QueueFamilyIndices::get(instance, surface, physical_device)?;
+    let support = SwapchainSupport::get(instance, surface, physical_device)?;
+
+    let surface_format = get_swapchain_surface_format(&support.formats, options.surface_format);
+    let present_mode = get_swapchain_present_mode(&support.present_modes, options.present_mode);
+    let transform = support.capabilities.current_transform;
+    let composite_alpha = get_swapchain_composite_alpha(
+        support.capabilities.supported_composite_alpha,
+        &options.composite_alpha,
+    );
+    let mut extent = get_swapchain_extent(window, support.capabilities);
+
+    // Android처럼 화면이 회전된 경우 current_extent는 회전된 방향의 크기임
+    // pre_transform을 current_transform으로 지정하면 presentation engine이 회전하지 않으므로
+    // 회전하지 않은 방향의 크기로 image를 만들고 앱이 직접 회전해서 그려야 함
+    if is_rotated(transform) {
+        extent = vk::Extent2D {
+            width: extent.height,
+            height: extent.width,
+        };
+    }
+
+    // 이미지 개수를 설정
+    // 이미지 개수는 min_image_count보다 1개 더 많아야 함. 드라이버 내부 연산 완료가 되어야만 이미지를 얻을수 있는 문제를 피하기 위함.
+    let mut image_count = support.capabilities.min_image_count + 1;
+
+    // 이미지 수가 최대 이미지 수를 초과하지 않도록 함
+    if support.capabilities.max_image_count != 0
+        && image_count > support.capabilities.max_image_count
+    {
+        image_count = support.capabilities.max_image_count;
+    }
+
+    let mut queue_family_indices = vec![];
+    let image_sharing_mode = if indices.graphics != indices.present
+        && options.sharing_mode == vk::SharingMode::CONCURRENT
+    {
+        queue_family_indices.push(indices.graphics);
+        queue_family_indices.push(indices.present);
+        vk::SharingMode::CONCURRENT
+    } else {
+        vk::SharingMode::EXCLUSIVE
+    };
+
+    // screenshot을 위해 swapchain image를 복사할 수 있도록 surface가 지원하면 TRANSFER_SRC도 지정
+    let mut usage = vk::ImageUsageFlags::COLOR_ATTACHMENT;
+    if support
+        .capabilities
+        .supported_usage_flags
+        .contains(vk::ImageUsageFlags::TRANSFER_SRC)
+    {
+        usage |= vk::ImageUsageFlags::TRANSFER_SRC;
+    }
+
+    // ray tracing 결과처럼 다른 image에서 blit해서 present할 수 있도록 surface가 지원하면 TRANSFER_DST도 지정
+    if support
+        .capabilities
+        .supported_usage_flags
+        .contains(vk::ImageUsageFlags::TRANSFER_DST)
+    {
+        usage |= vk::ImageUsageFlags::TRANSFER_DST;
+    }
+
+    let mut full_screen_exclusive_info = vk::SurfaceFullScreenExclusiveInfoEXT::builder()
+        .full_screen_exclusive(options.full_screen_exclusive.unwrap_or_default());
+
+    let mut info = vk::SwapchainCreateInfoKHR::builder()
+        .surface(surface)
+        .min_image_count(image_count)
+        .image_format(surface_format.format)
+        .image_color_space(surface_format.color_space)
+        .image_extent(extent)
+        .image_array_layers(1)
+        .image_usage(usage)
+        .image_sharing_mode(image_sharing_mode)
+        .queue_family_indices(&queue_family_indices)
+        .pre_transform(transform)
+        .composite_alpha(composite_alpha)
+        .present_mode(present_mode)
+        .clipped(true)
+        .old_swapchain(options.old_swapchain);
+
+    if options.full_screen_exclusive.is_some() {
+        info = info.push_next(&mut full_screen_exclusive_info);
+    }
+
+    let handle = device.create_swapchain_khr(&info, None)?;
+    let images = device.get_swapchain_images_khr(handle)?;
+
+    Ok(Swapchain {
+        handle,
+        format: surface_format.format,
+        color_space: surface_format.color_space,
+        extent,
+        usage,
+        present_mode,
+        transform,
+        composite_alpha,
+        sharing_mode: image_sharing_mode,
+        full_screen_exclusive: options.full_screen_exclusive,
+        images,
+    })
+}
+
+/// `old`와 같은 설정으로 `old`를 대체하는 Swapchain 생성  
+/// `old.handle`을 `old_swapchain`으로 전달해서 retire하므로 `old`는 deletion queue 등으로 사용이 끝난 뒤에 파괴해야 함  
+/// 92_deletion_queue에서 window의 크기가 바뀌었을 때 사용하며, 다른 chapter는 `device_wait_idle` 뒤에 이전 swapchain을 먼저 파괴하고 `create_swapchain`으로 새로 생성함  
+/// `old.handle`이 null이면 retire할 swapchain 없이 새로 생성함
+#[tracing::instrument(skip_all)]
+pub unsafe fn recreate_swapchain(
+    window: &Window,
+    instance: &Instance,
+    device: &Device,
+    surface: vk::SurfaceKHR,
+    physical_device: vk::PhysicalDevice,
+    old: &Swapchain,
+) -> Result<Swapchain> {
+    let options = SwapchainOptions {
+        present_mode: old.present_mode,
+        surface_format: vk::SurfaceFormatKHR {
+            format: old.format,
+            color_space: old.color_space,
+        },
+        full_screen_exclusive: old.full_screen_exclusive,
+        composite_alpha: vec![old.composite_alpha],
+        sharing_mode: old.sharing_mode,
+        old_swapchain: old.handle,
+    };
+
+    create_swapchain(window, instance, device, surface, physical_device, &options)
+}
+
+/// swapchain image view 생성
+pub unsafe fn create_swapchain_image_views(
+    device: &Device,
+    swapchain: &Swapchain,
+) -> Result<Vec<vk::ImageView>> {
+    Ok(swapchain
+        .images
+        .iter()
+        .map(|i| {
+            let components = vk::ComponentMapping::builder()
+                .r(vk::ComponentSwizzle::IDENTITY)
+                .g(vk::ComponentSwizzle::IDENTITY)
+                .b(vk::ComponentSwizzle::IDENTITY)
+                .a(vk::ComponentSwizzle::IDENTITY);
+
+            let subresource_range = vk::ImageSubresourceRange::builder()
+                .aspect_mask(vk::ImageAspectFlags::COLOR)
+                .base_mip_level(0)
+                .level_count(1)
+                .base_array_layer(0)
+                .layer_count(1);
+
+            let info = vk::ImageViewCreateInfo::builder()
+                .image(*i)
+                .view_type(vk::ImageViewType::_2D)
+                .format(swapchain.format)
+                .components(components)
+                .subresource_range(subresource_range);
+
+            device.create_image_view(&info, None)
+        })
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// swapchain image마다 하나씩 사용하는 render finished semaphore를 `semaphores`에 생성  
+/// present가 semaphore를 다 기다렸는지는 알 수 없으므로 frame마다 semaphore를 돌려쓰면 아직 present가 기다리는 semaphore를 다시 signal할 수 있음  
+/// 같은 image를 다시 acquire했다면 그 image의 이전 present는 끝났으므로 image마다 semaphore를 두면 안전함  
+/// swapchain을 다시 생성하면 image의 개수가 바뀔 수 있으므로 `semaphores`에 있던 이전 semaphore를 파괴하고 새로 만듦
+pub unsafe fn create_render_finished_semaphores(
+    device: &Device,
+    swapchain: &Swapchain,
+    semaphores: &mut Vec<vk::Semaphore>,
+) -> Result<()> {
+    semaphores
+        .drain(..)
+        .for_each(|s| device.destroy_semaphore(s, None));
+
+    let semaphore_info = vk::SemaphoreCreateInfo::builder();
+    *semaphores = swapchain
+        .images
+        .iter()
+        .map(|_| device.create_semaphore(&semaphore_info, None))
+        .collect::<Result<_, _>>()?;
+
+    Ok(())
+}
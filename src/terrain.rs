@@ -0,0 +1,345 @@
+//! heightmap에서 terrain mesh를 만들고 chunk로 나누어 거리에 따라 LOD를 고르는 기능  
+//! 모든 chunk가 같은 크기의 vertex grid를 가지므로 LOD마다 만든 index 배열을 모든 chunk가 함께 사용함  
+//! LOD가 다른 chunk 사이에 생기는 틈은 chunk의 가장자리에서 아래로 늘어뜨린 skirt로 가림
+
+use anyhow::{anyhow, Result};
+use cgmath::{vec3, InnerSpace, Point3};
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::gltf_model::MaterialImage;
+
+/// 0.0 ~ 1.0으로 정규화한 높이를 저장한 grayscale image
+#[derive(Clone, Debug)]
+pub struct Heightmap {
+    pub width: u32,
+    pub height: u32,
+    heights: Vec<f32>,
+}
+
+impl Heightmap {
+    /// PNG 파일의 첫 번째 channel을 높이로 읽음  
+    /// 8bit와 16bit를 모두 지원하며, 16bit image를 사용하면 완만한 경사에서 계단이 생기지 않음
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        // palette와 8bit보다 작은 grayscale을 8bit로 펼침
+        decoder.set_transformations(png::Transformations::EXPAND);
+        let mut reader = decoder.read_info()?;
+
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels)?;
+
+        let channels = info.color_type.samples();
+        let heights = match info.bit_depth {
+            png::BitDepth::Eight => pixels[..info.buffer_size()]
+                .chunks_exact(channels)
+                .map(|p| p[0] as f32 / u8::MAX as f32)
+                .collect(),
+            // 16bit sample은 big endian으로 저장됨
+            png::BitDepth::Sixteen => pixels[..info.buffer_size()]
+                .chunks_exact(channels * 2)
+                .map(|p| u16::from_be_bytes([p[0], p[1]]) as f32 / u16::MAX as f32)
+                .collect(),
+            depth => {
+                return Err(anyhow!(
+                    "Unsupported heightmap bit depth {:?} in `{}`.",
+                    depth,
+                    path.display()
+                ))
+            }
+        };
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            heights,
+        })
+    }
+
+    /// (`x`, `y`) texel의 높이  
+    /// 범위를 벗어나면 가장 가까운 가장자리의 높이를 사용함
+    pub fn get(&self, x: i64, y: i64) -> f32 {
+        let x = x.clamp(0, self.width as i64 - 1) as usize;
+        let y = y.clamp(0, self.height as i64 - 1) as usize;
+        self.heights[y * self.width as usize + x]
+    }
+}
+
+/// heightmap을 terrain mesh로 바꿀 때 사용하는 설정
+#[derive(Copy, Clone, Debug)]
+pub struct TerrainSettings {
+    /// chunk 한 변의 사각형 수  
+    /// 가장 낮은 LOD에서도 사각형이 하나는 남도록 `2^(lod_count - 1)`의 배수여야 함
+    pub chunk_size: u32,
+    /// LOD의 수  
+    /// LOD가 하나 높아질 때마다 사각형 한 변의 길이가 2배가 됨
+    pub lod_count: u32,
+    /// heightmap의 texel 사이의 world 거리
+    pub spacing: f32,
+    /// 높이 1.0에 해당하는 world 높이
+    pub height_scale: f32,
+    /// chunk 가장자리에서 skirt를 늘어뜨리는 깊이
+    pub skirt_depth: f32,
+}
+
+impl Default for TerrainSettings {
+    fn default() -> Self {
+        Self {
+            chunk_size: 32,
+            lod_count: 4,
+            spacing: 0.25,
+            height_scale: 16.0,
+            skirt_depth: 1.0,
+        }
+    }
+}
+
+/// terrain을 이루는 chunk
+#[derive(Copy, Clone, Debug)]
+pub struct Chunk {
+    /// chunk의 첫 vertex의 index  
+    /// draw할 때 vertex offset으로 사용하면 모든 chunk가 같은 index를 사용할 수 있음
+    pub vertex_offset: u32,
+    /// chunk를 감싸는 bounding sphere의 중심
+    pub center: Point3<f32>,
+    /// chunk를 감싸는 bounding sphere의 반지름
+    pub radius: f32,
+}
+
+impl Chunk {
+    /// `position`에서 chunk의 bounding sphere까지의 거리  
+    /// `position`이 sphere 안에 있으면 0
+    pub fn distance(&self, position: Point3<f32>) -> f32 {
+        ((position - self.center).magnitude() - self.radius).max(0.0)
+    }
+}
+
+/// chunk로 나눈 terrain mesh  
+/// z축이 위를 향하고 terrain의 중심이 원점에 오도록 배치됨
+#[derive(Clone, Debug, Default)]
+pub struct TerrainMesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// terrain 전체를 0.0 ~ 1.0으로 덮는 texture 좌표  
+    /// splat map은 그대로, 반복되는 layer texture는 값을 곱해서 사용함
+    pub tex_coords: Vec<[f32; 2]>,
+    pub chunks: Vec<Chunk>,
+    /// LOD마다 chunk 하나를 그리는 index  
+    /// 0번이 가장 자세한 LOD임
+    pub lods: Vec<Vec<u32>>,
+}
+
+impl TerrainMesh {
+    /// `heightmap`을 `settings`의 chunk 크기로 나누어 mesh를 생성  
+    /// chunk 크기로 나누어떨어지지 않는 heightmap의 가장자리는 사용하지 않음
+    pub fn new(heightmap: &Heightmap, settings: &TerrainSettings) -> Result<Self> {
+        let size = settings.chunk_size;
+        let lod_count = settings.lod_count.max(1);
+        let coarsest_step = 1 << (lod_count - 1);
+        if size == 0 || size % coarsest_step != 0 {
+            return Err(anyhow!(
+                "Chunk size {} must be a multiple of {}.",
+                size,
+                coarsest_step
+            ));
+        }
+
+        let chunks_x = (heightmap.width.saturating_sub(1)) / size;
+        let chunks_y = (heightmap.height.saturating_sub(1)) / size;
+        if chunks_x == 0 || chunks_y == 0 {
+            return Err(anyhow!(
+                "Heightmap ({}x{}) is smaller than a chunk ({}x{}).",
+                heightmap.width,
+                heightmap.height,
+                size + 1,
+                size + 1
+            ));
+        }
+
+        let mut mesh = Self::default();
+        let (texels_x, texels_y) = ((chunks_x * size) as f32, (chunks_y * size) as f32);
+        for chunk_y in 0..chunks_y {
+            for chunk_x in 0..chunks_x {
+                let origin = (chunk_x * size, chunk_y * size);
+                mesh.add_chunk(heightmap, settings, origin, (texels_x, texels_y));
+            }
+        }
+
+        mesh.lods = (0..lod_count)
+            .map(|lod| lod_indices(size, 1 << lod))
+            .collect();
+
+        Ok(mesh)
+    }
+
+    /// heightmap의 `origin` texel부터 chunk 하나의 grid와 skirt vertex를 추가
+    fn add_chunk(
+        &mut self,
+        heightmap: &Heightmap,
+        settings: &TerrainSettings,
+        origin: (u32, u32),
+        texels: (f32, f32),
+    ) {
+        let size = settings.chunk_size;
+        let vertex_offset = self.positions.len() as u32;
+
+        // terrain의 중심이 원점에 오도록 옮김
+        let position = |x: u32, y: u32| {
+            let height = heightmap.get(x as i64, y as i64);
+            [
+                (x as f32 - texels.0 / 2.0) * settings.spacing,
+                (y as f32 - texels.1 / 2.0) * settings.spacing,
+                height * settings.height_scale,
+            ]
+        };
+
+        // 양 옆 texel의 높이 차이로 경사를 구해서 normal을 계산함
+        let normal = |x: u32, y: u32| {
+            let (x, y) = (x as i64, y as i64);
+            let scale = settings.height_scale / (2.0 * settings.spacing);
+            let dx = (heightmap.get(x + 1, y) - heightmap.get(x - 1, y)) * scale;
+            let dy = (heightmap.get(x, y + 1) - heightmap.get(x, y - 1)) * scale;
+            vec3(-dx, -dy, 1.0).normalize().into()
+        };
+
+        let tex_coord = |x: u32, y: u32| [x as f32 / texels.0, y as f32 / texels.1];
+
+        for y in 0..=size {
+            for x in 0..=size {
+                let (x, y) = (origin.0 + x, origin.1 + y);
+                self.positions.push(position(x, y));
+                self.normals.push(normal(x, y));
+                self.tex_coords.push(tex_coord(x, y));
+            }
+        }
+
+        // skirt는 가장자리의 vertex를 `skirt_depth`만큼 아래로 내린 vertex로 이루어짐
+        for edge in 0..4 {
+            for i in 0..=size {
+                let (x, y) = edge_vertex(edge, i, size);
+                let (x, y) = (origin.0 + x, origin.1 + y);
+                let [px, py, pz] = position(x, y);
+                self.positions.push([px, py, pz - settings.skirt_depth]);
+                self.normals.push(normal(x, y));
+                self.tex_coords.push(tex_coord(x, y));
+            }
+        }
+
+        // bounding sphere는 grid vertex의 AABB를 감싸도록 정함
+        let grid = &self.positions[vertex_offset as usize..];
+        let (min, max) = grid
+            .iter()
+            .fold(([f32::MAX; 3], [f32::MIN; 3]), |(min, max), p| {
+                (
+                    [min[0].min(p[0]), min[1].min(p[1]), min[2].min(p[2])],
+                    [max[0].max(p[0]), max[1].max(p[1]), max[2].max(p[2])],
+                )
+            });
+        let center = Point3::new(
+            (min[0] + max[0]) / 2.0,
+            (min[1] + max[1]) / 2.0,
+            (min[2] + max[2]) / 2.0,
+        );
+        let radius = (Point3::from(max) - center).magnitude();
+
+        self.chunks.push(Chunk {
+            vertex_offset,
+            center,
+            radius,
+        });
+    }
+
+    /// chunk 하나의 vertex 수
+    pub fn chunk_vertex_count(&self) -> usize {
+        self.positions.len() / self.chunks.len().max(1)
+    }
+}
+
+/// `distance`만큼 떨어진 chunk에 사용할 LOD  
+/// `base_distance`까지는 0번 LOD를 사용하고, 그 뒤로는 거리가 2배가 될 때마다 LOD가 하나씩 높아짐
+pub fn select_lod(distance: f32, base_distance: f32, lod_count: usize) -> usize {
+    if distance < base_distance {
+        return 0;
+    }
+
+    let lod = (distance / base_distance).log2().floor() as usize + 1;
+    lod.min(lod_count.saturating_sub(1))
+}
+
+/// `edge`번 가장자리의 `i`번째 vertex의 chunk 안에서의 좌표  
+/// 0: 아래쪽(y = 0), 1: 위쪽(y = size), 2: 왼쪽(x = 0), 3: 오른쪽(x = size)
+fn edge_vertex(edge: u32, i: u32, size: u32) -> (u32, u32) {
+    match edge {
+        0 => (i, 0),
+        1 => (i, size),
+        2 => (0, i),
+        _ => (size, i),
+    }
+}
+
+/// `step`개의 texel마다 vertex를 사용해서 chunk를 그리는 index  
+/// 위(+z)에서 보았을 때 반시계 방향이 앞면이 되도록 삼각형을 만듦
+fn lod_indices(size: u32, step: u32) -> Vec<u32> {
+    let row = size + 1;
+    let grid = |x: u32, y: u32| y * row + x;
+    let skirt = |edge: u32, i: u32| row * row + edge * row + i;
+
+    let mut indices = vec![];
+    for y in (0..size).step_by(step as usize) {
+        for x in (0..size).step_by(step as usize) {
+            let (a, b) = (grid(x, y), grid(x + step, y));
+            let (c, d) = (grid(x, y + step), grid(x + step, y + step));
+            indices.extend([a, b, d, a, d, c]);
+        }
+    }
+
+    // skirt는 가장자리마다 바깥을 보는 방향이 다르므로 양면을 모두 그림
+    for edge in 0..4 {
+        for i in (0..size).step_by(step as usize) {
+            let (x0, y0) = edge_vertex(edge, i, size);
+            let (x1, y1) = edge_vertex(edge, i + step, size);
+            let (a, b) = (grid(x0, y0), grid(x1, y1));
+            let (c, d) = (skirt(edge, i), skirt(edge, i + step));
+            indices.extend([a, c, d, a, d, b]);
+            indices.extend([a, d, c, a, b, d]);
+        }
+    }
+
+    indices
+}
+
+/// splat map이 없을 때 높이와 경사로 layer의 비율을 정한 splat map을 생성  
+/// r: 모래(낮은 곳), g: 풀, b: 바위(가파른 곳), a: 눈(높은 곳)
+pub fn generate_splat_map(heightmap: &Heightmap, settings: &TerrainSettings) -> MaterialImage {
+    let mut pixels = Vec::with_capacity((heightmap.width * heightmap.height * 4) as usize);
+    let smoothstep = |edge0: f32, edge1: f32, x: f32| {
+        let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    };
+
+    for y in 0..heightmap.height as i64 {
+        for x in 0..heightmap.width as i64 {
+            let height = heightmap.get(x, y);
+            let scale = settings.height_scale / (2.0 * settings.spacing);
+            let dx = (heightmap.get(x + 1, y) - heightmap.get(x - 1, y)) * scale;
+            let dy = (heightmap.get(x, y + 1) - heightmap.get(x, y - 1)) * scale;
+            let slope = (dx * dx + dy * dy).sqrt();
+
+            let rock = smoothstep(0.6, 1.2, slope);
+            let snow = smoothstep(0.65, 0.8, height) * (1.0 - rock);
+            let sand = (1.0 - smoothstep(0.1, 0.2, height)) * (1.0 - rock);
+            let grass = (1.0 - rock - snow - sand).max(0.0);
+
+            pixels.extend([sand, grass, rock, snow].map(|w| (w * u8::MAX as f32).round() as u8));
+        }
+    }
+
+    MaterialImage {
+        width: heightmap.width,
+        height: heightmap.height,
+        pixels,
+    }
+}
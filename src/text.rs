@@ -0,0 +1,700 @@
+//! font의 glyph를 하나의 texture(atlas)에 미리 그려두고 글자마다 quad를 만들어서 text를 그리는 renderer  
+//! `queue_text`로 쌓아둔 text를 swapchain image마다 가진 vertex buffer에 올려서 기존 render pass의 마지막 draw로 그림
+
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+use anyhow::{anyhow, Result};
+use vulkanalia::prelude::v1_0::*;
+
+use crate::allocator::{Allocation, Allocator};
+use crate::pipeline::create_shader_module;
+
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::ptr::copy_nonoverlapping as memcpy;
+
+/// atlas에 미리 그려두는 문자  
+/// HUD에는 ASCII의 출력 가능한 문자만 사용함
+const CHARACTERS: std::ops::RangeInclusive<char> = ' '..='~';
+
+/// atlas에 없는 문자 대신 그릴 문자
+const FALLBACK_CHARACTER: char = '?';
+
+/// atlas의 가로 크기
+const ATLAS_WIDTH: u32 = 512;
+
+/// linear filtering으로 읽을 때 이웃한 glyph가 섞이지 않도록 glyph 사이에 두는 간격
+const ATLAS_PADDING: u32 = 1;
+
+/// atlas 안에서의 glyph 위치와 배치 정보
+#[derive(Copy, Clone, Debug, Default)]
+struct GlyphInfo {
+    uv_min: [f32; 2],
+    uv_max: [f32; 2],
+    // pen 위치(baseline)에서 quad의 왼쪽 위 모서리까지의 거리 (pixel 단위)
+    offset: [f32; 2],
+    size: [f32; 2],
+    // 다음 글자까지 pen을 움직이는 거리
+    advance: f32,
+}
+
+/// glyph quad의 vertex  
+/// 위치는 화면 왼쪽 위를 원점으로 하는 pixel 단위임
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+struct TextVertex {
+    pos: [f32; 2],
+    uv: [f32; 2],
+    color: [f32; 4],
+}
+
+/// swapchain image마다 존재하는 glyph quad의 vertex를 담는 buffer
+#[derive(Copy, Clone, Debug, Default)]
+struct VertexBuffer {
+    buffer: vk::Buffer,
+    memory: Allocation,
+    size: vk::DeviceSize,
+}
+
+/// font atlas와 text를 그리기 위한 Vulkan 오브젝트
+#[derive(Clone, Debug)]
+pub struct TextRenderer {
+    glyphs: HashMap<char, GlyphInfo>,
+    // 한 줄의 baseline에서 다음 줄의 baseline까지의 거리
+    line_height: f32,
+    // 줄의 위쪽 끝에서 baseline까지의 거리
+    ascent: f32,
+    atlas_image: vk::Image,
+    atlas_memory: Allocation,
+    atlas_view: vk::ImageView,
+    sampler: vk::Sampler,
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+    descriptor_set: vk::DescriptorSet,
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+    vertex_buffers: Vec<VertexBuffer>,
+    // 다음 `cmd_draw`에서 그릴 glyph quad
+    vertices: Vec<TextVertex>,
+}
+
+impl TextRenderer {
+    /// `font`(TrueType 또는 OpenType)를 `pixel_size` 크기로 atlas에 그리고 pipeline을 제외한 Vulkan 오브젝트를 생성  
+    /// atlas는 `queue`에서 upload하므로 `queue_family`는 `queue`가 속한 queue family여야 함
+    pub unsafe fn new(
+        device: &Device,
+        allocator: &mut Allocator,
+        queue: vk::Queue,
+        queue_family: u32,
+        font: &[u8],
+        pixel_size: f32,
+    ) -> Result<Self> {
+        let font = FontRef::try_from_slice(font)?;
+        let font = font.as_scaled(PxScale::from(pixel_size));
+
+        // glyph를 왼쪽 위부터 한 줄씩 채워나가고, 줄의 높이는 그 줄에서 가장 높은 glyph에 맞춤
+        let mut glyphs = HashMap::new();
+        let mut placements = vec![];
+        let (mut x, mut y, mut row_height) = (ATLAS_PADDING, ATLAS_PADDING, 0);
+        for c in CHARACTERS {
+            let id = font.glyph_id(c);
+            let advance = font.h_advance(id);
+
+            // 공백처럼 outline이 없는 glyph는 pen만 움직임
+            let Some(outlined) = font.outline_glyph(font.scaled_glyph(c)) else {
+                let info = GlyphInfo {
+                    advance,
+                    ..Default::default()
+                };
+                glyphs.insert(c, info);
+                continue;
+            };
+
+            let bounds = outlined.px_bounds();
+            let width = bounds.width() as u32;
+            let height = bounds.height() as u32;
+            if width + 2 * ATLAS_PADDING > ATLAS_WIDTH {
+                return Err(anyhow!("Glyph `{}` is too large for the font atlas.", c));
+            }
+
+            if x + width + ATLAS_PADDING > ATLAS_WIDTH {
+                x = ATLAS_PADDING;
+                y += row_height + ATLAS_PADDING;
+                row_height = 0;
+            }
+
+            placements.push((c, outlined, x, y));
+            glyphs.insert(
+                c,
+                GlyphInfo {
+                    offset: [bounds.min.x, bounds.min.y],
+                    size: [width as f32, height as f32],
+                    advance,
+                    ..Default::default()
+                },
+            );
+
+            x += width + ATLAS_PADDING;
+            row_height = row_height.max(height);
+        }
+        let atlas_height = y + row_height + ATLAS_PADDING;
+
+        // coverage를 R8 texel 하나에 저장
+        let mut pixels = vec![0u8; (ATLAS_WIDTH * atlas_height) as usize];
+        for (c, outlined, x, y) in &placements {
+            outlined.draw(|gx, gy, coverage| {
+                let index = (y + gy) * ATLAS_WIDTH + x + gx;
+                pixels[index as usize] = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+            });
+
+            let glyph = glyphs.get_mut(c).unwrap();
+            glyph.uv_min = [
+                *x as f32 / ATLAS_WIDTH as f32,
+                *y as f32 / atlas_height as f32,
+            ];
+            glyph.uv_max = [
+                (*x as f32 + glyph.size[0]) / ATLAS_WIDTH as f32,
+                (*y as f32 + glyph.size[1]) / atlas_height as f32,
+            ];
+        }
+
+        let (atlas_image, atlas_memory) = create_atlas_image(
+            device,
+            allocator,
+            queue,
+            queue_family,
+            &pixels,
+            ATLAS_WIDTH,
+            atlas_height,
+        )?;
+
+        let subresource_range = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(0)
+            .level_count(1)
+            .base_array_layer(0)
+            .layer_count(1);
+        let info = vk::ImageViewCreateInfo::builder()
+            .image(atlas_image)
+            .view_type(vk::ImageViewType::_2D)
+            .format(vk::Format::R8_UNORM)
+            .subresource_range(subresource_range);
+        let atlas_view = device.create_image_view(&info, None)?;
+
+        let info = vk::SamplerCreateInfo::builder()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE);
+        let sampler = device.create_sampler(&info, None)?;
+
+        let binding = vk::DescriptorSetLayoutBinding::builder()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+        let bindings = &[binding];
+        let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(bindings);
+        let descriptor_set_layout = device.create_descriptor_set_layout(&info, None)?;
+
+        // atlas는 하나뿐이므로 descriptor set도 하나만 할당함
+        let pool_size = vk::DescriptorPoolSize::builder()
+            .type_(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(1);
+        let pool_sizes = &[pool_size];
+        let info = vk::DescriptorPoolCreateInfo::builder()
+            .pool_sizes(pool_sizes)
+            .max_sets(1);
+        let descriptor_pool = device.create_descriptor_pool(&info, None)?;
+
+        let layouts = &[descriptor_set_layout];
+        let info = vk::DescriptorSetAllocateInfo::builder()
+            .descriptor_pool(descriptor_pool)
+            .set_layouts(layouts);
+        let descriptor_set = device.allocate_descriptor_sets(&info)?[0];
+
+        let image_info = vk::DescriptorImageInfo::builder()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(atlas_view)
+            .sampler(sampler);
+        let image_infos = &[image_info];
+        let write = vk::WriteDescriptorSet::builder()
+            .dst_set(descriptor_set)
+            .dst_binding(0)
+            .dst_array_element(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .image_info(image_infos);
+        device.update_descriptor_sets(&[write], &[] as &[vk::CopyDescriptorSet]);
+
+        Ok(Self {
+            glyphs,
+            line_height: font.height() + font.line_gap(),
+            ascent: font.ascent(),
+            atlas_image,
+            atlas_memory,
+            atlas_view,
+            sampler,
+            descriptor_set_layout,
+            descriptor_pool,
+            descriptor_set,
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+            vertex_buffers: vec![],
+            vertices: vec![],
+        })
+    }
+
+    /// 한 줄의 높이 (pixel 단위)
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    /// `text`를 다음 `cmd_draw`에서 그리도록 추가  
+    /// `position`은 첫 줄의 왼쪽 위 모서리(pixel 단위)이며 `\n`을 만나면 다음 줄로 넘어감  
+    /// `color`는 linear RGBA이고 alpha는 glyph의 coverage와 곱해짐
+    pub fn queue_text(&mut self, text: &str, position: [f32; 2], color: [f32; 4]) {
+        let [left, top] = position;
+        // glyph가 texel 경계에 맞도록 baseline을 pixel 단위로 맞춤
+        let mut pen = [left.round(), (top + self.ascent).round()];
+
+        for c in text.chars() {
+            if c == '\n' {
+                pen = [left.round(), (pen[1] + self.line_height).round()];
+                continue;
+            }
+
+            let glyph = match self.glyphs.get(&c) {
+                Some(glyph) => *glyph,
+                None => self.glyphs[&FALLBACK_CHARACTER],
+            };
+
+            if glyph.size[0] > 0.0 && glyph.size[1] > 0.0 {
+                let x0 = pen[0] + glyph.offset[0];
+                let y0 = pen[1] + glyph.offset[1];
+                let x1 = x0 + glyph.size[0];
+                let y1 = y0 + glyph.size[1];
+                let [u0, v0] = glyph.uv_min;
+                let [u1, v1] = glyph.uv_max;
+
+                let vertex = |pos, uv| TextVertex { pos, uv, color };
+                self.vertices.extend_from_slice(&[
+                    vertex([x0, y0], [u0, v0]),
+                    vertex([x0, y1], [u0, v1]),
+                    vertex([x1, y1], [u1, v1]),
+                    vertex([x0, y0], [u0, v0]),
+                    vertex([x1, y1], [u1, v1]),
+                    vertex([x1, y0], [u1, v0]),
+                ]);
+            }
+
+            pen[0] += glyph.advance;
+        }
+    }
+
+    /// `render_pass`의 `subpass`에서 text를 그리는 pipeline을 생성  
+    /// render pass가 다시 생성되면 `destroy_pipeline`을 호출한 뒤 다시 생성해야 함
+    pub unsafe fn create_pipeline(
+        &mut self,
+        device: &Device,
+        render_pass: vk::RenderPass,
+        subpass: u32,
+        samples: vk::SampleCountFlags,
+        pipeline_cache: vk::PipelineCache,
+    ) -> Result<()> {
+        let vert = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/text/vert.spv"));
+        let frag = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/text/frag.spv"));
+
+        let vert_shader_module = create_shader_module(device, &vert[..])?;
+        let frag_shader_module = create_shader_module(device, &frag[..])?;
+
+        let vert_stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(b"main\0");
+        let frag_stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(b"main\0");
+
+        let binding_description = vk::VertexInputBindingDescription::builder()
+            .binding(0)
+            .stride(size_of::<TextVertex>() as u32)
+            .input_rate(vk::VertexInputRate::VERTEX);
+        let pos = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(0)
+            .format(vk::Format::R32G32_SFLOAT)
+            .offset(0);
+        let uv = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(1)
+            .format(vk::Format::R32G32_SFLOAT)
+            .offset(8);
+        let color = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(2)
+            .format(vk::Format::R32G32B32A32_SFLOAT)
+            .offset(16);
+
+        let binding_descriptions = &[binding_description];
+        let attribute_descriptions = &[pos, uv, color];
+        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
+            .vertex_binding_descriptions(binding_descriptions)
+            .vertex_attribute_descriptions(attribute_descriptions);
+
+        let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::builder()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        // viewport와 scissor는 dynamic state로 지정하므로 개수만 알려줌
+        let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+            .viewport_count(1)
+            .scissor_count(1);
+
+        let rasterization_state = vk::PipelineRasterizationStateCreateInfo::builder()
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE);
+
+        let multisample_state =
+            vk::PipelineMultisampleStateCreateInfo::builder().rasterization_samples(samples);
+
+        // text는 scene 위에 그려지므로 depth를 비교하거나 기록하지 않음
+        let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::builder()
+            .depth_test_enable(false)
+            .depth_write_enable(false);
+
+        // fragment shader가 coverage를 alpha로 출력하므로 일반적인 alpha blending을 사용
+        let attachment = vk::PipelineColorBlendAttachmentState::builder()
+            .color_write_mask(vk::ColorComponentFlags::all())
+            .blend_enable(true)
+            .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
+            .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+            .color_blend_op(vk::BlendOp::ADD)
+            .src_alpha_blend_factor(vk::BlendFactor::ONE)
+            .dst_alpha_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+            .alpha_blend_op(vk::BlendOp::ADD);
+        let attachments = &[attachment];
+        let color_blend_state =
+            vk::PipelineColorBlendStateCreateInfo::builder().attachments(attachments);
+
+        let dynamic_states = &[vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state =
+            vk::PipelineDynamicStateCreateInfo::builder().dynamic_states(dynamic_states);
+
+        // 화면의 크기(pixel 단위)를 push constant로 전달
+        let push_constant_range = vk::PushConstantRange::builder()
+            .stage_flags(vk::ShaderStageFlags::VERTEX)
+            .offset(0)
+            .size(8);
+
+        let set_layouts = &[self.descriptor_set_layout];
+        let push_constant_ranges = &[push_constant_range];
+        let layout_info = vk::PipelineLayoutCreateInfo::builder()
+            .set_layouts(set_layouts)
+            .push_constant_ranges(push_constant_ranges);
+        self.pipeline_layout = device.create_pipeline_layout(&layout_info, None)?;
+
+        let stages = &[vert_stage, frag_stage];
+        let info = vk::GraphicsPipelineCreateInfo::builder()
+            .stages(stages)
+            .vertex_input_state(&vertex_input_state)
+            .input_assembly_state(&input_assembly_state)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterization_state)
+            .multisample_state(&multisample_state)
+            .depth_stencil_state(&depth_stencil_state)
+            .color_blend_state(&color_blend_state)
+            .dynamic_state(&dynamic_state)
+            .layout(self.pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(subpass);
+
+        self.pipeline = device
+            .create_graphics_pipelines(pipeline_cache, &[info], None)?
+            .0[0];
+
+        device.destroy_shader_module(vert_shader_module, None);
+        device.destroy_shader_module(frag_shader_module, None);
+
+        Ok(())
+    }
+
+    /// `create_pipeline`으로 생성한 pipeline을 파괴
+    pub unsafe fn destroy_pipeline(&mut self, device: &Device) {
+        device.destroy_pipeline(self.pipeline, None);
+        device.destroy_pipeline_layout(self.pipeline_layout, None);
+        self.pipeline = vk::Pipeline::null();
+        self.pipeline_layout = vk::PipelineLayout::null();
+    }
+
+    /// `queue_text`로 추가한 text를 그리는 command를 기록하고 쌓아둔 text를 비움  
+    /// render pass 안에서 호출해야 하며 viewport와 scissor를 덮어씀  
+    /// `image_index`의 buffer를 다시 쓰므로 해당 swapchain image를 사용하는 이전 frame이 끝난 뒤에 호출해야 함
+    pub unsafe fn cmd_draw(
+        &mut self,
+        device: &Device,
+        allocator: &mut Allocator,
+        command_buffer: vk::CommandBuffer,
+        image_index: usize,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        let vertices = std::mem::take(&mut self.vertices);
+        if vertices.is_empty() {
+            return Ok(());
+        }
+
+        let size = (vertices.len() * size_of::<TextVertex>()) as vk::DeviceSize;
+
+        if self.vertex_buffers.len() <= image_index {
+            self.vertex_buffers
+                .resize(image_index + 1, VertexBuffer::default());
+        }
+
+        // buffer가 부족하면 다시 생성함
+        // 이 swapchain image를 사용하는 이전 frame은 끝났으므로 기존 buffer를 바로 파괴할 수 있음
+        let mut vertex_buffer = self.vertex_buffers[image_index];
+        if vertex_buffer.size < size {
+            device.destroy_buffer(vertex_buffer.buffer, None);
+            allocator.free(vertex_buffer.memory);
+
+            // 자주 다시 만들지 않도록 여유를 두고 할당
+            let size = size.next_power_of_two();
+            let info = vk::BufferCreateInfo::builder()
+                .size(size)
+                .usage(vk::BufferUsageFlags::VERTEX_BUFFER)
+                .sharing_mode(vk::SharingMode::EXCLUSIVE);
+            let (buffer, memory) = allocator.create_buffer(
+                device,
+                &info,
+                vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+            )?;
+
+            vertex_buffer = VertexBuffer {
+                buffer,
+                memory,
+                size,
+            };
+            self.vertex_buffers[image_index] = vertex_buffer;
+        }
+
+        memcpy(
+            vertices.as_ptr(),
+            vertex_buffer.memory.mapped_ptr()?.cast(),
+            vertices.len(),
+        );
+
+        device.cmd_bind_pipeline(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            self.pipeline,
+        );
+        device.cmd_bind_vertex_buffers(command_buffer, 0, &[vertex_buffer.buffer], &[0]);
+        device.cmd_bind_descriptor_sets(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            self.pipeline_layout,
+            0,
+            &[self.descriptor_set],
+            &[],
+        );
+
+        let viewport = vk::Viewport::builder()
+            .x(0.0)
+            .y(0.0)
+            .width(extent.width as f32)
+            .height(extent.height as f32)
+            .min_depth(0.0)
+            .max_depth(1.0);
+        device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+
+        let scissor = vk::Rect2D::builder()
+            .offset(vk::Offset2D::default())
+            .extent(extent);
+        device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+
+        let screen_size = [extent.width as f32, extent.height as f32];
+        let screen_size_bytes = std::slice::from_raw_parts(screen_size.as_ptr().cast::<u8>(), 8);
+        device.cmd_push_constants(
+            command_buffer,
+            self.pipeline_layout,
+            vk::ShaderStageFlags::VERTEX,
+            0,
+            screen_size_bytes,
+        );
+
+        device.cmd_draw(command_buffer, vertices.len() as u32, 1, 0, 0);
+
+        // 다음 frame에서 vector의 capacity를 다시 사용함
+        self.vertices = vertices;
+        self.vertices.clear();
+
+        Ok(())
+    }
+
+    /// 모든 Vulkan 오브젝트를 파괴  
+    /// device가 idle 상태일 때 `allocator`를 파괴하기 전에 호출해야 함
+    pub unsafe fn destroy(&mut self, device: &Device, allocator: &mut Allocator) {
+        self.destroy_pipeline(device);
+
+        for vertex_buffer in self.vertex_buffers.drain(..) {
+            device.destroy_buffer(vertex_buffer.buffer, None);
+            allocator.free(vertex_buffer.memory);
+        }
+
+        // descriptor set은 pool과 함께 해제됨
+        device.destroy_descriptor_pool(self.descriptor_pool, None);
+        device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+        device.destroy_sampler(self.sampler, None);
+        device.destroy_image_view(self.atlas_view, None);
+        device.destroy_image(self.atlas_image, None);
+        allocator.free(self.atlas_memory);
+    }
+}
+
+/// `pixels`를 담은 `width` x `height` 크기의 R8 atlas image를 생성  
+/// upload가 끝날 때까지 기다리므로 반환된 image는 바로 shader에서 읽을 수 있음
+unsafe fn create_atlas_image(
+    device: &Device,
+    allocator: &mut Allocator,
+    queue: vk::Queue,
+    queue_family: u32,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+) -> Result<(vk::Image, Allocation)> {
+    let info = vk::ImageCreateInfo::builder()
+        .image_type(vk::ImageType::_2D)
+        .extent(vk::Extent3D {
+            width,
+            height,
+            depth: 1,
+        })
+        .mip_levels(1)
+        .array_layers(1)
+        .format(vk::Format::R8_UNORM)
+        .tiling(vk::ImageTiling::OPTIMAL)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .usage(vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE)
+        .samples(vk::SampleCountFlags::_1);
+    let (image, memory) =
+        allocator.create_image(device, &info, vk::MemoryPropertyFlags::DEVICE_LOCAL)?;
+
+    // staging buffer 생성
+    let info = vk::BufferCreateInfo::builder()
+        .size(pixels.len() as vk::DeviceSize)
+        .usage(vk::BufferUsageFlags::TRANSFER_SRC)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE);
+    let (staging_buffer, staging_buffer_memory) = allocator.create_buffer(
+        device,
+        &info,
+        vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+    )?;
+
+    memcpy(
+        pixels.as_ptr(),
+        staging_buffer_memory.mapped_ptr()?.cast(),
+        pixels.len(),
+    );
+
+    // atlas는 한 번만 upload하므로 command pool도 upload가 끝나면 바로 파괴함
+    let info = vk::CommandPoolCreateInfo::builder()
+        .flags(vk::CommandPoolCreateFlags::TRANSIENT)
+        .queue_family_index(queue_family);
+    let command_pool = device.create_command_pool(&info, None)?;
+
+    let info = vk::CommandBufferAllocateInfo::builder()
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_pool(command_pool)
+        .command_buffer_count(1);
+    let command_buffer = device.allocate_command_buffers(&info)?[0];
+
+    let info =
+        vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+    device.begin_command_buffer(command_buffer, &info)?;
+
+    let subresource_range = vk::ImageSubresourceRange::builder()
+        .aspect_mask(vk::ImageAspectFlags::COLOR)
+        .base_mip_level(0)
+        .level_count(1)
+        .base_array_layer(0)
+        .layer_count(1);
+
+    let barrier = vk::ImageMemoryBarrier::builder()
+        .old_layout(vk::ImageLayout::UNDEFINED)
+        .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .image(image)
+        .subresource_range(subresource_range)
+        .src_access_mask(vk::AccessFlags::empty())
+        .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
+    device.cmd_pipeline_barrier(
+        command_buffer,
+        vk::PipelineStageFlags::TOP_OF_PIPE,
+        vk::PipelineStageFlags::TRANSFER,
+        vk::DependencyFlags::empty(),
+        &[] as &[vk::MemoryBarrier],
+        &[] as &[vk::BufferMemoryBarrier],
+        &[barrier],
+    );
+
+    let subresource = vk::ImageSubresourceLayers::builder()
+        .aspect_mask(vk::ImageAspectFlags::COLOR)
+        .mip_level(0)
+        .base_array_layer(0)
+        .layer_count(1);
+    let region = vk::BufferImageCopy::builder()
+        .buffer_offset(0)
+        .buffer_row_length(0)
+        .buffer_image_height(0)
+        .image_subresource(subresource)
+        .image_offset(vk::Offset3D::default())
+        .image_extent(vk::Extent3D {
+            width,
+            height,
+            depth: 1,
+        });
+    device.cmd_copy_buffer_to_image(
+        command_buffer,
+        staging_buffer,
+        image,
+        vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+        &[region],
+    );
+
+    let barrier = vk::ImageMemoryBarrier::builder()
+        .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+        .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .image(image)
+        .subresource_range(subresource_range)
+        .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+        .dst_access_mask(vk::AccessFlags::SHADER_READ);
+    device.cmd_pipeline_barrier(
+        command_buffer,
+        vk::PipelineStageFlags::TRANSFER,
+        vk::PipelineStageFlags::FRAGMENT_SHADER,
+        vk::DependencyFlags::empty(),
+        &[] as &[vk::MemoryBarrier],
+        &[] as &[vk::BufferMemoryBarrier],
+        &[barrier],
+    );
+
+    device.end_command_buffer(command_buffer)?;
+
+    let command_buffers = &[command_buffer];
+    let info = vk::SubmitInfo::builder().command_buffers(command_buffers);
+    device.queue_submit(queue, &[info], vk::Fence::null())?;
+    device.queue_wait_idle(queue)?;
+
+    device.destroy_command_pool(command_pool, None);
+    device.destroy_buffer(staging_buffer, None);
+    allocator.free(staging_buffer_memory);
+
+    Ok((image, memory))
+}
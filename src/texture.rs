@@ -0,0 +1,202 @@
+//! KTX2 container에 담긴 block compressed texture를 읽는 loader  
+//! Basis Universal(UASTC)로 encode된 texture는 device가 지원하는 block compressed format으로 transcode함  
+//! 모든 mip level이 파일에 들어있으므로 PNG처럼 실행 중에 mipmap을 생성할 필요가 없음
+
+use anyhow::{anyhow, Result};
+use basis_universal::{
+    DecodeFlags, LowLevelUastcTranscoder, SliceParametersUastc, TranscoderBlockFormat,
+};
+use ktx2::{ColorModel, DfdBlockBasic, SupercompressionScheme, TransferFunction};
+use log::*;
+use vulkanalia::prelude::v1_0::*;
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Once;
+
+/// UASTC block 하나가 차지하는 texel의 가로, 세로 크기
+const UASTC_BLOCK_SIZE: u32 = 4;
+
+/// GPU에 그대로 upload할 수 있는 texture  
+/// `levels[0]`이 가장 큰 mip level이며 각 level은 `format`의 block 단위로 빈틈없이 채워져 있음
+#[derive(Clone, Debug)]
+pub struct CompressedTexture {
+    pub format: vk::Format,
+    pub width: u32,
+    pub height: u32,
+    pub levels: Vec<Vec<u8>>,
+}
+
+impl CompressedTexture {
+    /// 모든 level을 이어붙였을 때의 크기
+    pub fn size(&self) -> usize {
+        self.levels.iter().map(|l| l.len()).sum()
+    }
+}
+
+/// UASTC를 transcode할 수 있는 block compressed format  
+/// desktop GPU가 주로 지원하는 BC7부터 mobile GPU가 주로 지원하는 ASTC, ETC2 순서로 시도함
+const TRANSCODE_TARGETS: &[(TranscoderBlockFormat, vk::Format, vk::Format)] = &[
+    (
+        TranscoderBlockFormat::BC7,
+        vk::Format::BC7_SRGB_BLOCK,
+        vk::Format::BC7_UNORM_BLOCK,
+    ),
+    (
+        TranscoderBlockFormat::ASTC_4x4,
+        vk::Format::ASTC_4X4_SRGB_BLOCK,
+        vk::Format::ASTC_4X4_UNORM_BLOCK,
+    ),
+    (
+        TranscoderBlockFormat::ETC2_RGBA,
+        vk::Format::ETC2_R8G8B8A8_SRGB_BLOCK,
+        vk::Format::ETC2_R8G8B8A8_UNORM_BLOCK,
+    ),
+];
+
+/// KTX2 파일을 읽어서 `physical_device`에서 sampling할 수 있는 texture로 변환  
+/// UASTC로 encode된 texture는 지원하는 format으로 transcode하고, 그 외에는 파일의 format을 그대로 사용함  
+/// ETC1S(BasisLZ)로 encode된 texture는 지원하지 않음
+pub unsafe fn load_ktx2(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    path: impl AsRef<Path>,
+) -> Result<CompressedTexture> {
+    let path = path.as_ref();
+    let bytes = fs::read(path)?;
+    let reader = ktx2::Reader::new(&bytes[..])
+        .map_err(|e| anyhow!("Invalid KTX2 file `{}`: {:?}", path.display(), e))?;
+    let header = reader.header();
+
+    if header.pixel_depth > 1 || header.layer_count > 1 || header.face_count > 1 {
+        return Err(anyhow!(
+            "Only 2D KTX2 textures are supported, `{}` is not.",
+            path.display()
+        ));
+    }
+
+    // DFD(data format descriptor)에 texel이 어떻게 encode되어 있는지와 color space가 기록되어 있음
+    let dfd = reader
+        .dfd_blocks()
+        .next()
+        .map(|b| DfdBlockBasic::parse(b.data))
+        .transpose()
+        .map_err(|e| anyhow!("Invalid KTX2 DFD in `{}`: {:?}", path.display(), e))?
+        .ok_or_else(|| anyhow!("Missing KTX2 DFD in `{}`.", path.display()))?;
+    let srgb = dfd.header.transfer_function == Some(TransferFunction::SRGB);
+
+    // zstd로 supercompression된 level은 먼저 압축을 풀어야 함
+    let levels = reader
+        .levels()
+        .map(|level| match header.supercompression_scheme {
+            None => Ok(level.data.to_vec()),
+            Some(SupercompressionScheme::Zstandard) => {
+                let mut decoder = ruzstd::StreamingDecoder::new(level.data)
+                    .map_err(|e| anyhow!("Invalid zstd level data: {:?}", e))?;
+                let mut data = Vec::with_capacity(level.uncompressed_byte_length as usize);
+                decoder.read_to_end(&mut data)?;
+                Ok(data)
+            }
+            Some(scheme) => Err(anyhow!(
+                "Unsupported KTX2 supercompression scheme {:?} in `{}`.",
+                scheme,
+                path.display()
+            )),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let (format, levels) = match dfd.header.color_model {
+        Some(ColorModel::UASTC) => {
+            transcode_uastc(instance, physical_device, &header, &levels, srgb)?
+        }
+        Some(ColorModel::ETC1S) => {
+            return Err(anyhow!(
+                "ETC1S encoded KTX2 textures are not supported, encode `{}` with UASTC.",
+                path.display()
+            ))
+        }
+        _ => {
+            // 이미 GPU format으로 저장된 texture는 device가 지원하는 경우에만 그대로 사용할 수 있음
+            let format = header
+                .format
+                .map(|f| vk::Format::from_raw(f.value() as i32))
+                .ok_or_else(|| anyhow!("Missing KTX2 format in `{}`.", path.display()))?;
+            if !supports_sampling(instance, physical_device, format) {
+                return Err(anyhow!(
+                    "Texture format {:?} of `{}` is not supported.",
+                    format,
+                    path.display()
+                ));
+            }
+            (format, levels)
+        }
+    };
+
+    Ok(CompressedTexture {
+        format,
+        width: header.pixel_width,
+        height: header.pixel_height,
+        levels,
+    })
+}
+
+/// UASTC로 encode된 모든 level을 device가 지원하는 block compressed format으로 transcode
+unsafe fn transcode_uastc(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    header: &ktx2::Header,
+    levels: &[Vec<u8>],
+    srgb: bool,
+) -> Result<(vk::Format, Vec<Vec<u8>>)> {
+    let (block_format, format) = TRANSCODE_TARGETS
+        .iter()
+        .map(|(b, s, u)| (*b, if srgb { *s } else { *u }))
+        .find(|(_, f)| supports_sampling(instance, physical_device, *f))
+        .ok_or_else(|| anyhow!("No supported block compressed format for UASTC textures."))?;
+
+    info!("Transcoding UASTC texture to {:?}.", format);
+
+    // transcoder가 사용하는 table은 한 번만 초기화하면 됨
+    static INIT: Once = Once::new();
+    INIT.call_once(basis_universal::transcoder_init);
+
+    let transcoder = LowLevelUastcTranscoder::new();
+    let levels = levels
+        .iter()
+        .enumerate()
+        .map(|(i, data)| {
+            // 각 level은 이전 level의 절반 크기이며 가장 작아도 1 texel임
+            let width = (header.pixel_width >> i).max(1);
+            let height = (header.pixel_height >> i).max(1);
+            let parameters = SliceParametersUastc {
+                num_blocks_x: width.div_ceil(UASTC_BLOCK_SIZE),
+                num_blocks_y: height.div_ceil(UASTC_BLOCK_SIZE),
+                has_alpha: false,
+                original_width: width,
+                original_height: height,
+            };
+
+            transcoder
+                .transcode_slice(data, parameters, DecodeFlags::HIGH_QUALITY, block_format)
+                .map_err(|e| anyhow!("Failed to transcode level {}: {:?}", i, e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((format, levels))
+}
+
+/// `format`의 image를 optimal tiling으로 만들어서 linear filtering으로 sampling할 수 있는지 확인  
+/// block compressed format은 해당 feature를 device에서 활성화해야 사용할 수 있음
+unsafe fn supports_sampling(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    format: vk::Format,
+) -> bool {
+    let properties = instance.get_physical_device_format_properties(physical_device, format);
+    properties.optimal_tiling_features.contains(
+        vk::FormatFeatureFlags::SAMPLED_IMAGE
+            | vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR
+            | vk::FormatFeatureFlags::TRANSFER_DST,
+    )
+}
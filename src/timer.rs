@@ -0,0 +1,255 @@
+//! frame마다 걸린 시간을 측정해서 FPS와 함께 보고하는 timer
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// 평균을 계산할 때 사용하는 최근 frame의 수
+const HISTORY: usize = 120;
+
+/// 통계를 보고하는 간격
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 최근 frame들의 평균 통계
+#[derive(Copy, Clone, Debug)]
+pub struct FrameStats {
+    /// 평균 FPS
+    pub fps: f32,
+    /// CPU에서 측정한 평균 frame time
+    pub frame_time: Duration,
+    /// timestamp query로 측정한 평균 GPU time  
+    /// GPU time이 기록되지 않았으면 `None`
+    pub gpu_time: Option<Duration>,
+}
+
+impl fmt::Display for FrameStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.1} FPS ({:.2} ms CPU",
+            self.fps,
+            self.frame_time.as_secs_f64() * 1000.0
+        )?;
+        if let Some(gpu_time) = self.gpu_time {
+            write!(f, ", {:.2} ms GPU", gpu_time.as_secs_f64() * 1000.0)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// render loop에서 매 frame마다 `tick`을 호출해서 frame time을 측정하는 timer
+#[derive(Clone, Debug)]
+pub struct FrameTimer {
+    last_frame: Instant,
+    last_report: Instant,
+    frame_times: VecDeque<Duration>,
+    gpu_times: VecDeque<Duration>,
+}
+
+impl Default for FrameTimer {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            last_frame: now,
+            last_report: now,
+            frame_times: VecDeque::with_capacity(HISTORY),
+            gpu_times: VecDeque::with_capacity(HISTORY),
+        }
+    }
+}
+
+impl FrameTimer {
+    /// 새로운 frame을 시작하고 이전 frame부터 걸린 시간을 반환
+    pub fn tick(&mut self) -> Duration {
+        let now = Instant::now();
+        let frame_time = now - self.last_frame;
+        self.last_frame = now;
+
+        push(&mut self.frame_times, frame_time);
+
+        frame_time
+    }
+
+    /// GPU에서 한 frame을 그리는 데 걸린 시간을 기록
+    pub fn record_gpu_time(&mut self, gpu_time: Duration) {
+        push(&mut self.gpu_times, gpu_time);
+    }
+
+    /// 최근 frame들의 평균 통계를 반환  
+    /// 아직 측정된 frame이 없으면 `None`
+    pub fn stats(&self) -> Option<FrameStats> {
+        let frame_time = average(&self.frame_times)?;
+
+        Some(FrameStats {
+            fps: 1.0 / frame_time.as_secs_f32(),
+            frame_time,
+            gpu_time: average(&self.gpu_times),
+        })
+    }
+
+    /// 마지막으로 보고한 뒤 `REPORT_INTERVAL`이 지났으면 통계를 반환  
+    /// window title이나 log를 매 frame 갱신하지 않도록 하기 위해 사용함
+    pub fn report(&mut self) -> Option<FrameStats> {
+        if self.last_report.elapsed() < REPORT_INTERVAL {
+            return None;
+        }
+
+        self.last_report = Instant::now();
+        self.stats()
+    }
+}
+
+/// 오래된 값을 버리고 `HISTORY`개의 최근 값만 유지
+pub(crate) fn push(times: &mut VecDeque<Duration>, time: Duration) {
+    if times.len() == HISTORY {
+        times.pop_front();
+    }
+    times.push_back(time);
+}
+
+/// 최근 값들의 평균  
+/// 값이 없으면 `None`
+pub(crate) fn average(times: &VecDeque<Duration>) -> Option<Duration> {
+    if times.is_empty() {
+        return None;
+    }
+
+    Some(times.iter().sum::<Duration>() / times.len() as u32)
+}
+
+/// 한 frame에서 실행할 수 있는 최대 update 횟수  
+/// update가 render보다 오래 걸리면 밀린 update를 따라잡느라 frame이 더 늦어지는 것을 반복하므로 제한함
+const MAX_STEPS_PER_FRAME: u32 = 8;
+
+/// 화면의 refresh rate와 상관없이 simulation을 일정한 간격으로 update하기 위한 accumulator  
+/// 매 frame `advance`가 반환한 횟수만큼 `step_secs`로 update하고, `alpha`로 이전 상태와 현재 상태를 보간해서 그림
+#[derive(Clone, Debug)]
+pub struct FixedTimestep {
+    step: Duration,
+    // 아직 update에 사용하지 않은 시간
+    accumulator: Duration,
+    last_update: Instant,
+}
+
+impl FixedTimestep {
+    /// `step` 간격으로 update하는 timestep을 생성  
+    /// `step`이 0이면 update 횟수를 정할 수 없으므로 panic
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "FixedTimestep step must be non-zero.");
+
+        Self {
+            step,
+            accumulator: Duration::ZERO,
+            last_update: Instant::now(),
+        }
+    }
+
+    /// 초당 `hz`번 update하는 timestep을 생성  
+    /// `hz`가 0이면 panic
+    pub fn from_hz(hz: u32) -> Self {
+        assert!(hz != 0, "FixedTimestep rate must be non-zero.");
+
+        Self::new(Duration::from_secs(1) / hz)
+    }
+
+    /// update 한 번의 간격
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// update 한 번의 간격(초), update의 `dt`로 사용함
+    pub fn step_secs(&self) -> f32 {
+        self.step.as_secs_f32()
+    }
+
+    /// 마지막 호출 이후 흐른 시간을 누적하고 이번 frame에서 실행해야 하는 update 횟수를 반환
+    pub fn advance(&mut self) -> u32 {
+        let now = Instant::now();
+        let elapsed = now - self.last_update;
+        self.last_update = now;
+
+        self.advance_by(elapsed)
+    }
+
+    /// `elapsed`만큼 시간을 누적하고 이번 frame에서 실행해야 하는 update 횟수를 반환  
+    /// `MAX_STEPS_PER_FRAME`을 넘는 만큼 밀린 시간은 버리므로 simulation이 실제 시간보다 느려질 수 있음
+    pub fn advance_by(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+
+        let mut steps = 0;
+        while self.accumulator >= self.step {
+            if steps == MAX_STEPS_PER_FRAME {
+                let remainder = self.accumulator.as_nanos() % self.step.as_nanos();
+                self.accumulator = Duration::from_nanos(remainder as u64);
+                break;
+            }
+
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        steps
+    }
+
+    /// 마지막 update 이후 다음 update까지 진행된 비율(0.0 ~ 1.0)  
+    /// 이전 상태와 현재 상태를 이 비율로 보간해서 그리면 update 간격보다 refresh rate가 높아도 움직임이 부드러움
+    pub fn alpha(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.step.as_secs_f32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: Duration = Duration::from_millis(10);
+
+    #[test]
+    fn advance_exact_steps() {
+        let mut timestep = FixedTimestep::new(STEP);
+
+        assert_eq!(timestep.advance_by(STEP * 3), 3);
+        assert_eq!(timestep.alpha(), 0.0);
+        assert_eq!(timestep.advance_by(STEP / 2), 0);
+    }
+
+    #[test]
+    fn remainder_carries_into_alpha() {
+        let mut timestep = FixedTimestep::new(STEP);
+
+        assert_eq!(timestep.advance_by(Duration::from_millis(25)), 2);
+        assert!((timestep.alpha() - 0.5).abs() < 1e-4);
+
+        // 남은 5ms와 합쳐서 한 번 더 update함
+        assert_eq!(timestep.advance_by(Duration::from_millis(7)), 1);
+        assert!((timestep.alpha() - 0.2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn steps_are_clamped() {
+        let mut timestep = FixedTimestep::new(STEP);
+
+        let steps = timestep.advance_by(STEP * 100 + Duration::from_millis(3));
+        assert_eq!(steps, MAX_STEPS_PER_FRAME);
+        // 밀린 시간은 버리고 step보다 작은 나머지만 남김
+        assert!((timestep.alpha() - 0.3).abs() < 1e-4);
+        assert_eq!(timestep.advance_by(Duration::ZERO), 0);
+    }
+
+    #[test]
+    fn from_hz() {
+        assert_eq!(FixedTimestep::from_hz(50).step(), Duration::from_millis(20));
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero")]
+    fn zero_rate_panics() {
+        FixedTimestep::from_hz(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero")]
+    fn zero_step_panics() {
+        FixedTimestep::new(Duration::ZERO);
+    }
+}
@@ -0,0 +1,133 @@
+//! `tracing` span으로 표시한 구간을 Tracy profiler의 timeline으로 보내는 기능  
+//! library와 chapter는 `tracing` span만 사용하고, `tracy` feature를 켜면 CPU zone과 GPU zone이 Tracy에 나타남  
+//! feature를 끄면 subscriber가 등록되지 않으므로 span은 거의 비용 없이 무시됨
+
+use anyhow::Result;
+use log::*;
+use vulkanalia::prelude::v1_0::*;
+
+use std::fmt;
+
+use crate::profiler::GpuProfiler;
+
+/// `tracing` span을 Tracy로 보내는 subscriber를 등록  
+/// 첫 span이 생성되기 전, 가능하면 main의 시작에서 호출해야 함
+pub fn init() {
+    #[cfg(feature = "tracy")]
+    {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let subscriber = tracing_subscriber::registry().with(tracing_tracy::TracyLayer::default());
+        match tracing::subscriber::set_global_default(subscriber) {
+            Ok(()) => info!("Tracy profiler enabled."),
+            Err(error) => warn!("Failed to enable Tracy profiler: {}", error),
+        }
+    }
+
+    #[cfg(not(feature = "tracy"))]
+    debug!("Tracy profiler requires the `tracy` feature.");
+}
+
+/// frame의 경계를 표시  
+/// present한 직후에 호출하면 Tracy의 timeline이 frame 단위로 나뉨
+pub fn frame_mark() {
+    #[cfg(feature = "tracy")]
+    if let Some(client) = tracy_client::Client::running() {
+        client.frame_mark();
+    }
+}
+
+/// `GpuProfiler`가 측정한 timestamp를 Tracy의 GPU zone으로 보내는 구조체  
+/// Tracy가 실행중이 아니거나 timestamp를 지원하지 않으면 아무것도 하지 않음
+#[derive(Default)]
+pub struct GpuTracer {
+    #[cfg(feature = "tracy")]
+    context: Option<tracy_client::GpuContext>,
+}
+
+impl GpuTracer {
+    /// Tracy에 Vulkan GPU context를 생성  
+    /// CPU와 GPU의 timeline을 맞추기 위해 `queue`에서 timestamp를 하나 기록해서 기준으로 사용하므로 frame을 기록하기 전에 호출해야 함
+    pub unsafe fn new(
+        device: &Device,
+        queue: vk::Queue,
+        command_pool: vk::CommandPool,
+        profiler: &GpuProfiler,
+    ) -> Result<Self> {
+        #[cfg(feature = "tracy")]
+        {
+            let Some(client) = tracy_client::Client::running() else {
+                return Ok(Self::default());
+            };
+
+            let Some(timestamp) = profiler.calibrate(device, queue, command_pool)? else {
+                return Ok(Self::default());
+            };
+
+            let context = match client.new_gpu_context(
+                Some("Graphics queue"),
+                tracy_client::GpuContextType::Vulkan,
+                timestamp as i64,
+                profiler.timestamp_period(),
+            ) {
+                Ok(context) => Some(context),
+                Err(error) => {
+                    warn!("Failed to create Tracy GPU context: {}", error);
+                    None
+                }
+            };
+
+            Ok(Self { context })
+        }
+
+        #[cfg(not(feature = "tracy"))]
+        {
+            let _ = (device, queue, command_pool, profiler);
+            Ok(Self::default())
+        }
+    }
+
+    /// GPU context가 생성되어 GPU zone을 보낼 수 있는지 여부
+    pub fn is_enabled(&self) -> bool {
+        #[cfg(feature = "tracy")]
+        {
+            self.context.is_some()
+        }
+
+        #[cfg(not(feature = "tracy"))]
+        {
+            false
+        }
+    }
+
+    /// `GpuProfiler::read_timestamps`로 읽은 구간들을 GPU zone으로 보냄  
+    /// Tracy가 zone을 시간 순서대로 받아야 하므로 기록한 순서 그대로 전달해야 함
+    pub fn record(&self, timestamps: &[(String, u64, u64)]) {
+        #[cfg(feature = "tracy")]
+        if let Some(context) = &self.context {
+            for (name, begin, end) in timestamps {
+                match context.span_alloc(name, "", file!(), line!()) {
+                    Ok(mut span) => {
+                        span.end_zone();
+                        span.upload_timestamp(*begin as i64, *end as i64);
+                    }
+                    Err(error) => {
+                        warn!("Failed to create Tracy GPU zone (`{}`): {}", name, error);
+                        return;
+                    }
+                }
+            }
+        }
+
+        #[cfg(not(feature = "tracy"))]
+        let _ = timestamps;
+    }
+}
+
+impl fmt::Debug for GpuTracer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GpuTracer")
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
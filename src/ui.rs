@@ -0,0 +1,790 @@
+//! egui로 만든 UI를 기존 render pass의 마지막 draw로 그리는 overlay  
+//! winit event를 egui에 전달하고, egui가 tessellate한 mesh를 swapchain image마다 가진 buffer에 올려서 그림
+
+use anyhow::{anyhow, Result};
+use egui::epaint::{ImageData, ImageDelta, Primitive, TextureId, Vertex};
+use egui::{ClippedPrimitive, Context, ViewportId};
+use vulkanalia::prelude::v1_0::*;
+use winit::event::WindowEvent;
+use winit::window::Window;
+
+use crate::allocator::{Allocation, Allocator};
+use crate::pipeline::create_shader_module;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::mem::size_of;
+use std::ptr::copy_nonoverlapping as memcpy;
+
+/// 동시에 존재할 수 있는 egui texture의 최대 수  
+/// texture마다 descriptor set을 하나씩 할당함
+const MAX_TEXTURES: u32 = 64;
+
+/// egui가 요청한 texture
+#[derive(Copy, Clone, Debug, Default)]
+struct Texture {
+    image: vk::Image,
+    memory: Allocation,
+    view: vk::ImageView,
+    descriptor_set: vk::DescriptorSet,
+}
+
+/// swapchain image마다 존재하는 vertex와 index를 담는 buffer  
+/// vertex 뒤에 index를 이어서 저장함
+#[derive(Copy, Clone, Debug, Default)]
+struct MeshBuffer {
+    buffer: vk::Buffer,
+    memory: Allocation,
+    size: vk::DeviceSize,
+}
+
+/// egui context와 egui를 그리기 위한 Vulkan 오브젝트
+pub struct Ui {
+    context: Context,
+    // winit event를 egui의 입력으로 변환하고 cursor, clipboard 등의 출력을 window에 반영
+    state: egui_winit::State,
+    queue: vk::Queue,
+    // texture upload에 사용하는 command pool
+    command_pool: vk::CommandPool,
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+    // egui가 지정하는 texture option과 관계없이 모든 texture를 linear filtering으로 읽음
+    sampler: vk::Sampler,
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+    textures: HashMap<TextureId, Texture>,
+    // 이번 frame을 그린 뒤에 파괴해야 하는 texture
+    textures_to_free: Vec<TextureId>,
+    mesh_buffers: Vec<MeshBuffer>,
+    // 마지막 `end_frame`에서 tessellate한 mesh
+    primitives: Vec<ClippedPrimitive>,
+    pixels_per_point: f32,
+}
+
+impl fmt::Debug for Ui {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ui")
+            .field("pipeline", &self.pipeline)
+            .field("textures", &self.textures.len())
+            .field("primitives", &self.primitives.len())
+            .finish()
+    }
+}
+
+impl Ui {
+    /// egui context와 pipeline을 제외한 Vulkan 오브젝트를 생성  
+    /// texture는 `queue`에서 upload하므로 `queue_family`는 `queue`가 속한 queue family여야 함
+    pub unsafe fn new(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        window: &Window,
+        queue: vk::Queue,
+        queue_family: u32,
+    ) -> Result<Self> {
+        let properties = instance.get_physical_device_properties(physical_device);
+
+        let context = Context::default();
+        let state = egui_winit::State::new(
+            context.clone(),
+            ViewportId::ROOT,
+            window,
+            Some(window.scale_factor() as f32),
+            Some(properties.limits.max_image_dimension_2d as usize),
+        );
+
+        // upload에만 사용하고 바로 해제하므로 TRANSIENT를 지정
+        let info = vk::CommandPoolCreateInfo::builder()
+            .flags(vk::CommandPoolCreateFlags::TRANSIENT)
+            .queue_family_index(queue_family);
+        let command_pool = device.create_command_pool(&info, None)?;
+
+        let binding = vk::DescriptorSetLayoutBinding::builder()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+        let bindings = &[binding];
+        let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(bindings);
+        let descriptor_set_layout = device.create_descriptor_set_layout(&info, None)?;
+
+        // texture가 해제되면 descriptor set도 하나씩 해제해야 하므로 FREE_DESCRIPTOR_SET을 지정
+        let pool_size = vk::DescriptorPoolSize::builder()
+            .type_(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(MAX_TEXTURES);
+        let pool_sizes = &[pool_size];
+        let info = vk::DescriptorPoolCreateInfo::builder()
+            .flags(vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET)
+            .pool_sizes(pool_sizes)
+            .max_sets(MAX_TEXTURES);
+        let descriptor_pool = device.create_descriptor_pool(&info, None)?;
+
+        let info = vk::SamplerCreateInfo::builder()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .mipmap_mode(vk::SamplerMipmapMode::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE);
+        let sampler = device.create_sampler(&info, None)?;
+
+        Ok(Self {
+            context,
+            state,
+            queue,
+            command_pool,
+            descriptor_set_layout,
+            descriptor_pool,
+            sampler,
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+            textures: HashMap::new(),
+            textures_to_free: vec![],
+            mesh_buffers: vec![],
+            primitives: vec![],
+            pixels_per_point: 1.0,
+        })
+    }
+
+    /// UI를 구성할 때 사용하는 egui context
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// 마지막 `end_frame`에서 사용한 point 하나의 physical pixel 수  
+    /// `on_window_event`에 `ScaleFactorChanged`를 전달하면 다음 frame부터 새 scale factor가 반영됨
+    pub fn pixels_per_point(&self) -> f32 {
+        self.pixels_per_point
+    }
+
+    /// winit event를 egui에 전달  
+    /// egui가 event를 사용했으면 `true`를 반환하므로 camera 조작 등에는 전달하지 않아야 함
+    pub fn on_window_event(&mut self, window: &Window, event: &WindowEvent) -> bool {
+        self.state.on_window_event(window, event).consumed
+    }
+
+    /// 지금까지 전달한 event로 새 frame을 시작  
+    /// 이후 `context`로 UI를 구성하고 `end_frame`을 호출해야 함
+    pub fn begin_frame(&mut self, window: &Window) {
+        let input = self.state.take_egui_input(window);
+        self.context.begin_frame(input);
+    }
+
+    /// frame을 끝내고 변경된 texture를 upload한 뒤 그릴 mesh를 tessellate  
+    /// texture를 다시 만들거나 파괴하는 경우 사용 중일 수 있으므로 queue가 idle 상태가 될 때까지 기다림
+    pub unsafe fn end_frame(
+        &mut self,
+        device: &Device,
+        allocator: &mut Allocator,
+        window: &Window,
+    ) -> Result<()> {
+        let output = self.context.end_frame();
+        self.state
+            .handle_platform_output(window, output.platform_output);
+
+        // 다시 만들 texture나 이전 frame을 그린 뒤에 파괴하라고 요청된 texture는 아직 사용 중일 수 있음
+        let replaced = output
+            .textures_delta
+            .set
+            .iter()
+            .any(|(id, d)| d.pos.is_none() && self.textures.contains_key(id));
+        if replaced || !self.textures_to_free.is_empty() {
+            device.queue_wait_idle(self.queue)?;
+        }
+        for id in self.textures_to_free.drain(..) {
+            if let Some(texture) = self.textures.remove(&id) {
+                destroy_texture(device, allocator, self.descriptor_pool, texture)?;
+            }
+        }
+
+        for (id, delta) in &output.textures_delta.set {
+            self.update_texture(device, allocator, *id, delta)?;
+        }
+        self.textures_to_free = output.textures_delta.free;
+
+        self.pixels_per_point = output.pixels_per_point;
+        self.primitives = self
+            .context
+            .tessellate(output.shapes, output.pixels_per_point);
+
+        Ok(())
+    }
+
+    /// `render_pass`의 `subpass`에서 UI를 그리는 pipeline을 생성  
+    /// render pass가 다시 생성되면 `destroy_pipeline`을 호출한 뒤 다시 생성해야 함  
+    /// UNORM format에 그리는 경우 `encode_srgb`를 켜면 shader에서 sRGB로 encode함
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn create_pipeline(
+        &mut self,
+        device: &Device,
+        render_pass: vk::RenderPass,
+        subpass: u32,
+        samples: vk::SampleCountFlags,
+        pipeline_cache: vk::PipelineCache,
+        encode_srgb: bool,
+    ) -> Result<()> {
+        let vert = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/ui/vert.spv"));
+        let frag = include_bytes!(concat!(env!("OUT_DIR"), "/shaders/ui/frag.spv"));
+
+        let vert_shader_module = create_shader_module(device, &vert[..])?;
+        let frag_shader_module = create_shader_module(device, &frag[..])?;
+
+        let vert_stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(b"main\0");
+        // ENCODE_SRGB specialization constant(constant_id = 0)는 bool이므로 VkBool32(u32)로 전달함
+        let encode_srgb = (encode_srgb as u32).to_ne_bytes();
+        let map_entries = &[vk::SpecializationMapEntry::builder()
+            .constant_id(0)
+            .offset(0)
+            .size(size_of::<u32>())];
+        let specialization_info = vk::SpecializationInfo::builder()
+            .map_entries(map_entries)
+            .data(&encode_srgb);
+
+        let frag_stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .specialization_info(&specialization_info)
+            .name(b"main\0");
+
+        // egui::epaint::Vertex는 repr(C)로 pos, uv, color 순서로 저장됨
+        let binding_description = vk::VertexInputBindingDescription::builder()
+            .binding(0)
+            .stride(size_of::<Vertex>() as u32)
+            .input_rate(vk::VertexInputRate::VERTEX);
+        let pos = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(0)
+            .format(vk::Format::R32G32_SFLOAT)
+            .offset(0);
+        let uv = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(1)
+            .format(vk::Format::R32G32_SFLOAT)
+            .offset(8);
+        let color = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(2)
+            .format(vk::Format::R8G8B8A8_UNORM)
+            .offset(16);
+
+        let binding_descriptions = &[binding_description];
+        let attribute_descriptions = &[pos, uv, color];
+        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
+            .vertex_binding_descriptions(binding_descriptions)
+            .vertex_attribute_descriptions(attribute_descriptions);
+
+        let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::builder()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        // viewport와 scissor는 dynamic state로 지정하므로 개수만 알려줌
+        let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+            .viewport_count(1)
+            .scissor_count(1);
+
+        // egui는 triangle의 방향을 보장하지 않으므로 culling하지 않음
+        let rasterization_state = vk::PipelineRasterizationStateCreateInfo::builder()
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE);
+
+        let multisample_state =
+            vk::PipelineMultisampleStateCreateInfo::builder().rasterization_samples(samples);
+
+        // UI는 scene 위에 그려지므로 depth를 비교하거나 기록하지 않음
+        let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::builder()
+            .depth_test_enable(false)
+            .depth_write_enable(false);
+
+        // egui의 color는 premultiplied alpha임
+        let attachment = vk::PipelineColorBlendAttachmentState::builder()
+            .color_write_mask(vk::ColorComponentFlags::all())
+            .blend_enable(true)
+            .src_color_blend_factor(vk::BlendFactor::ONE)
+            .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+            .color_blend_op(vk::BlendOp::ADD)
+            .src_alpha_blend_factor(vk::BlendFactor::ONE_MINUS_DST_ALPHA)
+            .dst_alpha_blend_factor(vk::BlendFactor::ONE)
+            .alpha_blend_op(vk::BlendOp::ADD);
+        let attachments = &[attachment];
+        let color_blend_state =
+            vk::PipelineColorBlendStateCreateInfo::builder().attachments(attachments);
+
+        let dynamic_states = &[vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state =
+            vk::PipelineDynamicStateCreateInfo::builder().dynamic_states(dynamic_states);
+
+        // 화면의 크기(point 단위)를 push constant로 전달
+        let push_constant_range = vk::PushConstantRange::builder()
+            .stage_flags(vk::ShaderStageFlags::VERTEX)
+            .offset(0)
+            .size(8);
+
+        let set_layouts = &[self.descriptor_set_layout];
+        let push_constant_ranges = &[push_constant_range];
+        let layout_info = vk::PipelineLayoutCreateInfo::builder()
+            .set_layouts(set_layouts)
+            .push_constant_ranges(push_constant_ranges);
+        self.pipeline_layout = device.create_pipeline_layout(&layout_info, None)?;
+
+        let stages = &[vert_stage, frag_stage];
+        let info = vk::GraphicsPipelineCreateInfo::builder()
+            .stages(stages)
+            .vertex_input_state(&vertex_input_state)
+            .input_assembly_state(&input_assembly_state)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterization_state)
+            .multisample_state(&multisample_state)
+            .depth_stencil_state(&depth_stencil_state)
+            .color_blend_state(&color_blend_state)
+            .dynamic_state(&dynamic_state)
+            .layout(self.pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(subpass);
+
+        self.pipeline = device
+            .create_graphics_pipelines(pipeline_cache, &[info], None)?
+            .0[0];
+
+        device.destroy_shader_module(vert_shader_module, None);
+        device.destroy_shader_module(frag_shader_module, None);
+
+        Ok(())
+    }
+
+    /// `create_pipeline`으로 생성한 pipeline을 파괴
+    pub unsafe fn destroy_pipeline(&mut self, device: &Device) {
+        device.destroy_pipeline(self.pipeline, None);
+        device.destroy_pipeline_layout(self.pipeline_layout, None);
+        self.pipeline = vk::Pipeline::null();
+        self.pipeline_layout = vk::PipelineLayout::null();
+    }
+
+    /// 마지막 `end_frame`에서 tessellate한 mesh를 그리는 command를 기록  
+    /// render pass 안에서 호출해야 하며 viewport와 scissor를 덮어씀  
+    /// `image_index`의 buffer를 다시 쓰므로 해당 swapchain image를 사용하는 이전 frame이 끝난 뒤에 호출해야 함
+    pub unsafe fn cmd_draw(
+        &mut self,
+        device: &Device,
+        allocator: &mut Allocator,
+        command_buffer: vk::CommandBuffer,
+        image_index: usize,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        // 아직 upload되지 않은 texture를 사용하는 mesh는 그릴 수 없음
+        let meshes = self
+            .primitives
+            .iter()
+            .filter_map(|p| match &p.primitive {
+                Primitive::Mesh(m) if self.textures.contains_key(&m.texture_id) => {
+                    Some((p.clip_rect, m))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let vertex_count = meshes.iter().map(|(_, m)| m.vertices.len()).sum::<usize>();
+        let index_count = meshes.iter().map(|(_, m)| m.indices.len()).sum::<usize>();
+        if index_count == 0 {
+            return Ok(());
+        }
+
+        // Vertex의 크기는 4의 배수이므로 index를 vertex 바로 뒤에 저장해도 정렬이 맞음
+        let index_offset = (vertex_count * size_of::<Vertex>()) as vk::DeviceSize;
+        let size = index_offset + (index_count * size_of::<u32>()) as vk::DeviceSize;
+
+        if self.mesh_buffers.len() <= image_index {
+            self.mesh_buffers
+                .resize(image_index + 1, MeshBuffer::default());
+        }
+
+        // buffer가 부족하면 다시 생성함
+        // 이 swapchain image를 사용하는 이전 frame은 끝났으므로 기존 buffer를 바로 파괴할 수 있음
+        let mut mesh_buffer = self.mesh_buffers[image_index];
+        if mesh_buffer.size < size {
+            device.destroy_buffer(mesh_buffer.buffer, None);
+            allocator.free(mesh_buffer.memory);
+
+            // 자주 다시 만들지 않도록 여유를 두고 할당
+            let size = size.next_power_of_two();
+            let info = vk::BufferCreateInfo::builder()
+                .size(size)
+                .usage(vk::BufferUsageFlags::VERTEX_BUFFER | vk::BufferUsageFlags::INDEX_BUFFER)
+                .sharing_mode(vk::SharingMode::EXCLUSIVE);
+            let (buffer, memory) = allocator.create_buffer(
+                device,
+                &info,
+                vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+            )?;
+
+            mesh_buffer = MeshBuffer {
+                buffer,
+                memory,
+                size,
+            };
+            self.mesh_buffers[image_index] = mesh_buffer;
+        }
+
+        let memory = mesh_buffer.memory.mapped_ptr()?.cast::<u8>();
+        let mut vertices = memory.cast::<Vertex>();
+        let mut indices = memory.add(index_offset as usize).cast::<u32>();
+        for (_, mesh) in &meshes {
+            memcpy(mesh.vertices.as_ptr(), vertices, mesh.vertices.len());
+            memcpy(mesh.indices.as_ptr(), indices, mesh.indices.len());
+            vertices = vertices.add(mesh.vertices.len());
+            indices = indices.add(mesh.indices.len());
+        }
+
+        device.cmd_bind_pipeline(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            self.pipeline,
+        );
+        device.cmd_bind_vertex_buffers(command_buffer, 0, &[mesh_buffer.buffer], &[0]);
+        device.cmd_bind_index_buffer(
+            command_buffer,
+            mesh_buffer.buffer,
+            index_offset,
+            vk::IndexType::UINT32,
+        );
+
+        let viewport = vk::Viewport::builder()
+            .x(0.0)
+            .y(0.0)
+            .width(extent.width as f32)
+            .height(extent.height as f32)
+            .min_depth(0.0)
+            .max_depth(1.0);
+        device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+
+        // egui의 좌표는 point 단위이므로 화면의 크기도 point 단위로 전달
+        let screen_size = [
+            extent.width as f32 / self.pixels_per_point,
+            extent.height as f32 / self.pixels_per_point,
+        ];
+        let screen_size_bytes = std::slice::from_raw_parts(screen_size.as_ptr().cast::<u8>(), 8);
+        device.cmd_push_constants(
+            command_buffer,
+            self.pipeline_layout,
+            vk::ShaderStageFlags::VERTEX,
+            0,
+            screen_size_bytes,
+        );
+
+        let mut first_index = 0;
+        let mut vertex_offset = 0;
+        for (clip_rect, mesh) in &meshes {
+            // clip rect를 pixel 단위로 바꾸고 화면 밖으로 나가지 않도록 자름
+            let min_x = (clip_rect.min.x * self.pixels_per_point).round() as u32;
+            let min_y = (clip_rect.min.y * self.pixels_per_point).round() as u32;
+            let max_x =
+                ((clip_rect.max.x * self.pixels_per_point).round() as u32).min(extent.width);
+            let max_y =
+                ((clip_rect.max.y * self.pixels_per_point).round() as u32).min(extent.height);
+
+            if min_x < max_x && min_y < max_y {
+                let scissor = vk::Rect2D::builder()
+                    .offset(vk::Offset2D {
+                        x: min_x as i32,
+                        y: min_y as i32,
+                    })
+                    .extent(vk::Extent2D {
+                        width: max_x - min_x,
+                        height: max_y - min_y,
+                    });
+                device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+
+                device.cmd_bind_descriptor_sets(
+                    command_buffer,
+                    vk::PipelineBindPoint::GRAPHICS,
+                    self.pipeline_layout,
+                    0,
+                    &[self.textures[&mesh.texture_id].descriptor_set],
+                    &[],
+                );
+                device.cmd_draw_indexed(
+                    command_buffer,
+                    mesh.indices.len() as u32,
+                    1,
+                    first_index,
+                    vertex_offset,
+                    0,
+                );
+            }
+
+            first_index += mesh.indices.len() as u32;
+            vertex_offset += mesh.vertices.len() as i32;
+        }
+
+        Ok(())
+    }
+
+    /// 모든 Vulkan 오브젝트를 파괴  
+    /// device가 idle 상태일 때 `allocator`를 파괴하기 전에 호출해야 함
+    pub unsafe fn destroy(&mut self, device: &Device, allocator: &mut Allocator) {
+        self.destroy_pipeline(device);
+
+        for mesh_buffer in self.mesh_buffers.drain(..) {
+            device.destroy_buffer(mesh_buffer.buffer, None);
+            allocator.free(mesh_buffer.memory);
+        }
+
+        // descriptor set은 pool과 함께 해제됨
+        for (_, texture) in self.textures.drain() {
+            device.destroy_image_view(texture.view, None);
+            device.destroy_image(texture.image, None);
+            allocator.free(texture.memory);
+        }
+
+        device.destroy_descriptor_pool(self.descriptor_pool, None);
+        device.destroy_sampler(self.sampler, None);
+        device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+        device.destroy_command_pool(self.command_pool, None);
+    }
+
+    /// `delta`를 texture에 반영  
+    /// `delta.pos`가 없으면 texture 전체를 새로 만들고, 있으면 기존 texture의 일부만 갱신함
+    unsafe fn update_texture(
+        &mut self,
+        device: &Device,
+        allocator: &mut Allocator,
+        id: TextureId,
+        delta: &ImageDelta,
+    ) -> Result<()> {
+        // font texture는 coverage를 premultiplied sRGBA로 변환해서 사용
+        let pixels = match &delta.image {
+            ImageData::Color(image) => image
+                .pixels
+                .iter()
+                .flat_map(|c| c.to_array())
+                .collect::<Vec<_>>(),
+            ImageData::Font(image) => image
+                .srgba_pixels(None)
+                .flat_map(|c| c.to_array())
+                .collect::<Vec<_>>(),
+        };
+        let width = delta.image.width() as u32;
+        let height = delta.image.height() as u32;
+
+        let (texture, offset, old_layout) = match delta.pos {
+            Some([x, y]) => {
+                let texture = *self
+                    .textures
+                    .get(&id)
+                    .ok_or_else(|| anyhow!("Partial update of unknown texture {:?}.", id))?;
+                let offset = vk::Offset3D {
+                    x: x as i32,
+                    y: y as i32,
+                    z: 0,
+                };
+                (texture, offset, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            }
+            None => {
+                // end_frame에서 queue를 기다렸으므로 기존 texture를 바로 파괴할 수 있음
+                if let Some(texture) = self.textures.remove(&id) {
+                    destroy_texture(device, allocator, self.descriptor_pool, texture)?;
+                }
+                let texture = self.create_texture(device, allocator, width, height)?;
+                self.textures.insert(id, texture);
+                (texture, vk::Offset3D::default(), vk::ImageLayout::UNDEFINED)
+            }
+        };
+
+        // staging buffer 생성
+        let info = vk::BufferCreateInfo::builder()
+            .size(pixels.len() as vk::DeviceSize)
+            .usage(vk::BufferUsageFlags::TRANSFER_SRC)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+        let (staging_buffer, staging_buffer_memory) = allocator.create_buffer(
+            device,
+            &info,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        )?;
+
+        memcpy(
+            pixels.as_ptr(),
+            staging_buffer_memory.mapped_ptr()?.cast(),
+            pixels.len(),
+        );
+
+        let info = vk::CommandBufferAllocateInfo::builder()
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_pool(self.command_pool)
+            .command_buffer_count(1);
+        let command_buffer = device.allocate_command_buffers(&info)?[0];
+
+        let info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        device.begin_command_buffer(command_buffer, &info)?;
+
+        let subresource_range = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(0)
+            .level_count(1)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        // 이전 frame의 fragment shader가 texture를 다 읽은 뒤에 복사하도록 함
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(old_layout)
+            .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(texture.image)
+            .subresource_range(subresource_range)
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+
+        let subresource = vk::ImageSubresourceLayers::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .mip_level(0)
+            .base_array_layer(0)
+            .layer_count(1);
+        let region = vk::BufferImageCopy::builder()
+            .buffer_offset(0)
+            .buffer_row_length(0)
+            .buffer_image_height(0)
+            .image_subresource(subresource)
+            .image_offset(offset)
+            .image_extent(vk::Extent3D {
+                width,
+                height,
+                depth: 1,
+            });
+        device.cmd_copy_buffer_to_image(
+            command_buffer,
+            staging_buffer,
+            texture.image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[region],
+        );
+
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(texture.image)
+            .subresource_range(subresource_range)
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::SHADER_READ);
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+
+        device.end_command_buffer(command_buffer)?;
+
+        // texture는 가끔씩만 바뀌므로 upload가 끝날 때까지 기다려서 staging buffer를 바로 정리함
+        let command_buffers = &[command_buffer];
+        let info = vk::SubmitInfo::builder().command_buffers(command_buffers);
+        device.queue_submit(self.queue, &[info], vk::Fence::null())?;
+        device.queue_wait_idle(self.queue)?;
+
+        device.free_command_buffers(self.command_pool, command_buffers);
+        device.destroy_buffer(staging_buffer, None);
+        allocator.free(staging_buffer_memory);
+
+        Ok(())
+    }
+
+    /// `width` x `height` 크기의 sRGB texture와 texture를 읽는 descriptor set을 생성
+    unsafe fn create_texture(
+        &self,
+        device: &Device,
+        allocator: &mut Allocator,
+        width: u32,
+        height: u32,
+    ) -> Result<Texture> {
+        let info = vk::ImageCreateInfo::builder()
+            .image_type(vk::ImageType::_2D)
+            .extent(vk::Extent3D {
+                width,
+                height,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(vk::Format::R8G8B8A8_SRGB)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(vk::SampleCountFlags::_1);
+        let (image, memory) =
+            allocator.create_image(device, &info, vk::MemoryPropertyFlags::DEVICE_LOCAL)?;
+
+        let subresource_range = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(0)
+            .level_count(1)
+            .base_array_layer(0)
+            .layer_count(1);
+        let info = vk::ImageViewCreateInfo::builder()
+            .image(image)
+            .view_type(vk::ImageViewType::_2D)
+            .format(vk::Format::R8G8B8A8_SRGB)
+            .subresource_range(subresource_range);
+        let view = device.create_image_view(&info, None)?;
+
+        let layouts = &[self.descriptor_set_layout];
+        let info = vk::DescriptorSetAllocateInfo::builder()
+            .descriptor_pool(self.descriptor_pool)
+            .set_layouts(layouts);
+        let descriptor_set = device.allocate_descriptor_sets(&info)?[0];
+
+        let image_info = vk::DescriptorImageInfo::builder()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(view)
+            .sampler(self.sampler);
+        let image_infos = &[image_info];
+        let write = vk::WriteDescriptorSet::builder()
+            .dst_set(descriptor_set)
+            .dst_binding(0)
+            .dst_array_element(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .image_info(image_infos);
+        device.update_descriptor_sets(&[write], &[] as &[vk::CopyDescriptorSet]);
+
+        Ok(Texture {
+            image,
+            memory,
+            view,
+            descriptor_set,
+        })
+    }
+}
+
+/// texture와 texture의 descriptor set을 파괴  
+/// texture를 사용하는 command가 모두 끝난 뒤에 호출해야 함
+unsafe fn destroy_texture(
+    device: &Device,
+    allocator: &mut Allocator,
+    descriptor_pool: vk::DescriptorPool,
+    texture: Texture,
+) -> Result<()> {
+    device.free_descriptor_sets(descriptor_pool, &[texture.descriptor_set])?;
+    device.destroy_image_view(texture.view, None);
+    device.destroy_image(texture.image, None);
+    allocator.free(texture.memory);
+
+    Ok(())
+}
@@ -0,0 +1,225 @@
+//! 여러 object의 uniform 데이터를 하나의 buffer에 나누어 담는 dynamic uniform buffer  
+//! object마다 uniform buffer와 descriptor set을 만드는 대신 `UNIFORM_BUFFER_DYNAMIC` descriptor 하나를 bind하고  
+//! draw마다 `cmd_bind_descriptor_sets`의 dynamic offset만 바꿔서 object의 데이터를 선택함  
+//! 매 frame 갱신하는 uniform 데이터를 DEVICE_LOCAL memory에 바로 쓸 수 있는지 확인하는 기능도 제공함
+
+use anyhow::{anyhow, Result};
+use vulkanalia::prelude::v1_0::*;
+
+use std::fmt;
+use std::mem::size_of;
+use std::ptr::copy_nonoverlapping as memcpy;
+use std::ptr::NonNull;
+
+use crate::allocator::get_memory_type;
+
+/// frame마다 영역을 나누어 사용하는 ring 형태의 dynamic uniform buffer  
+/// buffer는 `frames`개의 영역으로 나뉘고, 각 영역에는 `capacity`개의 object 데이터를 `stride` 간격으로 저장함  
+/// GPU가 아직 읽고 있는 영역을 덮어쓰지 않도록 frame의 fence를 기다린 뒤에 `begin_frame`을 호출해야 함
+#[derive(Clone, Debug, Default)]
+pub struct DynamicUniformBuffer {
+    /// 모든 frame의 영역을 담는 buffer
+    pub buffer: vk::Buffer,
+    /// buffer에 bind된 HOST_VISIBLE memory
+    pub memory: vk::DeviceMemory,
+    /// object 하나의 uniform 데이터 크기로, descriptor의 range로 사용함
+    pub element_size: vk::DeviceSize,
+    /// `min_uniform_buffer_offset_alignment`의 배수로 맞춘 object 사이의 간격
+    pub stride: vk::DeviceSize,
+    /// frame 하나에 저장할 수 있는 object의 수
+    pub capacity: u32,
+    frames: u32,
+    // 생성할 때 한 번만 map해두고 파괴할 때까지 유지하는 주소
+    mapped: Option<NonNull<u8>>,
+    // 현재 frame의 영역이 시작하는 위치
+    frame_offset: vk::DeviceSize,
+    // 현재 frame에서 지금까지 저장한 object의 수
+    count: u32,
+}
+
+impl DynamicUniformBuffer {
+    /// `T`를 frame마다 `capacity`개까지 저장할 수 있는 buffer를 `frames`개의 frame만큼 생성  
+    /// dynamic offset은 `min_uniform_buffer_offset_alignment`의 배수여야 하므로 `T`의 크기를 그 배수로 올려서 간격을 정함
+    pub unsafe fn new<T: Copy>(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        capacity: u32,
+        frames: u32,
+    ) -> Result<Self> {
+        let limits = instance
+            .get_physical_device_properties(physical_device)
+            .limits;
+
+        let element_size = size_of::<T>() as vk::DeviceSize;
+        if element_size > limits.max_uniform_buffer_range as vk::DeviceSize {
+            return Err(anyhow!(
+                "Uniform data ({} bytes) exceeds maxUniformBufferRange ({} bytes).",
+                element_size,
+                limits.max_uniform_buffer_range
+            ));
+        }
+
+        let alignment = limits.min_uniform_buffer_offset_alignment.max(1);
+        let stride = element_size.div_ceil(alignment) * alignment;
+        let size = stride * capacity as vk::DeviceSize * frames as vk::DeviceSize;
+
+        // dynamic offset은 u32이므로 buffer 전체가 u32 범위 안에 있어야 함
+        if size > u32::MAX as vk::DeviceSize {
+            return Err(anyhow!(
+                "Dynamic uniform buffer ({} bytes) is too large.",
+                size
+            ));
+        }
+
+        let buffer_info = vk::BufferCreateInfo::builder()
+            .size(size)
+            .usage(vk::BufferUsageFlags::UNIFORM_BUFFER)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+        let buffer = device.create_buffer(&buffer_info, None)?;
+
+        // 매 frame host에서 쓰므로 flush가 필요 없는 HOST_COHERENT memory를 사용
+        let requirements = device.get_buffer_memory_requirements(buffer);
+        let memory_info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(requirements.size)
+            .memory_type_index(get_memory_type(
+                &instance.get_physical_device_memory_properties(physical_device),
+                vk::MemoryPropertyFlags::HOST_COHERENT | vk::MemoryPropertyFlags::HOST_VISIBLE,
+                requirements,
+            )?);
+
+        let memory = device.allocate_memory(&memory_info, None)?;
+        device.bind_buffer_memory(buffer, memory, 0)?;
+
+        let mapped = device.map_memory(memory, 0, size, vk::MemoryMapFlags::empty())?;
+
+        Ok(Self {
+            buffer,
+            memory,
+            element_size,
+            stride,
+            capacity,
+            frames,
+            mapped: NonNull::new(mapped.cast()),
+            frame_offset: 0,
+            count: 0,
+        })
+    }
+
+    /// `frame`의 영역을 처음부터 다시 사용  
+    /// 이전에 이 영역을 사용한 frame이 끝난 뒤에 호출해야 함
+    pub fn begin_frame(&mut self, frame: usize) {
+        let frame = frame as u32 % self.frames;
+        self.frame_offset = self.stride * self.capacity as vk::DeviceSize * frame as vk::DeviceSize;
+        self.count = 0;
+    }
+
+    /// 현재 frame의 영역에 `value`를 저장하고 `cmd_bind_descriptor_sets`에 전달할 dynamic offset을 반환
+    pub unsafe fn push<T: Copy>(&mut self, value: &T) -> Result<u32> {
+        if size_of::<T>() as vk::DeviceSize > self.element_size {
+            return Err(anyhow!("Uniform data is larger than the element size."));
+        }
+
+        if self.count >= self.capacity {
+            return Err(anyhow!(
+                "Dynamic uniform buffer is full ({} objects).",
+                self.capacity
+            ));
+        }
+
+        let mapped = self
+            .mapped
+            .ok_or_else(|| anyhow!("Dynamic uniform buffer is not mapped."))?;
+
+        let offset = self.frame_offset + self.stride * self.count as vk::DeviceSize;
+        memcpy(value, mapped.as_ptr().add(offset as usize).cast(), 1);
+        self.count += 1;
+
+        Ok(offset as u32)
+    }
+
+    /// descriptor set에 연결할 buffer 정보  
+    /// 실제로 읽는 위치는 이 offset에 dynamic offset을 더한 위치가 됨
+    pub fn descriptor_info(&self) -> vk::DescriptorBufferInfo {
+        vk::DescriptorBufferInfo::builder()
+            .buffer(self.buffer)
+            .offset(0)
+            .range(self.element_size)
+            .build()
+    }
+
+    /// buffer를 파괴하고 memory를 해제  
+    /// `free_memory`가 암묵적으로 unmap함
+    pub unsafe fn destroy(&mut self, device: &Device) {
+        device.destroy_buffer(self.buffer, None);
+        device.free_memory(self.memory, None);
+        self.mapped = None;
+    }
+}
+
+/// ReBAR가 없어도 대부분의 discrete GPU가 제공하는 DEVICE_LOCAL | HOST_VISIBLE heap의 크기  
+/// 이 크기 이하의 heap은 driver도 함께 사용하므로 uniform 데이터를 위해 사용하지 않음
+pub const SMALL_BAR_HEAP_SIZE: vk::DeviceSize = 256 * 1024 * 1024;
+
+/// 매 frame 갱신하는 uniform 데이터를 DEVICE_LOCAL memory로 옮기는 방법
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum UniformMemoryPath {
+    /// resizable BAR나 통합 GPU처럼 큰 DEVICE_LOCAL | HOST_VISIBLE heap이 있으면 host에서 바로 씀
+    DirectWrite,
+    /// HOST_VISIBLE staging buffer에 쓰고 transfer command로 DEVICE_LOCAL buffer에 복사함
+    #[default]
+    Staging,
+}
+
+impl UniformMemoryPath {
+    /// `physical_device`의 memory type을 확인해서 사용할 방법을 선택  
+    /// DEVICE_LOCAL | HOST_VISIBLE | HOST_COHERENT memory type이 `SMALL_BAR_HEAP_SIZE`보다 큰 heap에 있을 때만 바로 씀
+    pub unsafe fn select(instance: &Instance, physical_device: vk::PhysicalDevice) -> Self {
+        let memory = instance.get_physical_device_memory_properties(physical_device);
+        let properties = Self::DirectWrite.memory_properties();
+
+        let large_heap = memory.memory_types[..memory.memory_type_count as usize]
+            .iter()
+            .filter(|t| t.property_flags.contains(properties))
+            .any(|t| memory.memory_heaps[t.heap_index as usize].size > SMALL_BAR_HEAP_SIZE);
+
+        if large_heap {
+            Self::DirectWrite
+        } else {
+            Self::Staging
+        }
+    }
+
+    /// uniform buffer의 memory가 가져야 하는 속성
+    pub fn memory_properties(self) -> vk::MemoryPropertyFlags {
+        match self {
+            Self::DirectWrite => {
+                vk::MemoryPropertyFlags::DEVICE_LOCAL
+                    | vk::MemoryPropertyFlags::HOST_VISIBLE
+                    | vk::MemoryPropertyFlags::HOST_COHERENT
+            }
+            Self::Staging => vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        }
+    }
+
+    /// uniform buffer를 생성할 때 지정해야 하는 usage  
+    /// staging 경로는 transfer command로 복사받으므로 TRANSFER_DST가 필요함
+    pub fn buffer_usage(self) -> vk::BufferUsageFlags {
+        match self {
+            Self::DirectWrite => vk::BufferUsageFlags::UNIFORM_BUFFER,
+            Self::Staging => {
+                vk::BufferUsageFlags::UNIFORM_BUFFER | vk::BufferUsageFlags::TRANSFER_DST
+            }
+        }
+    }
+}
+
+impl fmt::Display for UniformMemoryPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DirectWrite => write!(f, "direct write to DEVICE_LOCAL | HOST_VISIBLE memory"),
+            Self::Staging => write!(f, "staging copy to DEVICE_LOCAL memory"),
+        }
+    }
+}
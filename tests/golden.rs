@@ -0,0 +1,52 @@
+//! 결과를 image로 저장할 수 있는 chapter를 실행해서 `tests/golden`의 기준 image와 비교하는 test  
+//! `HEADLESS_OUTPUT_ENV`로 결과를 저장하는 chapter는 38_screenshot과 39_headless뿐이므로 두 chapter만 test함  
+//! 다른 chapter는 결과를 저장하는 기능이 없고, 대부분 시간에 따라 model이 회전해서 frame마다 결과가 달라지므로 제외함  
+//! 39_headless 외의 chapter는 window를 만들므로 display도 필요함  
+//! Vulkan device가 필요하므로 기본적으로는 실행되지 않고, `cargo test --test golden -- --ignored`로 실행함  
+//! 기준 image는 GPU마다 조금씩 다를 수 있으므로 Vulkan device가 있는 환경에서 만들어서 commit해야 함  
+//! 기준 image를 만들거나 갱신하려면 `VK_TUTORIAL_UPDATE_GOLDEN=1`을 함께 설정함
+
+use anyhow::{anyhow, Result};
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use vulkan_tutorial::golden::{
+    check, GoldenImage, Tolerance, HEADLESS_FRAMES_ENV, HEADLESS_OUTPUT_ENV,
+};
+
+/// 결과를 저장하기 전에 그릴 frame 수  
+/// 첫 frame에서만 초기화되는 resource가 있어도 결과가 같은지 확인하기 위해 여러 frame을 그림
+const FRAMES: u32 = 3;
+
+/// chapter를 실행해서 `name`의 기준 image와 비교
+fn run_chapter(name: &str, executable: &str) -> Result<()> {
+    let output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("golden");
+    let output = output_dir.join(format!("{}.png", name));
+    // chapter는 상위 directory를 만들지 않으므로 미리 생성함
+    fs::create_dir_all(&output_dir)?;
+
+    let status = Command::new(executable)
+        .env(HEADLESS_OUTPUT_ENV, &output)
+        .env(HEADLESS_FRAMES_ENV, FRAMES.to_string())
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("`{}` exited with {}.", name, status));
+    }
+
+    let actual = GoldenImage::load(&output)?;
+    check(name, &actual, &Tolerance::default(), &output_dir)
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn headless() -> Result<()> {
+    run_chapter("39_headless", env!("CARGO_BIN_EXE_39_headless"))
+}
+
+#[test]
+#[ignore = "requires a Vulkan device and a display"]
+fn screenshot() -> Result<()> {
+    run_chapter("38_screenshot", env!("CARGO_BIN_EXE_38_screenshot"))
+}